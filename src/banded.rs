@@ -0,0 +1,476 @@
+use ndarray::prelude::*;
+
+use crate::{Real, parallel::for_each_chunk};
+
+
+/// Square banded matrix with `lower` sub-diagonals and `upper` super-diagonals
+///
+/// The matrix is stored by rows: the row `i` contains the elements of the columns
+/// from `i - lower` to `i + upper + lower`. The additional `lower` super-diagonals
+/// are reserved for the fill-in of the LU factorization with partial pivoting.
+///
+#[derive(Debug, Clone)]
+pub(crate) struct BandedMatrix<T> {
+    size: usize,
+    lower: usize,
+    upper: usize,
+    data: Array2<T>,
+}
+
+
+impl<T> BandedMatrix<T>
+    where
+        T: Real<T>
+{
+    /// Creates the zero banded matrix
+    pub(crate) fn zeros(size: usize, lower: usize, upper: usize) -> Self {
+        BandedMatrix {
+            size,
+            lower,
+            upper,
+            data: Array2::zeros((size, 2 * lower + upper + 1)),
+        }
+    }
+
+    /// Adds the value to the element `(i, j)`
+    ///
+    /// # Panics
+    ///
+    /// - If the element is out of the matrix band
+    ///
+    pub(crate) fn add(&mut self, i: usize, j: usize, value: T) {
+        assert!(i + self.lower >= j && j <= i + self.upper && i < self.size && j < self.size,
+                "The element ({}, {}) is out of the matrix band", i, j);
+
+        self.data[[i, j + self.lower - i]] += value;
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.data[[i, j + self.lower - i]]
+    }
+
+    fn get_mut(&mut self, i: usize, j: usize) -> &mut T {
+        &mut self.data[[i, j + self.lower - i]]
+    }
+
+    /// Computes LU factorization with partial pivoting
+    ///
+    /// Returns None if the matrix is singular.
+    pub(crate) fn factorize(mut self) -> Option<BandedLu<T>> {
+        let n = self.size;
+        let lower = self.lower;
+        let width = self.lower + self.upper;
+
+        let mut pivots = Vec::with_capacity(n);
+
+        for k in 0..n {
+            let last_row = (k + lower).min(n - 1);
+            let last_col = (k + width).min(n - 1);
+
+            let (pivot, pivot_value) = (k..=last_row)
+                .map(|i| (i, self.get(i, k)))
+                .fold((k, T::zero()), |acc, (i, v)| if v.abs() > acc.1.abs() { (i, v) } else { acc });
+
+            if pivot_value == T::zero() || !pivot_value.is_finite() {
+                return None
+            }
+
+            pivots.push(pivot);
+
+            // The multipliers in the columns before `k` are not swapped, the interchanges
+            // are applied in the same order while solving the system
+            if pivot != k {
+                for j in k..=last_col {
+                    let tmp = self.get(k, j);
+                    *self.get_mut(k, j) = self.get(pivot, j);
+                    *self.get_mut(pivot, j) = tmp;
+                }
+            }
+
+            for i in (k + 1)..=last_row {
+                let factor = self.get(i, k) / pivot_value;
+                *self.get_mut(i, k) = factor;
+
+                if factor == T::zero() {
+                    continue
+                }
+
+                for j in (k + 1)..=last_col {
+                    let v = self.get(k, j);
+                    *self.get_mut(i, j) -= factor * v;
+                }
+            }
+        }
+
+        Some(BandedLu { matrix: self, pivots })
+    }
+}
+
+
+/// LU factorization of the banded matrix
+#[derive(Debug, Clone)]
+pub(crate) struct BandedLu<T> {
+    matrix: BandedMatrix<T>,
+    pivots: Vec<usize>,
+}
+
+
+impl<T> BandedLu<T>
+    where
+        T: Real<T>
+{
+    /// Solves the linear system `Ax = b` for every column of `b`
+    ///
+    /// The columns are solved in parallel with `rayon` feature.
+    pub(crate) fn solve(&self, b: ArrayView2<'_, T>) -> Array2<T> {
+        let mut x = b.to_owned();
+        for_each_chunk(x.view_mut(), Axis(1), 1, |_, chunk| self.solve_inplace(chunk));
+        x
+    }
+
+    /// Solves the linear system in place for every column of `x` which contains `b`
+    fn solve_inplace(&self, mut x: ArrayViewMut2<'_, T>) {
+        let m = &self.matrix;
+        let n = m.size;
+        let width = m.lower + m.upper;
+
+        for mut col in x.axis_iter_mut(Axis(1)) {
+            for k in 0..n {
+                col.swap(k, self.pivots[k]);
+                let v = col[k];
+
+                for i in (k + 1)..=(k + m.lower).min(n - 1) {
+                    col[i] -= m.get(i, k) * v;
+                }
+            }
+
+            for k in (0..n).rev() {
+                let mut v = col[k];
+
+                for j in (k + 1)..=(k + width).min(n - 1) {
+                    v -= m.get(k, j) * col[j];
+                }
+
+                col[k] = v / m.get(k, k);
+            }
+        }
+    }
+}
+
+
+/// Symmetric banded matrix with `bandwidth` sub-diagonals and super-diagonals
+///
+/// Only the lower part of the matrix is stored by rows: the row `i` contains the elements
+/// of the columns from `i - bandwidth` to `i`.
+///
+#[derive(Debug, Clone)]
+pub(crate) struct SymmetricBandedMatrix<T> {
+    size: usize,
+    bandwidth: usize,
+    data: Array2<T>,
+}
+
+
+impl<T> SymmetricBandedMatrix<T>
+    where
+        T: Real<T>
+{
+    /// Creates the zero symmetric banded matrix
+    pub(crate) fn zeros(size: usize, bandwidth: usize) -> Self {
+        SymmetricBandedMatrix {
+            size,
+            bandwidth,
+            data: Array2::zeros((size, bandwidth + 1)),
+        }
+    }
+
+    /// Adds the value to the elements `(i, j)` and `(j, i)`
+    ///
+    /// # Panics
+    ///
+    /// - If the element is out of the matrix band
+    ///
+    pub(crate) fn add(&mut self, i: usize, j: usize, value: T) {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+
+        assert!(i <= j + self.bandwidth && i < self.size,
+                "The element ({}, {}) is out of the matrix band", i, j);
+
+        self.data[[i, j + self.bandwidth - i]] += value;
+    }
+
+    /// Returns the element `(i, j)` in the matrix band
+    pub(crate) fn get(&self, i: usize, j: usize) -> T {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+        self.data[[i, j + self.bandwidth - i]]
+    }
+
+    fn get_mut(&mut self, i: usize, j: usize) -> &mut T {
+        &mut self.data[[i, j + self.bandwidth - i]]
+    }
+
+    /// Computes LDL' factorization without pivoting
+    ///
+    /// The factorization exists for the positive definite matrices. The elements of the unit lower
+    /// triangular `L` are stored in place of the sub-diagonals and `D` is stored on the diagonal.
+    ///
+    /// Returns None if the zero pivot has been encountered.
+    pub(crate) fn factorize(mut self) -> Option<BandedLdl<T>> {
+        let n = self.size;
+        let p = self.bandwidth;
+
+        for j in 0..n {
+            let first = j.saturating_sub(p);
+
+            let d = (first..j).fold(self.get(j, j), |acc, k| {
+                let l = self.get(j, k);
+                acc - l * l * self.get(k, k)
+            });
+
+            if d == T::zero() || !d.is_finite() {
+                return None
+            }
+
+            *self.get_mut(j, j) = d;
+
+            for i in (j + 1)..=(j + p).min(n - 1) {
+                let v = (i.saturating_sub(p)..j).fold(self.get(i, j), |acc, k| {
+                    acc - self.get(i, k) * self.get(j, k) * self.get(k, k)
+                });
+
+                *self.get_mut(i, j) = v / d;
+            }
+        }
+
+        Some(BandedLdl { matrix: self })
+    }
+}
+
+
+/// LDL' factorization of the symmetric banded matrix
+#[derive(Debug, Clone)]
+pub(crate) struct BandedLdl<T> {
+    matrix: SymmetricBandedMatrix<T>,
+}
+
+
+impl<T> BandedLdl<T>
+    where
+        T: Real<T>
+{
+    /// Solves the linear system `Ax = b` for all columns of `b`
+    ///
+    /// The rows of `b` are processed at once, so the cost is linear in the size of `b`.
+    /// The chunks of the columns are solved in parallel with `rayon` feature.
+    pub(crate) fn solve(&self, b: ArrayView2<'_, T>) -> Array2<T> {
+        let mut x = b.to_owned();
+        for_each_chunk(x.view_mut(), Axis(1), 1, |_, chunk| self.solve_inplace(chunk));
+        x
+    }
+
+    /// Computes the elements of the inverse matrix in the band of the factorized matrix
+    ///
+    /// The elements are computed by Takahashi recurrence from the last row to the first one,
+    /// the cost is linear in the matrix size. The elements out of the band are not computed.
+    pub(crate) fn inverse_band(&self) -> SymmetricBandedMatrix<T> {
+        let m = &self.matrix;
+        let n = m.size;
+        let p = m.bandwidth;
+
+        let mut z = SymmetricBandedMatrix::zeros(n, p);
+
+        for i in (0..n).rev() {
+            let last = (i + p).min(n - 1);
+
+            for j in ((i + 1)..=last).rev() {
+                let v = ((i + 1)..=last).fold(T::zero(), |acc, k| acc - m.get(k, i) * z.get(k, j));
+                *z.get_mut(j, i) = v;
+            }
+
+            let v = ((i + 1)..=last).fold(T::one() / m.get(i, i), |acc, k| acc - m.get(k, i) * z.get(k, i));
+            *z.get_mut(i, i) = v;
+        }
+
+        z
+    }
+
+    /// Solves the linear system in place for all columns of `x` which contains `b`
+    fn solve_inplace(&self, mut x: ArrayViewMut2<'_, T>) {
+        let m = &self.matrix;
+        let n = m.size;
+        let p = m.bandwidth;
+
+        for i in 1..n {
+            for k in i.saturating_sub(p)..i {
+                let (head, mut tail) = x.view_mut().split_at(Axis(0), i);
+                tail.row_mut(0).scaled_add(-m.get(i, k), &head.row(k));
+            }
+        }
+
+        for i in 0..n {
+            let d = m.get(i, i);
+            x.row_mut(i).mapv_inplace(|v| v / d);
+        }
+
+        for i in (0..n.saturating_sub(1)).rev() {
+            for k in (i + 1)..=(i + p).min(n - 1) {
+                let (mut head, tail) = x.view_mut().split_at(Axis(0), k);
+                head.row_mut(i).scaled_add(-m.get(k, i), &tail.row(0));
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array2};
+    use approx::assert_abs_diff_eq;
+
+    use super::{BandedMatrix, SymmetricBandedMatrix};
+
+
+    fn banded_from_dense(a: &Array2<f64>, lower: usize, upper: usize) -> BandedMatrix<f64> {
+        let n = a.nrows();
+        let mut m = BandedMatrix::zeros(n, lower, upper);
+
+        for i in 0..n {
+            for j in i.saturating_sub(lower)..=(i + upper).min(n - 1) {
+                m.add(i, j, a[[i, j]]);
+            }
+        }
+
+        m
+    }
+
+    #[test]
+    fn test_solve_tridiagonal() {
+        let a = array![
+            [2., 1., 0., 0.],
+            [1., 4., 1., 0.],
+            [0., 1., 4., 1.],
+            [0., 0., 1., 2.],
+        ];
+        let b = array![[1., 2.], [2., 0.], [3., 1.], [4., 5.]];
+
+        let x = banded_from_dense(&a, 1, 1).factorize().unwrap().solve(b.view());
+
+        assert_abs_diff_eq!(a.dot(&x), b, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        // The zero diagonal elements require row interchanges
+        let a = array![
+            [0., 1., 2., 0., 0.],
+            [1., 0., 1., 3., 0.],
+            [4., 1., 0., 1., 2.],
+            [0., 2., 1., 0., 1.],
+            [0., 0., 3., 1., 0.],
+        ];
+        let b = array![[1.], [2.], [3.], [4.], [5.]];
+
+        let x = banded_from_dense(&a, 2, 2).factorize().unwrap().solve(b.view());
+
+        assert_abs_diff_eq!(a.dot(&x), b, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_singular() {
+        let a = array![
+            [1., 2., 0.],
+            [2., 4., 0.],
+            [0., 0., 1.],
+        ];
+
+        assert!(banded_from_dense(&a, 1, 1).factorize().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "The element (0, 2) is out of the matrix band")]
+    fn test_add_out_of_band() {
+        let mut m = BandedMatrix::<f64>::zeros(3, 1, 1);
+        m.add(0, 2, 1.);
+    }
+
+    fn symmetric_banded_from_dense(a: &Array2<f64>, bandwidth: usize) -> SymmetricBandedMatrix<f64> {
+        let n = a.nrows();
+        let mut m = SymmetricBandedMatrix::zeros(n, bandwidth);
+
+        for i in 0..n {
+            for j in i.saturating_sub(bandwidth)..=i {
+                m.add(i, j, a[[i, j]]);
+            }
+        }
+
+        m
+    }
+
+    #[test]
+    fn test_ldl_solve_pentadiagonal() {
+        let a = array![
+            [6., -4., 1., 0., 0., 0.],
+            [-4., 6., -4., 1., 0., 0.],
+            [1., -4., 6., -4., 1., 0.],
+            [0., 1., -4., 6., -4., 1.],
+            [0., 0., 1., -4., 6., -4.],
+            [0., 0., 0., 1., -4., 6.],
+        ];
+        let b = array![[1., 2.], [2., 0.], [3., 1.], [4., 5.], [0., 1.], [2., 2.]];
+
+        let x = symmetric_banded_from_dense(&a, 2).factorize().unwrap().solve(b.view());
+
+        assert_abs_diff_eq!(a.dot(&x), b, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_ldl_inverse_band() {
+        let a = array![
+            [6., -4., 1., 0., 0., 0.],
+            [-4., 7., -4., 1., 0., 0.],
+            [1., -4., 8., -4., 1., 0.],
+            [0., 1., -4., 6., -4., 1.],
+            [0., 0., 1., -4., 9., -4.],
+            [0., 0., 0., 1., -4., 6.],
+        ];
+
+        let ldl = symmetric_banded_from_dense(&a, 2).factorize().unwrap();
+        let inv = ldl.solve(Array2::eye(6).view());
+        let z = ldl.inverse_band();
+
+        for i in 0..6usize {
+            for j in i.saturating_sub(2)..=i {
+                assert_abs_diff_eq!(z.get(i, j), inv[[i, j]], epsilon = 1e-12);
+                assert_abs_diff_eq!(z.get(j, i), inv[[j, i]], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ldl_solve_single() {
+        let mut m = SymmetricBandedMatrix::<f64>::zeros(1, 2);
+        m.add(0, 0, 4.);
+
+        let x = m.factorize().unwrap().solve(array![[2., 8.]].view());
+
+        assert_abs_diff_eq!(x, array![[0.5, 2.]], epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_ldl_zero_pivot() {
+        let a = array![
+            [1., 1., 0.],
+            [1., 1., 0.],
+            [0., 0., 1.],
+        ];
+
+        assert!(symmetric_banded_from_dense(&a, 1).factorize().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "The element (3, 0) is out of the matrix band")]
+    fn test_symmetric_add_out_of_band() {
+        let mut m = SymmetricBandedMatrix::<f64>::zeros(4, 2);
+        m.add(0, 3, 1.);
+    }
+}
@@ -0,0 +1,38 @@
+/// Cross-validation criteria for data-driven selection of the smoothing parameter
+///
+/// When the criterion is set and the smoothing parameter is not set explicitly,
+/// the smoothing parameter is chosen by minimizing the cross-validation score.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array1};
+/// use csaps::{CubicSmoothingSpline, CrossValidation};
+///
+/// let x = Array1::linspace(0., 6., 25);
+/// let y = x.mapv(f64::sin) + array![
+///     0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+///     0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+///
+/// let s = CubicSmoothingSpline::new(&x, &y)
+///     .with_cross_validation(CrossValidation::Generalized)
+///     .make().unwrap();
+///
+/// println!("smooth: {:?}, GCV score: {:?}", s.smooth(), s.cv_score());
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossValidation {
+    /// Generalized cross-validation (GCV)
+    ///
+    /// The GCV score is `n * RSS / tr(I - H)^2` where `RSS` is the weighted residual
+    /// sum of squares and `H` is the influence (hat) matrix of the smoothing spline.
+    Generalized,
+
+    /// Leave-one-out cross-validation (CV)
+    ///
+    /// The CV score is `sum(w_i * (r_i / (1 - H_ii))^2) / n` where `r_i` are the residuals,
+    /// `w_i` are the weights and `H_ii` are the diagonal elements of the influence (hat) matrix.
+    LeaveOneOut,
+}
@@ -0,0 +1,41 @@
+/// End (boundary) conditions of the cubic smoothing spline
+///
+/// The condition is set for each end of the spline separately. The values of the
+/// prescribed derivatives are the same for all dimensions of multivariate data.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array1};
+/// use csaps::{CubicSmoothingSpline, EndCondition};
+///
+/// let x = array![0., 1., 2., 3.];
+/// let y = array![0., 1., 4., 9.];
+///
+/// let s = CubicSmoothingSpline::new(&x, &y)
+///     .with_smooth(1.0)
+///     .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::Clamped(6.0))
+///     .make().unwrap();
+///
+/// let dy: Array1<f64> = s.evaluate_derivative(&x, 1).unwrap();
+/// assert!((dy[0] - 0.0).abs() < 1e-10);
+/// assert!((dy[3] - 6.0).abs() < 1e-10);
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EndCondition<T> {
+    /// The second derivative is zero at the end (the default condition)
+    #[default]
+    Natural,
+
+    /// The first derivative is equal to the given value at the end
+    Clamped(T),
+
+    /// The second derivative is equal to the given value at the end
+    SecondDerivative(T),
+
+    /// The third derivative is continuous at the second (or the penultimate) data site,
+    /// so the first two (or the last two) polynomial pieces are the same polynomial
+    NotAKnot,
+}
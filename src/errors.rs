@@ -9,6 +9,10 @@ pub enum CsapsError {
     #[error("Invalid input: {0}")]
     InvalidInputData(String),
 
+    /// Any errors when the spline data is invalid, for example, when deserializing corrupted data
+    #[error("Invalid spline data: {0}")]
+    InvalidSplineData(String),
+
     /// Error occurs when reshape from 2-d representation for n-d data has failed
     #[error("Cannot reshape 2-d array with shape {input_shape:?} \
              to {}-d array with shape {output_shape:?} by axis {axis}. Error: {source}",
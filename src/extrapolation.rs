@@ -0,0 +1,45 @@
+/// Extrapolation modes for evaluating splines out of the breaks range
+///
+/// The mode defines the spline values for the data sites `xi < x1` and `xi > xN`
+/// where `x1` and `xN` are the first and the last breaks of the spline.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use csaps::{CubicSmoothingSpline, Extrapolation};
+///
+/// let x = array![1., 2., 3., 4.];
+/// let y = array![1., 3., 2., 4.];
+/// let xi = array![0., 2.5, 5.];
+///
+/// let yi = CubicSmoothingSpline::new(&x, &y)
+///     .with_smooth(1.0)
+///     .with_extrapolation(Extrapolation::Constant)
+///     .make().unwrap()
+///     .evaluate(&xi).unwrap();
+///
+/// assert_eq!(yi[0], 1.);
+/// assert_eq!(yi[2], 4.);
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// Extrapolates the first and the last polynomial pieces (the default mode)
+    #[default]
+    Polynomial,
+
+    /// Continues the spline linearly using the spline values and slopes at the boundaries
+    Linear,
+
+    /// Clamps the spline values to the spline values at the boundaries
+    Constant,
+
+    /// Fills the values out of the breaks range with NaN
+    Nan,
+
+    /// Returns `CsapsError::InvalidInputData` error if any data site is out of the breaks range
+    Error,
+}
+
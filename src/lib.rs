@@ -3,9 +3,9 @@
 //! csaps is a crate for univariate, multivariate and n-dimensional grid data interpolation and approximation
 //! using cubic smoothing splines.
 //!
-//! The crate provides functionality for computing and evaluating cubic smoothing splines.
-//! It does not contain any spline analysis functions. Therefore, the package can be useful in
-//! practical engineering tasks for data approximation and smoothing.
+//! The crate provides functionality for computing and evaluating cubic smoothing splines
+//! and their derivatives and integrals. Therefore, the package can be useful in practical engineering tasks
+//! for data approximation and smoothing.
 //!
 //! # Algorithm and Implementation
 //!
@@ -25,7 +25,23 @@
 //! - n-dimensional grid data (a surface or volume for example) smoothing
 //! - weighted smoothing
 //! - automatic smoothing (automatic computing the smoothing parameter)
-//! - computing natural cubic spline interpolant when smoothing parameter is equal to one
+//! - data-driven selection of the smoothing parameter by generalized or leave-one-out cross-validation
+//! - robust smoothing with Huber or Tukey bisquare weights by iteratively reweighted least squares
+//! - computing cubic spline interpolant when smoothing parameter is equal to one
+//! - natural, clamped, prescribed second derivative and not-a-knot end conditions
+//! - periodic splines for cyclic data
+//! - ignoring missing (NaN) values in the data
+//! - pointwise standard errors and confidence and prediction bands of the smoothed data
+//! - fit diagnostics: residuals, influence matrix diagonal, effective degrees of freedom and GCV score
+//! - evaluating derivatives of the computed splines
+//! - computing antiderivatives and definite integrals of the computed splines
+//! - configurable extrapolation out of the data sites range
+//! - parametric curves smoothing with uniform, chord-length or centripetal parameterization
+//!   and arc-length reparameterization
+//! - arc length, curvature, torsion and unit tangent and normal vectors of the curves represented
+//!   by multivariate splines
+//! - serialization of the computed splines with `serde` (optional `serde` feature)
+//! - parallel solving, fitting and evaluating with `rayon` (optional `rayon` feature)
 //!
 //! # Quick Examples
 //!
@@ -110,21 +126,52 @@
 //! but not a slice of `AsArray` array-like because `ndarray::Array` does not implement `AsRef` trait
 //! currently. In the future we might be able to support `AsArray` in n-dimensional grid data case.
 //!
-//! # Performance Issues
-//!
-//! Currently, the performance of computation of smoothing splines might be very low for a large data.
-//!
-//! The algorithm of sparse matrices mutliplication in sprs crate is not optimized for large diagonal
-//! matrices which causes a poor performance of computation of smoothing splines.
-//! See [issue](https://github.com/vbarrielle/sprs/issues/184) for details.
+//! # Performance
+//!
+//! The pentadiagonal system of the smoothing spline is factorized once by banded LDL' factorization
+//! and solved for all data dimensions at once, so computing the spline is linear in the number
+//! of the data sites. `CubicSmoother` keeps the factorized system for the given data sites, weights
+//! and smoothing parameter, so it computes the splines for many `y` arrays without rebuilding the system.
+//!
+//! Evaluating the spline finds the polynomial pieces by the binary search starting from the piece
+//! of the previous data site, so it is linear in the number of the evaluated data sites for sorted data sites
+//! or uniformly spaced breaks, and the data sites may be in any order. The `evaluate_into` methods
+//! write the spline values into the caller-provided arrays, so the spline can be evaluated repeatedly
+//! without allocating (`NdGridSpline::evaluate_into_with` reuses the scratch buffers of `NdGridWorkspace`
+//! for n-d grid splines). `NdSpline::eval_at` and `NdSpline::eval_iter` evaluate the spline at the single
+//! data sites and the streams of data sites without allocating.
+//!
+//! With the optional `rayon` feature the columns of the linear systems, the data series of `CubicSmoother`
+//! (and so the lines of every axis of the grid splines) and the chunks of the evaluated data sites are
+//! processed in parallel. Every column and data site is computed by the same operations as in the serial
+//! code, so the results are identical to the results without `rayon` feature.
+//!
+//! The cyclic system of the periodic spline is solved by sparse LDL' factorization from
+//! [sprs-ldl](https://docs.rs/sprs-ldl) crate. The selection of the smoothing parameter
+//! by cross-validation or degrees of freedom and the fit diagnostics compute the influence
+//! matrix diagonal from the banded factorization in linear time (quadratic for the periodic
+//! spline and for the diagnostics of the splines with the end conditions or the missing values).
+//! The confidence bands compute the smoother matrix columns by the chunks from the factorized
+//! system, so their memory is linear, but their cost is quadratic in the number of the data sites.
 //!
 
 mod errors;
+mod extrapolation;
+mod end_condition;
+mod missing_values;
+mod parameterization;
+mod cross_validation;
+mod robust;
 mod traits;
 mod ndarrayext;
 mod sprsext;
+mod banded;
+mod parallel;
+mod search;
 mod validate;
 mod util;
+#[cfg(feature = "serde")]
+mod serialize;
 mod umv;
 mod ndg;
 
@@ -134,9 +181,19 @@ use std::result;
 pub type Result<T> = result::Result<T, errors::CsapsError>;
 
 pub use errors::CsapsError;
+pub use extrapolation::Extrapolation;
+pub use end_condition::EndCondition;
+pub use missing_values::MissingValues;
+pub use parameterization::Parameterization;
+pub use cross_validation::CrossValidation;
+pub use robust::RobustLoss;
 pub use traits::{Real, RealRef};
-pub use umv::{NdSpline, CubicSmoothingSpline};
-pub use ndg::{NdGridSpline, GridCubicSmoothingSpline};
+pub use umv::{NdSpline, CubicSmoothingSpline, CubicSmoother, CubicSmoothingCurve, SmoothingBands, FitDiagnostics};
+pub use ndg::{NdGridSpline, NdGridWorkspace, GridCubicSmoothingSpline};
+#[cfg(feature = "serde")]
+pub use umv::NdSplineData;
+#[cfg(feature = "serde")]
+pub use ndg::NdGridSplineData;
 
 
 // #[cfg(test)]
@@ -0,0 +1,46 @@
+/// Handling modes of the missing (NaN) values in Y-data
+///
+/// The missing values are ignored by the zero weights, so the spline is still defined over
+/// the whole data sites range: the spline is smoothly continued through the gaps and it is linear
+/// between the end of the data sites range and the nearest not missing value for the natural
+/// end conditions.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array1};
+/// use csaps::{CubicSmoothingSpline, MissingValues};
+///
+/// let x = array![1., 2., 3., 4., 5.];
+/// let y = array![1., 2., f64::NAN, 4., 5.];
+///
+/// let yi: Array1<f64> = CubicSmoothingSpline::new(&x, &y)
+///     .with_smooth(1.0)
+///     .with_missing_values(MissingValues::Ignore)
+///     .make().unwrap()
+///     .evaluate(&x).unwrap();
+///
+/// assert!((yi[2] - 3.).abs() < 1e-12);
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingValues {
+    /// Ignores the data sites where any of Y-data values is missing (the default mode)
+    ///
+    /// For multivariate data the data site is ignored for all data series if the value
+    /// is missing in any of them.
+    #[default]
+    Ignore,
+
+    /// Ignores the missing values for every data series of multivariate data separately
+    ///
+    /// The data series (the 1-d lanes of Y-data along the `axis`) may have different gaps,
+    /// every series is computed with its own zero weights and the same smoothing parameter.
+    /// The smoothing parameter cannot be selected by the residual tolerance or cross-validation
+    /// in this mode because the data series residuals are not comparable.
+    PerSeries,
+
+    /// Returns `CsapsError::InvalidInputData` error if Y-data contains any missing values
+    Error,
+}
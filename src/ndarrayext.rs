@@ -1,9 +1,7 @@
 
 
-use almost::AlmostEqual;
 // use almost;
-use ndarray::{prelude::*, IntoDimension, Slice};
-use itertools::Itertools;
+use ndarray::{prelude::*, IntoDimension, RemoveAxis, Slice};
 
 
 use crate::{
@@ -13,9 +11,9 @@ use crate::{
 };
 
 
-pub fn diff<'a, T: 'a, D, V>(data: V, axis: Option<Axis>) -> Array<T, D>
+pub fn diff<'a, T, D, V>(data: V, axis: Option<Axis>) -> Array<T, D>
     where
-        T: Real<T>,
+        T: Real<T> + 'a,
         // T: Clone + NdFloat,
         D: Dimension,
         V: AsArray<'a, T, D>
@@ -30,8 +28,35 @@ pub fn diff<'a, T: 'a, D, V>(data: V, axis: Option<Axis>) -> Array<T, D>
 }
 
 
-pub fn to_2d<'a, T: 'a, D, I>(data: I, axis: Axis) -> Result<ArrayView2<'a, T>>
+/// Rolls the array elements along the given axis
+///
+/// The elements that roll beyond the last position are re-introduced at the first
+/// as `numpy.roll` does, so `roll(a, 1, axis)[i] == a[i - 1]` with the cyclic index.
+pub fn roll<'a, T, D, V>(data: V, shift: isize, axis: Axis) -> Array<T, D>
+    where
+        T: Clone + 'a,
+        D: Dimension + RemoveAxis,
+        V: AsArray<'a, T, D>
+{
+    let data_view = data.into();
+    let size = data_view.len_of(axis) as isize;
+
+    if size == 0 {
+        return data_view.to_owned()
+    }
+
+    let split = size - shift.rem_euclid(size);
+
+    let head = data_view.slice_axis(axis, Slice::from(split..));
+    let tail = data_view.slice_axis(axis, Slice::from(..split));
+
+    ndarray::concatenate(axis, &[head, tail]).unwrap()
+}
+
+
+pub fn to_2d<'a, T, D, I>(data: I, axis: Axis) -> Result<CowArray<'a, T, Ix2>>
     where
+        T: Clone + 'a,
         D: Dimension,
         I: AsArray<'a, T, D>,
 {
@@ -51,8 +76,20 @@ pub fn to_2d<'a, T: 'a, D, I>(data: I, axis: Axis) -> Result<ArrayView2<'a, T>>
     let axis_size = shape[axis.0];
     let new_shape = [numel / axis_size, axis_size];
 
-    match data_view.permuted_axes(axes).into_shape(new_shape) {
-        Ok(view_2d) => Ok(view_2d),
+    let permuted_view = data_view.permuted_axes(axes);
+
+    // The permuted view can be reshaped without copying only if it has standard memory layout
+    // (or it is at most 2-d, so reshaping does not depend on the order), otherwise we should copy
+    // the data to standard layout. Reshaping n-d view with Fortran layout is possible,
+    // but it reshapes the data in Fortran order
+    if ndim <= 2 || permuted_view.is_standard_layout() {
+        if let Ok(view_2d) = permuted_view.clone().into_shape(new_shape) {
+            return Ok(view_2d.into())
+        }
+    }
+
+    match permuted_view.as_standard_layout().into_owned().into_shape(new_shape) {
+        Ok(array_2d) => Ok(array_2d.into()),
         Err(error) => Err(
             ReshapeTo2d {
                 input_shape: shape,
@@ -65,16 +102,24 @@ pub fn to_2d<'a, T: 'a, D, I>(data: I, axis: Axis) -> Result<ArrayView2<'a, T>>
 }
 
 
-pub fn to_2d_simple<'a, T: 'a, D>(data: ArrayView<'a, T, D>) -> Result<ArrayView2<'a, T>>
+pub fn to_2d_simple<'a, T, D>(data: ArrayView<'a, T, D>) -> Result<CowArray<'a, T, Ix2>>
     where
+        T: Clone + 'a,
         D: Dimension
 {
     let ndim = data.ndim();
     let shape = data.shape().to_vec();
     let new_shape = [shape[0..(ndim - 1)].iter().product(), shape[ndim - 1]];
 
-    match data.into_shape(new_shape) {
-        Ok(data_2d) => Ok(data_2d),
+    // The data should be reshaped in C order, so n-d data with non-standard layout is copied
+    if ndim <= 2 || data.is_standard_layout() {
+        if let Ok(view_2d) = data.clone().into_shape(new_shape) {
+            return Ok(view_2d.into())
+        }
+    }
+
+    match data.as_standard_layout().into_owned().into_shape(new_shape) {
+        Ok(array_2d) => Ok(array_2d.into()),
         Err(error) => Err(
             ReshapeTo2d {
                 input_shape: shape,
@@ -124,50 +169,9 @@ pub fn from_2d<'a, T: 'a, D, S, I>(data: I, shape: S, axis: Axis) -> Result<Arra
 }
 
 
-/// Returns the indices of the bins to which each value in input array belongs
-///
-/// This code works if `bins` is increasing
-pub fn digitize<'a, T: 'a, A, B>(arr: A, bins: B) -> Array1<usize>
-    where
-        T: Real<T>,
-        // T: Clone  + NdFloat + AlmostEqual,
-
-        A: AsArray<'a, T, Ix1>,
-        B: AsArray<'a, T, Ix1>,
-{
-    let arr_view = arr.into();
-    let bins_view = bins.into();
-
-    let mut indices = Array1::zeros((arr_view.len(),));
-    let mut kstart: usize = 0;
-
-    for (i, &a) in arr_view.iter().enumerate()
-        .sorted_by(|e1, e2| e1.1.partial_cmp(e2.1).unwrap()) {
-
-        let mut k = kstart;
-
-        for bins_win in bins_view.slice(s![kstart..]).windows(2) {
-            let bl = bins_win[0];
-            let br = bins_win[1];
-
-            if (a > bl || almost::equal(a, bl)) && a < br {
-                indices[i] = k;
-                kstart = k;
-                break;
-            }
-
-            k += 1;
-        }
-    }
-
-    indices
-}
-
-
 #[cfg(test)]
 mod tests {
-    use std::f64;
-    use ndarray::{array, Array1, Axis, Ix1, Ix2, Ix3};
+    use ndarray::{array, Axis, Ix1, Ix2, Ix3};
     use crate::ndarrayext::*;
 
     #[test]
@@ -212,6 +216,24 @@ mod tests {
                    array![[[1., 1.], [1., 1.]], [[1., 1.], [1., 1.]]]);
     }
 
+    #[test]
+    fn test_roll_1d() {
+        let a = array![1., 2., 3., 4., 5.];
+
+        assert_eq!(roll(&a, 1, Axis(0)), array![5., 1., 2., 3., 4.]);
+        assert_eq!(roll(&a, -1, Axis(0)), array![2., 3., 4., 5., 1.]);
+        assert_eq!(roll(&a, 7, Axis(0)), array![4., 5., 1., 2., 3.]);
+        assert_eq!(roll(&a, 0, Axis(0)), a);
+    }
+
+    #[test]
+    fn test_roll_2d() {
+        let a = array![[1., 2., 3.], [4., 5., 6.]];
+
+        assert_eq!(roll(&a, 1, Axis(1)), array![[3., 1., 2.], [6., 4., 5.]]);
+        assert_eq!(roll(&a, -1, Axis(0)), array![[4., 5., 6.], [1., 2., 3.]]);
+    }
+
     #[test]
     fn test_to_2d_from_1d() {
         let a = array![1, 2, 3, 4];
@@ -231,9 +253,8 @@ mod tests {
     fn test_to_2d_from_3d() {
         let a = array![[[1, 2, 3], [4, 5, 6]], [[7, 8, 9], [10, 11, 12]]];
 
-        // FIXME: incompatible memory layout
-        // assert_eq!(to_2d(&a, Axis(0)).unwrap(), array![[1, 7], [2, 8], [3, 9], [4, 10], [5, 11], [6, 12]]);
-        // assert_eq!(to_2d(&a, Axis(1)).unwrap(), array![[1, 4], [2, 5], [3, 6], [7, 10], [8, 11], [9, 12]]);
+        assert_eq!(to_2d(&a, Axis(0)).unwrap(), array![[1, 7], [2, 8], [3, 9], [4, 10], [5, 11], [6, 12]]);
+        assert_eq!(to_2d(&a, Axis(1)).unwrap(), array![[1, 4], [2, 5], [3, 6], [7, 10], [8, 11], [9, 12]]);
         assert_eq!(to_2d(&a, Axis(2)).unwrap(), array![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]);
     }
 
@@ -255,6 +276,16 @@ mod tests {
         assert_eq!(to_2d_simple(a.view()).unwrap(), array![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]);
     }
 
+    #[test]
+    fn test_to_2d_simple_from_3d_fortran_layout() {
+        let a = array![[[1, 2, 3], [4, 5, 6]], [[7, 8, 9], [10, 11, 12]]];
+        let a_f = a.t().as_standard_layout().into_owned().reversed_axes();
+
+        assert_eq!(a_f, a);
+        assert_eq!(to_2d_simple(a_f.view()).unwrap(), array![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]);
+        assert_eq!(to_2d(&a_f, Axis(2)).unwrap(), array![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]);
+    }
+
     #[test]
     fn test_from_2d_to_3d() {
         let a = array![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]];
@@ -326,74 +357,4 @@ mod tests {
 
         assert_eq!(a, e);
     }
-
-    #[test]
-    fn test_digitize_1() {
-        let xi = Array1::<f64>::linspace(1., 5., 9);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![0, 0, 1, 1, 2, 2, 3, 3, 3])
-    }
-
-    #[test]
-    fn test_digitize_2() {
-        let xi = Array1::<f64>::linspace(0., 7., 15);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 4, 4, 4])
-    }
-
-    #[test]
-    fn test_digitize_3() {
-        let xi = Array1::<f64>::linspace(1.5, 4.5, 7);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![0, 1, 1, 2, 2, 3, 3])
-    }
-
-    #[test]
-    fn test_digitize_4() {
-        let xi = Array1::<f64>::linspace(1.5, 4.5, 13);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3])
-    }
-
-    #[test]
-    fn test_digitize_5() {
-        let xi = Array1::<f64>::linspace(2.5, 4.5, 17);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3])
-    }
-
-    #[test]
-    fn test_digitize_6() {
-        let xi = Array1::<f64>::linspace(2.5, 8.5, 13);
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![1, 2, 2, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4])
-    }
-
-    #[test]
-    fn test_digitize_not_increased() {
-        let xi = array![1., 2., 1., 3., 3., 2., 1., 4., 5., 5., 4., 4., 3., 3., 2., 1.];
-        let edges = array![f64::NEG_INFINITY, 2., 3., 4., 5., f64::INFINITY];
-
-        let indices = digitize(&xi, &edges);
-
-        assert_eq!(indices, array![0, 1, 0, 2, 2, 1, 0, 3, 4, 4, 3, 3, 2, 2, 1, 0])
-    }
 }
@@ -1,7 +1,16 @@
 mod validate;
 mod make;
 mod evaluate;
+mod derivative;
 mod util;
+mod workspace;
+#[cfg(feature = "serde")]
+mod serialize;
+
+pub use self::workspace::NdGridWorkspace;
+
+#[cfg(feature = "serde")]
+pub use self::serialize::NdGridSplineData;
 
 use ndarray::{
     Dimension,
@@ -9,9 +18,18 @@ use ndarray::{
     Array,
     ArrayView,
     ArrayView1,
+    ArrayView2,
+    ArrayViewMut,
+    Array1,
+    Ix2,
 };
 
-use crate::{Real, Result, RealRef};
+use crate::{
+    CrossValidation, Extrapolation, Real, Result, RealRef,
+    validate::validate_output_shape,
+};
+
+use self::validate::validate_xi_count;
 
 
 /// N-d grid spline PP-form representation
@@ -23,8 +41,11 @@ use crate::{Real, Result, RealRef};
 /// Also `evaluate` method is implemented for `NdGridSpline` for evaluating the values
 /// for given data sites.
 ///
-#[derive(Debug)]
-pub struct NdGridSpline<'a, T, D>
+/// `NdGridSpline` owns its breaks and coefficients, so it does not borrow the input data and
+/// can be stored, returned from functions or sent to other threads.
+///
+#[derive(Debug, Clone)]
+pub struct NdGridSpline<T, D>
     where
         T: Real<T>,
         D: Dimension
@@ -39,15 +60,21 @@ pub struct NdGridSpline<'a, T, D>
     pieces: Vec<usize>,
 
     /// The breaks (data sites for each grid dimension) which have been used for computing spline
-    breaks: Vec<ArrayView1<'a, T>>,
+    breaks: Vec<Array1<T>>,
 
     /// N-d array of the tensor-product univariate spline coefficients as
     /// representation of n-d grid spline coefficients
     coeffs: Array<T, D>,
+
+    /// The smoothing parameters for each dimension which have been used for computing spline
+    smooth: Vec<Option<T>>,
+
+    /// The flags of the periodic spline for each dimension
+    periodic: Vec<bool>,
 }
 
 
-impl<'a, T, D> NdGridSpline<'a, T, D>
+impl<T, D> NdGridSpline<T, D>
     where
         T: Real<T>,
         D: Dimension
@@ -56,14 +83,16 @@ impl<'a, T, D> NdGridSpline<'a, T, D>
     ///
     /// # Arguments
     ///
-    /// - `breaks` -- The vector of the breaks (data sites) which have been used for computing spline
+    /// - `breaks` -- The vector of the breaks (data sites) which have been used for computing spline.
+    ///   The breaks are copied to the owned arrays.
     /// - `coeffs` -- The n-d array of tensor-product spline coefficients
     ///
     /// # Notes
     ///
     /// - `NdGridSpline` struct should not be created directly by a user in most cases.
     ///
-    pub fn new(breaks: Vec<ArrayView1<'a, T>>, coeffs: Array<T, D>) -> Self {
+    pub fn new(breaks: Vec<ArrayView1<'_, T>>, coeffs: Array<T, D>) -> Self {
+        let breaks: Vec<Array1<T>> = breaks.iter().map(|x| x.to_owned()).collect();
         let ndim = breaks.len();
         let pieces: Vec<usize> = breaks.iter().map(|x| x.len() - 1).collect();
         let order: Vec<usize> = pieces.iter().zip(coeffs.shape().iter()).map(|(p, s)| s / p).collect();
@@ -74,6 +103,8 @@ impl<'a, T, D> NdGridSpline<'a, T, D>
             pieces,
             breaks,
             coeffs,
+            smooth: vec![None; ndim],
+            periodic: vec![false; ndim],
         }
     }
 
@@ -86,15 +117,167 @@ impl<'a, T, D> NdGridSpline<'a, T, D>
     /// Returns the vector of the number of pieces of the spline for each dimension
     pub fn pieces(&self) -> &Vec<usize> { &self.pieces }
 
-    /// Returns the vector of views to the breaks for each dimension
-    pub fn breaks(&self) -> &Vec<ArrayView1<'a, T>> { &self.breaks }
+    /// Returns the vector of the breaks for each dimension
+    pub fn breaks(&self) -> &Vec<Array1<T>> { &self.breaks }
 
     /// Returns the view to the spline coefficients array
     pub fn coeffs(&self) -> ArrayView<'_, T, D> { self.coeffs.view() }
 
+    /// Returns the smoothing parameters for each dimension which have been used for computing spline
+    ///
+    /// The smoothing parameters are None if the spline has been created directly by `new`.
+    pub fn smooth(&self) -> &Vec<Option<T>> { &self.smooth }
+
+    /// Returns the flags of the periodic spline for each dimension
+    ///
+    /// The data sites along the periodic dimensions are wrapped into the breaks range while evaluating.
+    pub fn periodic(&self) -> &Vec<bool> { &self.periodic }
+
     /// Evaluates the spline on the given data sites
-    pub fn evaluate(&self, xi: &'a [ArrayView1<'a, T>]) -> Array<T, D> {
-        self.evaluate_spline(xi)
+    pub fn evaluate(&self, xi: &[ArrayView1<'_, T>]) -> Array<T, D> {
+        // Polynomial extrapolation cannot fail
+        self.evaluate_spline(xi, &vec![0; self.ndim], Extrapolation::Polynomial).unwrap()
+    }
+
+    /// Evaluates the spline on the given data sites with the given extrapolation mode
+    ///
+    /// # Errors
+    ///
+    /// - If `extrapolation` is `Extrapolation::Error` and any data site is out of the breaks range
+    ///
+    pub fn evaluate_extrapolated(&self, xi: &[ArrayView1<'_, T>], extrapolation: Extrapolation) -> Result<Array<T, D>> {
+        self.evaluate_spline(xi, &vec![0; self.ndim], extrapolation)
+    }
+
+    /// Evaluates the spline on the given data sites into the given output array
+    ///
+    /// `out` must have shape `[xi[0].len(), xi[1].len(), ...]`. The values are written into `out`
+    /// directly, only the scratch buffers for the dimensions after the first are allocated for every call.
+    /// Use `evaluate_into_with` for reusing the buffers between the calls.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `xi` vectors is not equal to the grid dimensionality
+    /// - If the shape of `out` is invalid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Array2};
+    /// use csaps::GridCubicSmoothingSpline;
+    ///
+    /// let x0 = array![1.0, 2.0, 3.0];
+    /// let x1 = array![1.0, 2.0, 3.0, 4.0];
+    /// let y = array![
+    ///     [0.5, 1.2, 3.4, 2.5],
+    ///     [1.5, 2.2, 4.4, 3.5],
+    ///     [2.5, 3.2, 5.4, 4.5],
+    /// ];
+    ///
+    /// let s = GridCubicSmoothingSpline::new(&[x0.view(), x1.view()], &y).make().unwrap();
+    /// let spline = s.spline().unwrap();
+    ///
+    /// let xi0 = Array1::linspace(1., 3., 5);
+    /// let xi1 = Array1::linspace(1., 4., 7);
+    /// let mut yi = Array2::<f64>::zeros((5, 7));
+    ///
+    /// spline.evaluate_into(&[xi0.view(), xi1.view()], &mut yi).unwrap();
+    ///
+    /// assert_eq!(yi, spline.evaluate(&[xi0.view(), xi1.view()]));
+    /// ```
+    ///
+    pub fn evaluate_into<'b, O>(&self, xi: &[ArrayView1<'_, T>], out: O) -> Result<()>
+        where
+            O: Into<ArrayViewMut<'b, T, D>>,
+            T: 'b
+    {
+        self.evaluate_into_with(xi, &mut NdGridWorkspace::new(), out)
+    }
+
+    /// Evaluates the spline on the given data sites into the given output array with the given workspace
+    ///
+    /// `out` must have shape `[xi[0].len(), xi[1].len(), ...]`. The scratch buffers of `workspace`
+    /// are sized on the first evaluation for the grid shape and are reused, so the spline is evaluated
+    /// on the grids of the same shape many times without allocating.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `xi` vectors is not equal to the grid dimensionality
+    /// - If the shape of `out` is invalid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Array2};
+    /// use csaps::{GridCubicSmoothingSpline, NdGridWorkspace};
+    ///
+    /// let x0 = array![1.0, 2.0, 3.0];
+    /// let x1 = array![1.0, 2.0, 3.0, 4.0];
+    /// let y = array![
+    ///     [0.5, 1.2, 3.4, 2.5],
+    ///     [1.5, 2.2, 4.4, 3.5],
+    ///     [2.5, 3.2, 5.4, 4.5],
+    /// ];
+    ///
+    /// let s = GridCubicSmoothingSpline::new(&[x0.view(), x1.view()], &y).make().unwrap();
+    /// let spline = s.spline().unwrap();
+    ///
+    /// let mut workspace = NdGridWorkspace::new();
+    /// let mut yi = Array2::<f64>::zeros((5, 7));
+    ///
+    /// for offset in [0.0, 0.5] {
+    ///     let xi0 = Array1::linspace(1., 3., 5) + offset;
+    ///     let xi1 = Array1::linspace(1., 4., 7) + offset;
+    ///
+    ///     spline.evaluate_into_with(&[xi0.view(), xi1.view()], &mut workspace, &mut yi).unwrap();
+    ///
+    ///     assert_eq!(yi, spline.evaluate(&[xi0.view(), xi1.view()]));
+    /// }
+    /// ```
+    ///
+    pub fn evaluate_into_with<'b, O>(&self, xi: &[ArrayView1<'_, T>], workspace: &mut NdGridWorkspace<T>, out: O) -> Result<()>
+        where
+            O: Into<ArrayViewMut<'b, T, D>>,
+            T: 'b
+    {
+        let out = out.into();
+
+        validate_xi_count(self.ndim, xi.len())?;
+
+        validate_output_shape(out.shape(), xi.iter().map(|xi_ax| xi_ax.len()))?;
+
+        let NdGridWorkspace { orders, coeffs, values } = workspace;
+
+        // Only the zeros are ever written into the orders buffer
+        if orders.len() < self.ndim {
+            orders.resize(self.ndim, 0);
+        }
+
+        self.evaluate_spline_into_with(xi, &orders[..self.ndim], Extrapolation::Polynomial, coeffs, values, out)
+    }
+
+    /// Evaluates the spline at the scattered points
+    ///
+    /// `points` is `(npoints, ndim)` array of the points coordinates. Returns 1-d array of
+    /// the spline values with `npoints` size.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `points` columns is not equal to the grid dimensionality
+    ///
+    pub fn evaluate_points(&self, points: ArrayView2<'_, T>) -> Result<Array1<T>> {
+        self.evaluate_points_spline(points, &vec![0; self.ndim], Extrapolation::Polynomial)
+    }
+
+    /// Evaluates the spline at the scattered points with the given extrapolation mode
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `points` columns is not equal to the grid dimensionality
+    /// - If `extrapolation` is `Extrapolation::Error` and any point is out of the breaks range
+    ///
+    pub fn evaluate_points_extrapolated(&self, points: ArrayView2<'_, T>, extrapolation: Extrapolation) -> Result<Array1<T>> {
+        self.evaluate_points_spline(points, &vec![0; self.ndim], extrapolation)
     }
 }
 
@@ -149,8 +332,26 @@ pub struct GridCubicSmoothingSpline<'a, T, D>
     /// The optional smoothing parameter
     smooth: Vec<Option<T>>,
 
+    /// The smoothing parameters which have been used for computing the spline
+    selected_smooth: Option<Vec<Option<T>>>,
+
+    /// The optional cross-validation criteria for selecting the smoothing parameters
+    cross_validation: Vec<Option<CrossValidation>>,
+
+    /// The cross-validation scores for the selected smoothing parameters
+    cv_scores: Vec<Option<T>>,
+
+    /// The flag of the normalized smoothing parameters mode
+    normalized_smooth: bool,
+
+    /// The flags of the periodic spline for each dimension
+    periodic: Vec<bool>,
+
+    /// The extrapolation mode for evaluating the spline out of the data sites range
+    extrapolation: Extrapolation,
+
     /// `NdGridSpline` struct with computed spline
-    spline: Option<NdGridSpline<'a, T, D>>
+    spline: Option<NdGridSpline<T, D>>
 }
 
 
@@ -180,6 +381,12 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
             y: y.into(),
             weights: vec![None; ndim],
             smooth: vec![None; ndim],
+            selected_smooth: None,
+            cross_validation: vec![None; ndim],
+            cv_scores: vec![None; ndim],
+            normalized_smooth: false,
+            periodic: vec![false; ndim],
+            extrapolation: Extrapolation::default(),
             spline: None,
         }
     }
@@ -242,6 +449,91 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
         self
     }
 
+    /// Sets the cross-validation criteria for selecting the smoothing parameters for each dimension
+    ///
+    /// # Arguments
+    ///
+    /// - `cv` - the slice of optional cross-validation criteria for each dimension
+    ///
+    /// # Notes
+    ///
+    /// The smoothing parameter is selected by cross-validation only for the dimensions
+    /// for which the criterion is set and the smoothing parameter is `None`. The selected
+    /// smoothing parameters and the scores can be got by `smooth` and `cv_scores` methods
+    /// after making the spline.
+    ///
+    pub fn with_cross_validation(mut self, cv: &[Option<CrossValidation>]) -> Self {
+        self.invalidate();
+        self.cross_validation = cv.to_vec();
+        self
+    }
+
+    /// Sets the normalized smoothing parameters mode
+    ///
+    /// In the normalized mode the smoothing parameters are invariant to the data sites range
+    /// and density for each dimension. See `CubicSmoothingSpline::with_normalized_smooth`
+    /// for details.
+    ///
+    pub fn with_normalized_smooth(mut self, normalized_smooth: bool) -> Self {
+        self.invalidate();
+        self.normalized_smooth = normalized_smooth;
+        self
+    }
+
+    /// Sets the periodic spline mode for each dimension
+    ///
+    /// The spline is periodic along the dimensions for which the flag is `true` and it is evaluated
+    /// with wrapping the data sites into the period along these dimensions instead of extrapolating.
+    /// See `CubicSmoothingSpline::with_periodic` for details.
+    ///
+    /// # Arguments
+    ///
+    /// - `periodic` - the slice of the periodic flags for each dimension
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Array2};
+    /// use std::f64::consts::PI;
+    /// use csaps::GridCubicSmoothingSpline;
+    ///
+    /// let x0 = array![0.0, 1.0, 2.0];
+    /// let x1 = Array1::linspace(0., 2. * PI, 9);
+    /// let x = vec![x0.view(), x1.view()];
+    ///
+    /// let y = Array2::from_shape_fn((3, 9), |(i, j)| i as f64 + x1[j].cos());
+    ///
+    /// let s = GridCubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth_fill(1.0)
+    ///     .with_periodic(&[false, true])
+    ///     .make().unwrap();
+    ///
+    /// let points = array![[0.5, 1.0], [0.5, 1.0 + 2. * PI]];
+    /// let values = s.evaluate_points(&points).unwrap();
+    ///
+    /// assert!((values[0] - values[1]).abs() < 1e-12);
+    /// ```
+    ///
+    pub fn with_periodic(mut self, periodic: &[bool]) -> Self {
+        self.invalidate();
+        self.periodic = periodic.to_vec();
+        self
+    }
+
+    /// Sets the extrapolation mode
+    ///
+    /// The extrapolation mode defines the spline values for the data sites out of
+    /// the `x` data sites range along every axis. By default the first and the last
+    /// polynomial pieces are extrapolated (`Extrapolation::Polynomial`).
+    ///
+    /// The extrapolation mode does not affect the computed spline, so the spline
+    /// is not invalidated.
+    ///
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
     /// Makes (computes) the n-dimensional grid spline for given data and parameters
     ///
     /// # Errors
@@ -262,26 +554,115 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
     ///
     /// - If the `xi` data is invalid
     /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
     ///
     pub fn evaluate(&self, xi: &[ArrayView1<'a, T>]) -> Result<Array<T, D>> {
-        self.evaluate_validate(&xi)?;
-        let yi = self.evaluate_spline(&xi);
+        self.evaluate_validate(xi)?;
+        let yi = self.evaluate_spline(xi, &vec![0; self.x.len()])?;
 
         Ok(yi)
     }
 
-    /// Returns the ref to smoothing parameters vector or None
+    /// Evaluates the computed n-dimensional grid spline at the scattered points
+    ///
+    /// Unlike `evaluate` method, the spline is not evaluated on the mesh, but at the arbitrary
+    /// points, so this method is useful for evaluating the spline at the large number of points.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` -- `(npoints, ndim)` 2-d array-like of the points coordinates
+    ///
+    /// Returns 1-d array of the spline values with `npoints` size.
+    ///
+    /// # Errors
+    ///
+    /// - If the `points` data is invalid
+    /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and any point is out of the data sites range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::GridCubicSmoothingSpline;
+    ///
+    /// let x0 = array![1.0, 2.0, 3.0];
+    /// let x1 = array![1.0, 2.0, 3.0, 4.0];
+    /// let x = vec![x0.view(), x1.view()];
+    ///
+    /// let y = array![
+    ///     [0.5, 1.2, 3.4, 2.5],
+    ///     [1.5, 2.2, 4.4, 3.5],
+    ///     [2.5, 3.2, 5.4, 4.5],
+    /// ];
+    ///
+    /// let points = array![[1.5, 2.5], [2.0, 3.0], [2.7, 1.2]];
+    ///
+    /// let values = GridCubicSmoothingSpline::new(&x, &y)
+    ///     .make().unwrap()
+    ///     .evaluate_points(&points).unwrap();
+    ///
+    /// assert_eq!(values.len(), 3);
+    /// ```
+    ///
+    pub fn evaluate_points<'b, P>(&self, points: P) -> Result<Array1<T>>
+        where
+            T: 'b,
+            P: AsArray<'b, T, Ix2>
+    {
+        let points = points.into();
+
+        self.evaluate_points_validate(points)?;
+        let values = self.evaluate_points_spline(points, &vec![0; self.x.len()])?;
+
+        Ok(values)
+    }
+
+    /// Returns the ref to smoothing parameters vector
+    ///
+    /// After making the spline the smoothing parameters which have been used for computing it
+    /// are returned, including the values selected by cross-validation, otherwise the explicitly
+    /// set values are returned.
     pub fn smooth(&self) -> &Vec<Option<T>> {
-        &self.smooth
+        self.selected_smooth.as_ref().unwrap_or(&self.smooth)
+    }
+
+    /// Returns `true` if the normalized smoothing parameters mode is set
+    pub fn normalized_smooth(&self) -> bool {
+        self.normalized_smooth
+    }
+
+    /// Returns the ref to cross-validation scores vector for the selected smoothing parameters
+    pub fn cv_scores(&self) -> &Vec<Option<T>> {
+        &self.cv_scores
+    }
+
+    /// Returns the ref to the periodic flags vector
+    pub fn periodic(&self) -> &Vec<bool> {
+        &self.periodic
+    }
+
+    /// Returns the extrapolation mode
+    pub fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
     }
 
     /// Returns ref to `NdGridSpline` struct with data of computed spline or None
-    pub fn spline(&self) -> Option<&NdGridSpline<'a, T, D>> {
+    pub fn spline(&self) -> Option<&NdGridSpline<T, D>> {
         self.spline.as_ref()
     }
 
+    /// Consumes the struct and returns the owned `NdGridSpline` struct with computed spline or None
+    ///
+    /// The returned spline does not borrow the input data, so it can outlive it.
+    pub fn into_spline(self) -> Option<NdGridSpline<T, D>> {
+        self.spline
+    }
+
     /// Invalidate computed spline
     fn invalidate(&mut self) {
         self.spline = None;
+        self.selected_smooth = None;
+        self.cv_scores = vec![None; self.x.len()];
     }
 }
@@ -0,0 +1,216 @@
+use ndarray::{Dimension, Array, Array1, Array2, ArrayView1, ArrayView2, AsArray, Ix2};
+
+use crate::{
+    Real,
+    Result,
+    NdSpline,
+    Extrapolation,
+    ndarrayext::to_2d_simple,
+    util::dim_from_vec
+};
+
+use super::{
+    NdGridSpline,
+    GridCubicSmoothingSpline,
+    util::permute_axes,
+    validate::validate_derivative_orders,
+};
+
+
+impl<T, D> NdGridSpline<T, D>
+    where
+        T: Real<T>,
+        D: Dimension
+{
+    /// Returns the partial derivative of the given orders of the spline as a new n-d grid spline
+    ///
+    /// `orders` are the derivative orders for each dimension, for example, `[1, 0]` is the partial
+    /// derivative by the first axis and `[1, 1]` is the mixed partial derivative for a surface.
+    /// The order of the returned spline along every axis is reduced by the derivative order.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `orders` is not equal to the grid dimensionality
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::GridCubicSmoothingSpline;
+    ///
+    /// let x0 = array![1., 2., 3.];
+    /// let x1 = array![1., 2., 3., 4.];
+    /// let x = vec![x0.view(), x1.view()];
+    ///
+    /// let y = array![
+    ///     [1., 2., 3., 4.],
+    ///     [3., 4., 5., 6.],
+    ///     [5., 6., 7., 8.],
+    /// ];
+    ///
+    /// let s = GridCubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let ds = s.spline().unwrap().derivative(&[1, 0]).unwrap();
+    ///
+    /// assert_eq!(ds.order(), &vec![3, 4]);
+    /// ```
+    ///
+    pub fn derivative(&self, orders: &[usize]) -> Result<NdGridSpline<T, D>> {
+        validate_derivative_orders(self.ndim, orders)?;
+
+        let ndim_m1 = self.ndim - 1;
+        let permuted_axes: D = permute_axes(self.ndim);
+
+        let mut coeffs = self.coeffs.to_owned();
+        let mut coeffs_shape = coeffs.shape().to_vec();
+
+        // The derivative is computed along every axis in the same way as the spline is computed
+        for ax in (0..self.ndim).rev() {
+            let (_, coeffs_2d) = {
+                let coeffs_2d = to_2d_simple(coeffs.view()).unwrap();
+                NdSpline::derivative_coeffs(self.order[ax], self.pieces[ax], coeffs_2d.view(), orders[ax])
+            };
+
+            coeffs = {
+                coeffs_shape[ndim_m1] = coeffs_2d.ncols();
+                let shape: D = dim_from_vec(self.ndim, coeffs_shape);
+
+                coeffs_2d
+                    .into_shape(shape).unwrap()
+                    .permuted_axes(permuted_axes.clone())
+                    .to_owned()
+            };
+
+            coeffs_shape = coeffs.shape().to_vec();
+        }
+
+        let breaks: Vec<ArrayView1<'_, T>> = self.breaks.iter().map(|b| b.view()).collect();
+
+        Ok(NdGridSpline {
+            smooth: self.smooth.clone(),
+            periodic: self.periodic.clone(),
+            ..NdGridSpline::new(breaks, coeffs)
+        })
+    }
+
+    /// Evaluates the partial derivative of the given orders of the spline on the given mesh of data sites
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `xi` vectors or the number of `orders` is not equal to the grid dimensionality
+    ///
+    pub fn evaluate_derivative(&self, xi: &[ArrayView1<'_, T>], orders: &[usize]) -> Result<Array<T, D>> {
+        self.evaluate_spline(xi, orders, Extrapolation::Polynomial)
+    }
+
+    /// Evaluates the partial derivative of the given orders of the spline at the scattered points
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `points` columns or the number of `orders` is not equal to the grid dimensionality
+    ///
+    pub fn evaluate_points_derivative(&self, points: ArrayView2<'_, T>, orders: &[usize]) -> Result<Array1<T>> {
+        self.evaluate_points_spline(points, orders, Extrapolation::Polynomial)
+    }
+}
+
+
+impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        D: Dimension
+{
+    /// Evaluates the partial derivative of the given orders of the computed spline on the given mesh of data sites
+    ///
+    /// `orders` are the derivative orders for each dimension, for example, `[1, 0]` is the partial
+    /// derivative by the first axis and `[1, 1]` is the mixed partial derivative for a surface.
+    /// The output array has the same shape as `evaluate` output.
+    ///
+    /// # Errors
+    ///
+    /// - If the `xi` data or `orders` are invalid
+    /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::GridCubicSmoothingSpline;
+    ///
+    /// let x0 = array![1., 2., 3.];
+    /// let x1 = array![1., 2., 3., 4.];
+    /// let x = vec![x0.view(), x1.view()];
+    ///
+    /// let y = array![
+    ///     [1., 2., 3., 4.],
+    ///     [3., 4., 5., 6.],
+    ///     [5., 6., 7., 8.],
+    /// ];
+    ///
+    /// let dy = GridCubicSmoothingSpline::new(&x, &y)
+    ///     .make().unwrap()
+    ///     .evaluate_derivative(&x, &[1, 0]).unwrap();
+    ///
+    /// assert!(dy.iter().all(|v: &f64| (v - 2.).abs() < 1e-10));
+    /// ```
+    ///
+    pub fn evaluate_derivative(&self, xi: &[ArrayView1<'a, T>], orders: &[usize]) -> Result<Array<T, D>> {
+        self.evaluate_validate(xi)?;
+        let yi = self.evaluate_spline(xi, orders)?;
+
+        Ok(yi)
+    }
+
+    /// Evaluates the partial derivative of the given orders of the computed spline at the scattered points
+    ///
+    /// # Errors
+    ///
+    /// - If the `points` data or `orders` are invalid
+    /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and any point is out of the data sites range
+    ///
+    pub fn evaluate_points_derivative<'b, P>(&self, points: P, orders: &[usize]) -> Result<Array1<T>>
+        where
+            T: 'b,
+            P: AsArray<'b, T, Ix2>
+    {
+        let points = points.into();
+
+        self.evaluate_points_validate(points)?;
+        let values = self.evaluate_points_spline(points, orders)?;
+
+        Ok(values)
+    }
+
+    /// Evaluates the gradient of the computed spline at the scattered points
+    ///
+    /// Returns `(npoints, ndim)` array of the first partial derivatives by every axis.
+    ///
+    /// # Errors
+    ///
+    /// - If the `points` data is invalid
+    /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and any point is out of the data sites range
+    ///
+    pub fn evaluate_points_gradient<'b, P>(&self, points: P) -> Result<Array2<T>>
+        where
+            T: 'b,
+            P: AsArray<'b, T, Ix2>
+    {
+        let points = points.into();
+        self.evaluate_points_validate(points)?;
+
+        let ndim = self.x.len();
+        let mut gradient = Array2::zeros((points.nrows(), ndim));
+
+        for ax in 0..ndim {
+            let mut orders = vec![0; ndim];
+            orders[ax] = 1;
+
+            let values = self.evaluate_points_spline(points, &orders)?;
+            gradient.column_mut(ax).assign(&values);
+        }
+
+        Ok(gradient)
+    }
+}
@@ -1,61 +1,231 @@
-use ndarray::{Dimension, Array, ArrayView1};
+use ndarray::{Dimension, Array, Array1, ArrayView, ArrayView1, ArrayView2, ArrayViewMut, ArrayViewMut2, Axis};
 
 use crate::{
     Real,
+    Result,
     NdSpline,
-    ndarrayext::to_2d_simple,
+    Extrapolation,
+    CsapsError::InvalidInputData,
     util::dim_from_vec
 };
 
 use super::{
     NdGridSpline,
     GridCubicSmoothingSpline,
-    util::permute_axes
+    util::permute_axes,
+    workspace::scratch,
+    validate::{validate_derivative_orders, validate_xi_count},
 };
 
 
-impl<'a, T, D> NdGridSpline<'a, T, D>
+impl<T, D> NdGridSpline<T, D>
     where
         T: Real<T>,
         D: Dimension
 {
-    /// Implements evaluating the spline on the given mesh of Xi-sites
-    pub(super) fn evaluate_spline(&self, xi: &[ArrayView1<'a, T>]) -> Array<T, D> {
-        let mut coeffs = self.coeffs.to_owned();
-        let mut coeffs_shape = coeffs.shape().to_vec();
+    /// Implements evaluating the spline or its partial derivative of orders `nu` on the given mesh of Xi-sites
+    ///
+    /// The extrapolation is applied along every axis, so, for example, `Linear` extrapolation
+    /// is the tensor-product of linear continuations along the axes.
+    pub(super) fn evaluate_spline(&self, xi: &[ArrayView1<'_, T>], nu: &[usize], extrapolation: Extrapolation) -> Result<Array<T, D>> {
+        validate_xi_count(self.ndim, xi.len())?;
+
+        let shape: D = dim_from_vec(self.ndim, xi.iter().map(|xi_ax| xi_ax.len()).collect());
+        let mut values = Array::zeros(shape);
+
+        self.evaluate_spline_into(xi, nu, extrapolation, values.view_mut())?;
+
+        Ok(values)
+    }
+
+    /// Implements evaluating the spline or its partial derivative of orders `nu` on the given mesh
+    /// of Xi-sites into the values array
+    ///
+    /// The scratch buffers are allocated for every call, see `evaluate_spline_into_with`.
+    pub(super) fn evaluate_spline_into(
+        &self,
+        xi: &[ArrayView1<'_, T>],
+        nu: &[usize],
+        extrapolation: Extrapolation,
+        values: ArrayViewMut<'_, T, D>,
+    ) -> Result<()> {
+        self.evaluate_spline_into_with(xi, nu, extrapolation, &mut Vec::new(), &mut Vec::new(), values)
+    }
+
+    /// Implements evaluating the spline or its partial derivative of orders `nu` on the given mesh
+    /// of Xi-sites into the values array with the given scratch buffers
+    ///
+    /// The spline is evaluated along the axes from the last to the first. The coefficients evaluated
+    /// along every axis are stored in `values_buf` and then are permuted into `coeffs_buf` in
+    /// the standard layout with the evaluated axis first, so after evaluating the axes after the first
+    /// the rows of the coefficients are in the order of the values along the first axis. The values
+    /// along the first axis are written directly into `values`. The buffers only grow, so nothing
+    /// is allocated when the buffers have been used for the grid of the same shape.
+    pub(super) fn evaluate_spline_into_with(
+        &self,
+        xi: &[ArrayView1<'_, T>],
+        nu: &[usize],
+        extrapolation: Extrapolation,
+        coeffs_buf: &mut Vec<T>,
+        values_buf: &mut Vec<T>,
+        values: ArrayViewMut<'_, T, D>,
+    ) -> Result<()> {
+        validate_derivative_orders(self.ndim, nu)?;
 
         let ndim_m1 = self.ndim - 1;
         let permuted_axes: D = permute_axes(self.ndim);
 
-        for ax in (0..self.ndim).rev() {
-            let xi_ax = xi[ax];
+        let mut shape = self.coeffs.raw_dim();
+
+        // The coefficients are viewed as 2-d array in C order, so the coefficients with
+        // non-standard layout are copied into the buffer
+        let mut buffered = !self.coeffs.is_standard_layout();
+
+        if buffered {
+            ArrayViewMut::from_shape(shape.clone(), scratch(coeffs_buf, self.coeffs.len()))
+                .unwrap()
+                .assign(&self.coeffs);
+        }
+
+        for ax in (1..self.ndim).rev() {
+            let rows: usize = shape.slice()[..ndim_m1].iter().product();
+            let cols = shape[ndim_m1];
+            let len = rows * xi[ax].len();
+
+            let coeffs = if buffered { &coeffs_buf[..rows * cols] } else { self.coeffs.as_slice().unwrap() };
+            let coeffs_2d = ArrayView2::from_shape((rows, cols), coeffs).unwrap();
+            let values_2d = ArrayViewMut2::from_shape((rows, xi[ax].len()), scratch(values_buf, len)).unwrap();
+
+            NdSpline::evaluate_spline_extrapolated_into(
+                self.order[ax],
+                self.pieces[ax],
+                self.breaks[ax].view(),
+                coeffs_2d,
+                xi[ax],
+                self.periodic[ax],
+                nu[ax],
+                extrapolation,
+                values_2d,
+                Axis(1),
+            )?;
+
+            shape[ndim_m1] = xi[ax].len();
 
-            let coeffs_2d = {
-                let coeffs_2d = to_2d_simple(coeffs.view()).unwrap();
+            let permuted = ArrayView::from_shape(shape, &values_buf[..len])
+                .unwrap()
+                .permuted_axes(permuted_axes.clone());
+
+            shape = permuted.raw_dim();
+
+            ArrayViewMut::from_shape(shape.clone(), scratch(coeffs_buf, len))
+                .unwrap()
+                .assign(&permuted);
+
+            buffered = true;
+        }
 
-                NdSpline::evaluate_spline(
-                    self.order[ax],
-                    self.pieces[ax],
-                    self.breaks[ax],
-                    coeffs_2d,
-                    xi_ax,
+        // The rows of the coefficients are in the order of the values along the first axis
+        let rows: usize = shape.slice()[..ndim_m1].iter().product();
+        let cols = shape[ndim_m1];
+
+        let coeffs = if buffered { &coeffs_buf[..rows * cols] } else { self.coeffs.as_slice().unwrap() };
+        let coeffs_2d = ArrayView2::from_shape((rows, cols), coeffs).unwrap();
+
+        NdSpline::evaluate_spline_extrapolated_into(
+            self.order[0],
+            self.pieces[0],
+            self.breaks[0].view(),
+            coeffs_2d,
+            xi[0],
+            self.periodic[0],
+            nu[0],
+            extrapolation,
+            values.into_dyn(),
+            Axis(0),
+        )
+    }
+
+    /// Implements evaluating the spline or its partial derivative of orders `nu` at the scattered points
+    ///
+    /// `points` is `(npoints, ndim)` array. For every point the coefficients of the polynomial pieces
+    /// containing the point are contracted with the per-axis weights of the powers, so the extrapolation
+    /// is applied along every axis as for the mesh evaluation. The buffers for the weights and
+    /// the indices are allocated once, evaluating does not allocate per point.
+    pub(super) fn evaluate_points_spline(&self, points: ArrayView2<'_, T>, nu: &[usize], extrapolation: Extrapolation) -> Result<Array1<T>> {
+        validate_derivative_orders(self.ndim, nu)?;
+
+        if points.ncols() != self.ndim {
+            return Err(
+                InvalidInputData(
+                    format!("The number of `points` columns ({}) is not equal to the number of dimensions ({})",
+                            points.ncols(), self.ndim)
                 )
-            };
+            )
+        }
 
-            coeffs = {
-                coeffs_shape[ndim_m1] = xi_ax.len();
-                let shape: D = dim_from_vec(self.ndim, coeffs_shape);
+        // The coordinates along the periodic axes are wrapped into the period
+        let site = |ax: usize, x: T| {
+            if self.periodic[ax] { NdSpline::wrap_site(self.breaks[ax].view(), x) } else { x }
+        };
 
-                coeffs_2d
-                    .into_shape(shape).unwrap()
-                    .permuted_axes(permuted_axes.clone())
-                    .to_owned()
-            };
+        if extrapolation == Extrapolation::Error {
+            for (ax, breaks) in self.breaks.iter().enumerate() {
+                let x_first = breaks[0];
+                let x_last = breaks[breaks.len() - 1];
 
-            coeffs_shape = coeffs.shape().to_vec();
+                if let Some(x) = points.column(ax).iter().map(|&x| site(ax, x)).find(|&x| x < x_first || x > x_last) {
+                    return Err(
+                        InvalidInputData(
+                            format!("`xi` value {:?} is out of the breaks range [{:?}, {:?}]", x, x_first, x_last)
+                        )
+                    )
+                }
+            }
         }
 
-        coeffs
+        let coeffs = self.coeffs.view().into_dyn();
+
+        let mut weights: Vec<Vec<T>> = self.order.iter().map(|&order| vec![T::zero(); order]).collect();
+        let mut pieces = vec![0; self.ndim];
+        let mut powers = vec![0; self.ndim];
+        let mut index = vec![0; self.ndim];
+
+        let values = points.outer_iter().map(|point| {
+            for (ax, &x) in point.iter().enumerate() {
+                let breaks = self.breaks[ax].view();
+
+                match NdSpline::piece_weights(self.pieces[ax], breaks, site(ax, x), nu[ax], extrapolation, &mut weights[ax]) {
+                    Some(j) => pieces[ax] = j,
+                    None => return T::nan(),
+                }
+            }
+
+            // The coefficients of the polynomial pieces containing the point are iterated
+            // by the multi-index of the powers in row-major order
+            powers.iter_mut().for_each(|k| *k = 0);
+            let mut value = T::zero();
+
+            loop {
+                let mut w = T::one();
+
+                for ax in 0..self.ndim {
+                    w *= weights[ax][powers[ax]];
+                    index[ax] = powers[ax] * self.pieces[ax] + pieces[ax];
+                }
+
+                value += w * coeffs[index.as_slice()];
+
+                match (0..self.ndim).rev().find(|&ax| powers[ax] + 1 < self.order[ax]) {
+                    Some(ax) => {
+                        powers[ax] += 1;
+                        powers[ax + 1..].iter_mut().for_each(|k| *k = 0);
+                    },
+                    None => break value,
+                }
+            }
+        }).collect();
+
+        Ok(values)
     }
 }
 
@@ -65,7 +235,11 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
         T: Real<T>,
         D: Dimension
 {
-    pub(super) fn evaluate_spline(&self, xi: &[ArrayView1<'a, T>]) -> Array<T, D> {
-        self.spline.as_ref().unwrap().evaluate_spline(&xi)
+    pub(super) fn evaluate_spline(&self, xi: &[ArrayView1<'a, T>], nu: &[usize]) -> Result<Array<T, D>> {
+        self.spline.as_ref().unwrap().evaluate_spline(xi, nu, self.extrapolation)
+    }
+
+    pub(super) fn evaluate_points_spline(&self, points: ArrayView2<'_, T>, nu: &[usize]) -> Result<Array1<T>> {
+        self.spline.as_ref().unwrap().evaluate_points_spline(points, nu, self.extrapolation)
     }
 }
@@ -1,5 +1,4 @@
 use ndarray::Dimension;
-use ndarray::prelude::*;
 
 use crate::util::dim_from_vec;
 use crate::{
@@ -7,6 +6,7 @@ use crate::{
     RealRef,
     Result,
     CubicSmoothingSpline,
+    CubicSmoother,
     ndarrayext::to_2d_simple,
 };
 
@@ -33,6 +33,7 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
         let mut coeffs_shape = coeffs.shape().to_vec();
 
         let mut smooth: Vec<Option<T>> = vec![None; ndim];
+        let mut cv_scores: Vec<Option<T>> = vec![None; ndim];
 
         let permuted_axes: D = permute_axes(ndim);
 
@@ -42,32 +43,43 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
 
             let weights = self.weights[ax].map(|v| v.reborrow());
             let s = self.smooth[ax];
-
-            // Cannot explain how this error happens
-            //
-            // = note: required because of the requirements on the impl of `for<'r> Add` for `&'r CsMatBase<Simd<_, {_: usize}>, _, _, _, _, _>`
-            // = note: 127 redundant requirements hidden
-            // = note: required because of the requirements on the impl of `for<'r> Add` for `&'r CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase
-            // <CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase
-            // <CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase
-            // <CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase<CsMatBase
-            
-            // let x = Array1::<f64>::zeros(1).view();
-            // let y = Array2::<f64>::zeros((1,1)).view();
-            let sp = CubicSmoothingSpline::new(x, y)
-                .with_optional_weights(weights)
-                .with_optional_smooth(s)
-                .make()?;
-
-            smooth[ax] = sp.smooth();
+            let cv = self.cross_validation[ax];
+
+            // The smoother is built and factorized once for the axis and it is applied to all lines
+            // of the axis; the cross-validation and the missing values require the data values
+            let spline = if cv.is_none() && !y.iter().any(|v| v.is_nan()) {
+                let smoother = CubicSmoother::new(x)
+                    .with_optional_weights(weights)
+                    .with_optional_smooth(s)
+                    .with_normalized_smooth(self.normalized_smooth)
+                    .with_periodic(self.periodic[ax])
+                    .make()?;
+
+                smooth[ax] = smoother.smooth();
+                cv_scores[ax] = None;
+
+                smoother.fit(y.view())?
+            } else {
+                let sp = CubicSmoothingSpline::new(x, y.view())
+                    .with_optional_weights(weights)
+                    .with_optional_cross_validation(cv)
+                    .with_optional_smooth(s)
+                    .with_normalized_smooth(self.normalized_smooth)
+                    .with_periodic(self.periodic[ax])
+                    .make()?;
+
+                smooth[ax] = sp.smooth();
+                cv_scores[ax] = sp.cv_score();
+
+                sp.into_spline().unwrap()
+            };
 
             coeffs = {
-                let spline = sp.spline().unwrap();
-
                 coeffs_shape[ndim_m1] = spline.pieces() * spline.order();
                 let new_shape: D = dim_from_vec(ndim, coeffs_shape);
 
                 spline.coeffs()
+                    .as_standard_layout()
                     .into_shape(new_shape).unwrap()
                     .permuted_axes(permuted_axes.clone())
                     .to_owned()
@@ -76,8 +88,13 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
             coeffs_shape = coeffs.shape().to_vec();
         }
 
-        self.smooth = smooth;
-        self.spline = Some(NdGridSpline::new(breaks, coeffs));
+        self.spline = Some(NdGridSpline {
+            smooth: smooth.clone(),
+            periodic: self.periodic.clone(),
+            ..NdGridSpline::new(breaks, coeffs)
+        });
+        self.selected_smooth = Some(smooth);
+        self.cv_scores = cv_scores;
 
         Ok(())
     }
@@ -0,0 +1,168 @@
+use std::convert::TryFrom;
+
+use ndarray::{Array, Array1, Dimension, IxDyn};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    Real,
+    Result,
+    CsapsError,
+    CsapsError::InvalidSplineData,
+    serialize::{
+        SCHEMA_VERSION,
+        validate_version,
+        validate_order,
+        validate_breaks,
+        validate_coeffs,
+        validate_smooth,
+    },
+};
+
+use super::NdGridSpline;
+
+
+/// The serialized representation of `NdGridSpline`
+///
+/// The coefficients are stored as the flat vector in row-major order with the given shape.
+/// The periodic flags are absent in the schema version 1.
+///
+/// `NdGridSpline` is serialized and deserialized through this representation. The data can be
+/// deserialized to `NdGridSplineData` and converted to `NdGridSpline` by `TryFrom`
+/// to get `CsapsError::InvalidSplineData` for the invalid data (see `NdSplineData`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NdGridSplineData<T> {
+    version: u32,
+    ndim: usize,
+    order: Vec<usize>,
+    pieces: Vec<usize>,
+    breaks: Vec<Vec<T>>,
+    coeffs_shape: Vec<usize>,
+    coeffs: Vec<T>,
+    smooth: Vec<Option<T>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    periodic: Vec<bool>,
+}
+
+
+impl<T, D> From<&NdGridSpline<T, D>> for NdGridSplineData<T>
+    where
+        T: Real<T>,
+        D: Dimension
+{
+    fn from(spline: &NdGridSpline<T, D>) -> Self {
+        NdGridSplineData {
+            version: SCHEMA_VERSION,
+            ndim: spline.ndim,
+            order: spline.order.clone(),
+            pieces: spline.pieces.clone(),
+            breaks: spline.breaks.iter().map(|b| b.to_vec()).collect(),
+            coeffs_shape: spline.coeffs.shape().to_vec(),
+            coeffs: spline.coeffs.iter().cloned().collect(),
+            smooth: spline.smooth.clone(),
+            periodic: spline.periodic.clone(),
+        }
+    }
+}
+
+
+impl<T, D> TryFrom<NdGridSplineData<T>> for NdGridSpline<T, D>
+    where
+        T: Real<T>,
+        D: Dimension
+{
+    type Error = CsapsError;
+
+    fn try_from(data: NdGridSplineData<T>) -> Result<Self> {
+        validate_version(data.version)?;
+
+        let ndim = data.ndim;
+
+        if ndim == 0 || D::NDIM.is_some_and(|n| n != ndim) {
+            return Err(
+                InvalidSplineData(
+                    format!("The grid dimensionality ({}) is not valid for the spline type", ndim)
+                )
+            )
+        }
+
+        let sizes = [data.order.len(), data.pieces.len(), data.breaks.len(),
+                     data.coeffs_shape.len(), data.smooth.len()];
+
+        if sizes.iter().any(|&s| s != ndim) {
+            return Err(
+                InvalidSplineData(
+                    format!("The sizes of the spline attributes {:?} are not equal to the grid dimensionality ({})",
+                            sizes, ndim)
+                )
+            )
+        }
+
+        // The splines of the schema version 1 are not periodic
+        let periodic = if data.version == 1 {
+            vec![false; ndim]
+        } else if data.periodic.len() == ndim {
+            data.periodic
+        } else {
+            return Err(
+                InvalidSplineData(
+                    format!("The size of the periodic flags ({}) is not equal to the grid dimensionality ({})",
+                            data.periodic.len(), ndim)
+                )
+            )
+        };
+
+        for ax in 0..ndim {
+            validate_order(data.order[ax])?;
+            validate_breaks(&data.breaks[ax], data.pieces[ax])?;
+            validate_smooth(data.smooth[ax])?;
+
+            if data.order[ax].checked_mul(data.pieces[ax]) != Some(data.coeffs_shape[ax]) {
+                return Err(
+                    InvalidSplineData(
+                        format!("The coefficients shape[{}] ({}) does not match the order ({}) and the pieces ({})",
+                                ax, data.coeffs_shape[ax], data.order[ax], data.pieces[ax])
+                    )
+                )
+            }
+        }
+
+        validate_coeffs(&data.coeffs, &data.coeffs_shape)?;
+
+        let coeffs = Array::from_shape_vec(IxDyn(&data.coeffs_shape), data.coeffs)
+            .and_then(|c| c.into_dimensionality::<D>())
+            .map_err(|err| InvalidSplineData(err.to_string()))?;
+
+        Ok(NdGridSpline {
+            ndim,
+            order: data.order,
+            pieces: data.pieces,
+            breaks: data.breaks.into_iter().map(Array1::from).collect(),
+            coeffs,
+            smooth: data.smooth,
+            periodic,
+        })
+    }
+}
+
+
+impl<T, D> Serialize for NdGridSpline<T, D>
+    where
+        T: Real<T> + Serialize,
+        D: Dimension
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        NdGridSplineData::from(self).serialize(serializer)
+    }
+}
+
+
+impl<'de, T, D> Deserialize<'de> for NdGridSpline<T, D>
+    where
+        T: Real<T> + Deserialize<'de>,
+        D: Dimension
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> std::result::Result<Self, De::Error> {
+        let data = NdGridSplineData::deserialize(deserializer)?;
+        NdGridSpline::try_from(data).map_err(De::Error::custom)
+    }
+}
@@ -1,11 +1,12 @@
 use ndarray::{
     ArrayView,
     ArrayView1,
+    ArrayView2,
     Dimension,
 };
 
-use crate::{Real, Result, CsapsError::InvalidInputData};
-use crate::validate::{validate_data_sites, validate_smooth_value};
+use crate::{Real, Result, CrossValidation, CsapsError::InvalidInputData};
+use crate::validate::{validate_data_sites, validate_smooth_value, validate_weights_values};
 
 use super::GridCubicSmoothingSpline;
 
@@ -19,6 +20,8 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
         validate_xy(&self.x, self.y.view())?;
         validate_weights(&self.x, &self.weights)?;
         validate_smooth(&self.x, &self.smooth)?;
+        validate_cross_validation(&self.x, &self.cross_validation)?;
+        validate_periodic(&self.x, &self.periodic)?;
 
         Ok(())
     }
@@ -48,6 +51,65 @@ impl<'a, T, D> GridCubicSmoothingSpline<'a, T, D>
 
         Ok(())
     }
+
+    pub(super) fn evaluate_points_validate(&self, points: ArrayView2<'_, T>) -> Result<()> {
+        let ndim = self.x.len();
+
+        if points.ncols() != ndim {
+            return Err(
+                InvalidInputData(
+                    format!("The number of `points` columns ({}) is not equal to the number of dimensions ({})",
+                            points.ncols(), ndim)
+                )
+            )
+        }
+
+        if points.nrows() == 0 {
+            return Err(
+                InvalidInputData(
+                    "The number of `points` must be greater or equal to 1".to_string()
+                )
+            )
+        }
+
+        if self.spline.is_none() {
+            return Err(
+                InvalidInputData(
+                    "The spline has not been computed, use `make` method before".to_string()
+                )
+            )
+        }
+
+        Ok(())
+    }
+}
+
+
+pub(super) fn validate_xi_count(ndim: usize, xi_len: usize) -> Result<()> {
+    if xi_len != ndim {
+        return Err(
+            InvalidInputData(
+                format!("The number of `xi` vectors ({}) is not equal to the number of dimensions ({})",
+                        xi_len, ndim)
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(super) fn validate_derivative_orders(ndim: usize, orders: &[usize]) -> Result<()> {
+    if orders.len() != ndim {
+        return Err(
+            InvalidInputData(
+                format!("The number of derivative `orders` ({}) is not equal to the number of dimensions ({})",
+                        orders.len(), ndim)
+            )
+        )
+    }
+
+    Ok(())
 }
 
 
@@ -125,6 +187,8 @@ pub(super) fn validate_weights<T>(x: &[ArrayView1<'_, T>], w: &[Option<ArrayView
                     )
                 )
             }
+
+            validate_weights_values(*wi_view)?;
         }
     }
 
@@ -158,3 +222,43 @@ pub(super) fn validate_smooth<T>(x: &[ArrayView1<'_, T>], smooth: &[Option<T>])
 
     Ok(())
 }
+
+
+pub(super) fn validate_cross_validation<T>(x: &[ArrayView1<'_, T>], cv: &[Option<CrossValidation>]) -> Result<()>
+    where
+        T: Real<T>
+{
+    let x_len = x.len();
+    let cv_len = cv.len();
+
+    if cv_len != x_len {
+        return Err(
+            InvalidInputData(
+                format!("The number of `cross_validation` values ({}) is not equal to the number of dimensions ({})",
+                        cv_len, x_len)
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(super) fn validate_periodic<T>(x: &[ArrayView1<'_, T>], periodic: &[bool]) -> Result<()>
+    where
+        T: Real<T>
+{
+    let x_len = x.len();
+    let p_len = periodic.len();
+
+    if p_len != x_len {
+        return Err(
+            InvalidInputData(
+                format!("The number of `periodic` flags ({}) is not equal to the number of dimensions ({})",
+                        p_len, x_len)
+            )
+        )
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::Real;
+
+
+/// The reusable scratch buffers for evaluating `NdGridSpline` on the grids
+///
+/// Evaluating n-d grid spline on the mesh evaluates the coefficients along the axes from the last
+/// to the first, so the intermediate values for the dimensions after the first are stored in
+/// the scratch buffers. The buffers grow to the size required by the spline and the grid shape on
+/// the first evaluation and are reused by `NdGridSpline::evaluate_into_with`, so evaluating
+/// the spline again on the grid of the same shape does not allocate.
+///
+/// The workspace is not tied to a spline, it can be used for evaluating different splines.
+///
+#[derive(Debug, Clone)]
+pub struct NdGridWorkspace<T>
+    where
+        T: Real<T>
+{
+    /// The zero derivative orders for every dimension
+    pub(super) orders: Vec<usize>,
+
+    /// The coefficients evaluated along the processed axes in the standard layout
+    pub(super) coeffs: Vec<T>,
+
+    /// The values of the coefficients evaluated along the current axis
+    pub(super) values: Vec<T>,
+}
+
+
+impl<T> NdGridWorkspace<T>
+    where
+        T: Real<T>
+{
+    /// Creates the empty workspace, the buffers are allocated on the first evaluation
+    pub fn new() -> Self {
+        NdGridWorkspace {
+            orders: Vec::new(),
+            coeffs: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+
+impl<T> Default for NdGridWorkspace<T>
+    where
+        T: Real<T>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// Returns the scratch buffer slice of the given length, the buffer grows if it is shorter
+pub(super) fn scratch<T: Real<T>>(buffer: &mut Vec<T>, len: usize) -> &mut [T] {
+    if buffer.len() < len {
+        buffer.resize(len, T::zero());
+    }
+
+    &mut buffer[..len]
+}
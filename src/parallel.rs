@@ -0,0 +1,40 @@
+use ndarray::{ArrayViewMut, Axis, Dimension};
+
+#[cfg(feature = "rayon")]
+use ndarray::parallel::prelude::*;
+
+
+/// Computes the output array by the chunks of its subviews along the axis
+///
+/// `f` is called with the index of the first subview of the chunk and the view to the chunk.
+/// With `rayon` feature the chunks of at least `min_chunk` subviews are computed in parallel,
+/// otherwise `f` is called once for the whole array. The subviews must be computed independently of
+/// each other, so the results are identical for any chunks.
+pub(crate) fn for_each_chunk<T, D, F>(out: ArrayViewMut<'_, T, D>, axis: Axis, min_chunk: usize, f: F)
+    where
+        T: Send + Sync,
+        D: Dimension,
+        F: Fn(usize, ArrayViewMut<'_, T, D>) + Send + Sync
+{
+    #[cfg(feature = "rayon")]
+    {
+        let len = out.len_of(axis);
+        let chunk = len.div_ceil(rayon::current_num_threads()).max(min_chunk).max(1);
+
+        if chunk < len {
+            let mut out = out;
+
+            out.axis_chunks_iter_mut(axis, chunk)
+                .into_par_iter()
+                .enumerate()
+                .for_each(|(i, view)| f(i * chunk, view));
+
+            return
+        }
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    let _ = (axis, min_chunk);
+
+    f(0, out)
+}
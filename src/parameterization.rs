@@ -0,0 +1,35 @@
+/// Parameterization modes of the points of the parametric curve
+///
+/// The parameter increment between the consecutive points is `d^alpha` where `d` is the distance
+/// between the points, the parameter values are normalized to the range `[0, 1]`.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use csaps::{CubicSmoothingCurve, Parameterization};
+///
+/// let points = array![[0., 0.], [1., 0.], [1., 3.]];
+///
+/// let curve = CubicSmoothingCurve::new(&points)
+///     .with_parameterization(Parameterization::ChordLength)
+///     .make().unwrap();
+///
+/// assert_eq!(curve.parameter().unwrap(), array![0., 0.25, 1.]);
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parameterization {
+    /// Uniformly spaced parameter values (`alpha = 0`)
+    Uniform,
+
+    /// The parameter increments are equal to the distances between the points (`alpha = 1`, the default mode)
+    #[default]
+    ChordLength,
+
+    /// The parameter increments are equal to the square roots of the distances between the points (`alpha = 0.5`)
+    ///
+    /// The centripetal parameterization avoids cusps and self-intersections for the sharp turns of the curve.
+    Centripetal,
+}
@@ -0,0 +1,81 @@
+use crate::Real;
+
+
+/// Robust loss functions for fitting the smoothing spline by iteratively reweighted least squares
+///
+/// In the robust fitting mode the spline is computed repeatedly with the data weights multiplied
+/// by the robustness weights. The robustness weights are computed from the residuals of the previous
+/// fit scaled by the robust scale estimate `MAD / 0.6745` (the median absolute deviation of the residuals),
+/// so the points with large residuals (outliers) have less influence on the spline as in LOWESS
+/// robustness iterations.
+///
+/// The parameter of the loss is the tuning constant `c` in units of the robust scale.
+/// The conventional values are 1.345 for Huber and 4.685 for Tukey bisquare weights.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array1};
+/// use csaps::{CubicSmoothingSpline, RobustLoss};
+///
+/// let x = Array1::linspace(0., 6., 25);
+/// let mut y = x.mapv(f64::sin) + array![
+///     0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+///     0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+///
+/// // The spike
+/// y[12] += 3.0;
+///
+/// let s = CubicSmoothingSpline::new(&x, &y)
+///     .with_smooth(0.9)
+///     .with_robust(RobustLoss::Bisquare(4.685))
+///     .make().unwrap();
+///
+/// assert!(s.outliers().unwrap()[12]);
+/// assert_eq!(s.robustness_weights().unwrap()[12], 0.0);
+/// ```
+///
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RobustLoss<T> {
+    /// Huber weights `min(1, c / |u|)` for the scaled residuals `u`
+    ///
+    /// The outliers are down-weighted, but they are never rejected completely.
+    Huber(T),
+
+    /// Tukey bisquare (biweight) weights `(1 - (u / c)^2)^2` for `|u| < c` and zero otherwise
+    ///
+    /// The outliers with the scaled residuals greater than `c` are rejected.
+    Bisquare(T),
+}
+
+
+impl<T> RobustLoss<T>
+    where
+        T: Real<T>
+{
+    /// Returns the tuning constant of the loss
+    pub fn tuning(&self) -> T {
+        match *self {
+            RobustLoss::Huber(c) | RobustLoss::Bisquare(c) => c,
+        }
+    }
+
+    /// Returns the robustness weight for the absolute value of the scaled residual
+    pub(crate) fn weight(&self, u: T) -> T {
+        let one = T::one();
+
+        match *self {
+            RobustLoss::Huber(c) => if u <= c { one } else { c / u },
+            RobustLoss::Bisquare(c) => {
+                if u < c {
+                    let t = u / c;
+                    let t = one - t * t;
+                    t * t
+                } else {
+                    T::zero()
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use ndarray::ArrayView1;
+
+use crate::Real;
+
+
+/// Returns the index of the polynomial piece of the spline containing the data site
+///
+/// The piece `j` contains the data sites `breaks[j] <= x < breaks[j + 1]`, the data sites out of
+/// the breaks range belong to the first or the last piece. The index is found by the binary search,
+/// NaN belongs to the first piece.
+pub(crate) fn search_piece<T>(breaks: ArrayView1<'_, T>, pieces: usize, x: T) -> usize
+    where
+        T: Real<T>
+{
+    let (mut lo, mut hi) = (0, pieces - 1);
+
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+
+        if breaks[mid] <= x {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    lo
+}
+
+
+/// The search of the polynomial pieces of the spline containing the sequence of the data sites
+///
+/// The search starts from the piece of the previous data site (the hint), so it takes the constant
+/// time for the sorted data sites. The hint is computed directly from the data site for the uniformly
+/// spaced breaks. The binary search is used if the hint and its neighbours do not contain the data site.
+#[derive(Clone)]
+pub(crate) struct PieceSearch<'a, T>
+    where
+        T: Real<T>
+{
+    /// The breaks of the spline
+    breaks: ArrayView1<'a, T>,
+
+    /// The number of the pieces
+    pieces: usize,
+
+    /// The step of the uniformly spaced breaks
+    step: Option<T>,
+
+    /// The piece index of the previous data site
+    hint: usize,
+}
+
+
+impl<'a, T> PieceSearch<'a, T>
+    where
+        T: Real<T>
+{
+    /// Creates the search for the given breaks and the number of the pieces
+    pub(crate) fn new(breaks: ArrayView1<'a, T>, pieces: usize) -> Self {
+        let step = (breaks[pieces] - breaks[0]) / T::from(pieces).unwrap();
+        let tolerance = step * T::epsilon().sqrt();
+
+        let is_uniform = breaks.windows(2).into_iter()
+            .all(|w| (w[1] - w[0] - step).abs() <= tolerance);
+
+        PieceSearch {
+            breaks,
+            pieces,
+            step: if is_uniform { Some(step) } else { None },
+            hint: 0,
+        }
+    }
+
+    /// Returns the index of the polynomial piece containing the data site
+    ///
+    /// See `search_piece` for the pieces bounds.
+    pub(crate) fn find(&mut self, x: T) -> usize {
+        if x.is_nan() {
+            return 0
+        }
+
+        let guess = match self.step {
+            Some(step) => {
+                let t = ((x - self.breaks[0]) / step).floor();
+
+                if t <= T::zero() {
+                    0
+                } else {
+                    t.to_usize().unwrap_or(self.pieces - 1).min(self.pieces - 1)
+                }
+            },
+            None => self.hint,
+        };
+
+        let index = if self.contains(guess, x) {
+            guess
+        } else if guess + 1 < self.pieces && self.contains(guess + 1, x) {
+            guess + 1
+        } else if guess > 0 && self.contains(guess - 1, x) {
+            guess - 1
+        } else {
+            search_piece(self.breaks, self.pieces, x)
+        };
+
+        self.hint = index;
+        index
+    }
+
+    /// Returns true if the piece contains the data site
+    fn contains(&self, j: usize, x: T) -> bool {
+        (j == 0 || self.breaks[j] <= x) && (j + 1 == self.pieces || x < self.breaks[j + 1])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1};
+    use crate::search::*;
+
+    fn find_all(breaks: &Array1<f64>, xi: &Array1<f64>) -> Array1<usize> {
+        let mut search = PieceSearch::new(breaks.view(), breaks.len() - 1);
+        xi.mapv(|x| search.find(x))
+    }
+
+    #[test]
+    fn test_search_sorted() {
+        let breaks = array![1., 2., 3., 4., 5., 6.];
+
+        assert_eq!(find_all(&breaks, &Array1::linspace(1., 5., 9)),
+                   array![0, 0, 1, 1, 2, 2, 3, 3, 4]);
+        assert_eq!(find_all(&breaks, &Array1::linspace(0., 7., 15)),
+                   array![0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 4, 4, 4]);
+        assert_eq!(find_all(&breaks, &Array1::linspace(1.5, 4.5, 13)),
+                   array![0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3]);
+        assert_eq!(find_all(&breaks, &Array1::linspace(2.5, 8.5, 13)),
+                   array![1, 2, 2, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn test_search_not_sorted() {
+        let breaks = array![1., 2., 3., 4., 5., 6.];
+        let xi = array![1., 2., 1., 3., 3., 2., 1., 4., 5., 5., 4., 4., 3., 3., 2., 1.];
+
+        assert_eq!(find_all(&breaks, &xi), array![0, 1, 0, 2, 2, 1, 0, 3, 4, 4, 3, 3, 2, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_search_not_uniform() {
+        let breaks = array![0., 0.1, 0.5, 2., 2.2, 7.];
+        let xi = array![-1., 0.05, 6., 0.1, 0.3, 2.1, 2.2, 1.9, 8., 0.];
+
+        assert_eq!(find_all(&breaks, &xi), array![0, 0, 4, 1, 1, 3, 4, 2, 4, 0]);
+
+        for (&x, &j) in xi.iter().zip(find_all(&breaks, &xi).iter()) {
+            assert_eq!(search_piece(breaks.view(), 5, x), j);
+        }
+    }
+
+    #[test]
+    fn test_search_not_finite() {
+        let breaks = array![1., 2., 3., 4.];
+        let xi = array![f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 2.5];
+
+        assert_eq!(find_all(&breaks, &xi), array![0, 2, 0, 0, 1]);
+        assert_eq!(search_piece(breaks.view(), 3, f64::NAN), 0);
+    }
+}
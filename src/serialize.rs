@@ -0,0 +1,103 @@
+use crate::{Real, Result, CsapsError::InvalidSplineData};
+
+
+/// The version of the serialized splines schema
+///
+/// The version must be incremented on every incompatible change of the schema.
+/// The version 2 adds the periodic flags, the splines of the version 1 are not periodic.
+pub(crate) const SCHEMA_VERSION: u32 = 2;
+
+/// The oldest version of the serialized splines schema which can be deserialized
+pub(crate) const MIN_SCHEMA_VERSION: u32 = 1;
+
+
+pub(crate) fn validate_version(version: u32) -> Result<()> {
+    if !(MIN_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version) {
+        return Err(
+            InvalidSplineData(
+                format!("Unsupported schema version {}, expected {} to {}",
+                        version, MIN_SCHEMA_VERSION, SCHEMA_VERSION)
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_breaks<T>(breaks: &[T], pieces: usize) -> Result<()>
+    where
+        T: Real<T>
+{
+    if pieces == 0 || breaks.len() != pieces + 1 {
+        return Err(
+            InvalidSplineData(
+                format!("The number of breaks ({}) does not match the number of pieces ({})",
+                        breaks.len(), pieces)
+            )
+        )
+    }
+
+    if breaks.iter().any(|v| !v.is_finite()) || breaks.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(
+            InvalidSplineData(
+                "Breaks must be finite and strictly increasing".to_string()
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_coeffs<T>(coeffs: &[T], shape: &[usize]) -> Result<()>
+    where
+        T: Real<T>
+{
+    let size = shape.iter().try_fold(1usize, |acc, &s| acc.checked_mul(s));
+
+    if size != Some(coeffs.len()) {
+        return Err(
+            InvalidSplineData(
+                format!("The number of coefficients ({}) does not match the shape {:?}", coeffs.len(), shape)
+            )
+        )
+    }
+
+    if coeffs.iter().any(|v| !v.is_finite()) {
+        return Err(
+            InvalidSplineData("Coefficients must be finite".to_string())
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_smooth<T>(smooth: Option<T>) -> Result<()>
+    where
+        T: Real<T>
+{
+    if let Some(smooth) = smooth {
+        if !(smooth >= T::zero() && smooth <= T::one()) {
+            return Err(
+                InvalidSplineData(
+                    format!("`smooth` value must be in range 0..1, given {:?}", smooth)
+                )
+            )
+        }
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_order(order: usize) -> Result<()> {
+    if order == 0 {
+        return Err(
+            InvalidSplineData("The spline order must be greater or equal to 1".to_string())
+        )
+    }
+
+    Ok(())
+}
@@ -1,9 +1,6 @@
-use std::iter::FromIterator;
-
 use ndarray::{prelude::*};
 
 use sprs::{CsMat, TriMat, Shape, IndPtrBase};
-use sprs_ldl::LdlNumeric;
 
 use crate::Real;
 
@@ -27,7 +24,7 @@ pub fn diags<T>(diags: Array2<T>, offsets: &[isize], shape: Shape) -> CsMat<T>
         let mut j: usize = 0;
 
         if offset < 0 {
-            i = offset.abs() as usize;
+            i = offset.unsigned_abs();
         } else {
             j = offset as usize;
         }
@@ -75,6 +72,30 @@ pub fn diags<T>(diags: Array2<T>, offsets: &[isize], shape: Shape) -> CsMat<T>
 }
 
 
+/// Creates CSR square matrix from given cyclic diagonals
+///
+/// The diagonals wrap around the matrix, so the element `(i, (i + offset) mod size)` is
+/// equal to `diags[[k, i]]` where `k` is the index of the offset. The elements which fall
+/// into the same position are summed. It is used for the operators of periodic splines.
+///
+pub fn cyclic_diags<T>(diags: Array2<T>, offsets: &[isize], size: usize) -> CsMat<T>
+    where
+        T: Real<T>
+{
+    let mut mat = TriMat::<T>::new((size, size));
+    let n = size as isize;
+
+    for (k, &offset) in offsets.iter().enumerate() {
+        for i in 0..size {
+            let j = (i as isize + offset).rem_euclid(n) as usize;
+            mat.add_triplet(i, j, diags[[k, i]]);
+        }
+    }
+
+    mat.to_csr()
+}
+
+
 /// Returns values on k-diagonal for given sparse matrix
 ///
 ///
@@ -109,7 +130,7 @@ fn diagonal_csr<T>(k: isize,
     let first_row = if k >= 0 { 0 } else { (-k) as usize };
     let first_col = if k >= 0 { k as usize } else { 0 };
 
-    let diag_size = (rows - first_row).min(cols - first_col) as usize;
+    let diag_size = (rows - first_row).min(cols - first_col);
     let mut diag = Array1::<T>::zeros((diag_size, ));
 
     for i in 0..diag_size {
@@ -132,41 +153,6 @@ fn diagonal_csr<T>(k: isize,
 }
 
 
-/// Solves linear system Ax = b for symmetric CSR matrix A and dense vector(s) b
-///
-/// A: ref to CSR symmetric sparse matrix
-/// b: MxN stack of b-vectors where M is equal to A rows/cols and N is the data dimensional
-///
-pub fn solve<T>(a: &CsMat<T>, b: &Array2<T>) -> Array2<T>
-    where
-        T: Real<T> 
-{
-    let mut x = Array2::<T>::zeros(b.raw_dim());
-
-    let b_col_iter = b.axis_iter(Axis(1));
-    let x_col_iter = x.axis_iter_mut(Axis(1));
-
-    for (b_col, mut x_col) in b_col_iter.zip(x_col_iter) {
-        let xi = {
-            let x_vec = if b_col.len() > 1 {
-                let ldl = LdlNumeric::new(a.view()).unwrap();
-                let b_vec = Vec::from_iter(b_col.iter().cloned());
-                ldl.solve(&b_vec)
-            } else {
-                // In this corner case we have 1x1 sparse matrix and `b` vector with 1 element
-                vec![b_col[0] / a.to_dense()[[0, 0]]]
-            };
-
-            Array1::from(x_vec)
-        };
-
-        x_col.zip_mut_with(&xi, |v1, v2| *v1 = *v2);
-    }
-
-    x
-}
-
-
 #[cfg(test)]
 mod tests {
     use ndarray::array;
@@ -313,6 +299,59 @@ mod tests {
         assert_eq!(mat, mat_expected);
     }
 
+    #[test]
+    fn test_cyclic_diags_1() {
+        /*
+            4     7     1
+            2     5     8
+            9     3     6
+        */
+
+        let diags = array![
+            [1., 2., 3.],
+            [4., 5., 6.],
+            [7., 8., 9.],
+        ];
+
+        let mat = sprsext::cyclic_diags(diags, &[-1, 0, 1], 3);
+
+        let mat_expected = sprs::TriMat::<f64>::from_triplets(
+            (3, 3),
+            vec![0, 0, 0, 1, 1, 1, 2, 2, 2],
+            vec![0, 1, 2, 0, 1, 2, 0, 1, 2],
+            vec![4., 7., 1., 2., 5., 8., 9., 3., 6.],
+        ).to_csr();
+
+        assert_eq!(mat, mat_expected);
+    }
+
+    #[test]
+    fn test_cyclic_diags_2() {
+        /*
+            The elements of the sub- and super-diagonals fall into the same positions
+
+            2     4
+            6     4
+        */
+
+        let diags = array![
+            [1., 2.],
+            [2., 4.],
+            [3., 4.],
+        ];
+
+        let mat = sprsext::cyclic_diags(diags, &[-1, 0, 1], 2);
+
+        let mat_expected = sprs::TriMat::<f64>::from_triplets(
+            (2, 2),
+            vec![0, 0, 1, 1],
+            vec![0, 1, 0, 1],
+            vec![2., 4., 6., 4.],
+        ).to_csr();
+
+        assert_eq!(mat, mat_expected);
+    }
+
     #[test]
     fn test_diagonal_1() {
         let k = 0;
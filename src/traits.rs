@@ -1,11 +1,10 @@
 use almost::AlmostEqual;
 use ndarray::NdFloat;
-use sprs::{CsMatBase, MulAcc};
 
 // use num_traits::{Num, Float};
 use num_traits::{Num, MulAdd, Float};
 
-use std::ops::{Add, Mul, AddAssign};
+use std::ops::{Add, Mul};
 
 /// Floating-point element types `f32` and `f64`.
 ///
@@ -1,13 +1,38 @@
+mod bands;
+mod constrained;
+mod curve;
+mod derivative;
+mod diagnostics;
 mod evaluate;
+mod extrapolate;
+mod geometry;
+mod influence;
+mod integrate;
 mod make;
+mod missing;
+mod robust;
+mod select;
+#[cfg(feature = "serde")]
+mod serialize;
+mod smoother;
+mod sort;
 mod validate;
 
-use std::ops::{Add, Mul};
+use ndarray::{Array, Array1, Array2, ArrayView, ArrayView1, ArrayView2, ArrayViewMut2, AsArray, Axis, Dimension};
 
-use ndarray::{Array, Array2, ArrayView, ArrayView1, ArrayView2, AsArray, Axis, Dimension};
-use sprs::MulAcc;
+pub use self::bands::SmoothingBands;
+pub use self::curve::CubicSmoothingCurve;
+pub use self::diagnostics::FitDiagnostics;
+pub use self::smoother::CubicSmoother;
+#[cfg(feature = "serde")]
+pub use self::serialize::NdSplineData;
 
-use crate::{Real, RealRef, Result};
+use crate::{
+    CrossValidation, EndCondition, Extrapolation, MissingValues, Real, RealRef, Result, RobustLoss,
+    CsapsError::InvalidInputData,
+    search::PieceSearch,
+    validate::validate_output_shape,
+};
 
 /// N-dimensional (univariate/multivariate) spline PP-form representation
 ///
@@ -18,8 +43,11 @@ use crate::{Real, RealRef, Result};
 /// Also `evaluate` method is implemented for `NdSpline` for evaluating the data values
 /// for the given data sites.
 ///
-#[derive(Debug)]
-pub struct NdSpline<'a, T>
+/// `NdSpline` owns its breaks and coefficients, so it does not borrow the input data and
+/// can be stored, returned from functions or sent to other threads.
+///
+#[derive(Debug, Clone)]
+pub struct NdSpline<T>
 where
     T: Real<T>,
 {
@@ -33,13 +61,19 @@ where
     pieces: usize,
 
     /// The breaks (data sites) which have been used for computing spline
-    breaks: ArrayView1<'a, T>,
+    breaks: Array1<T>,
 
     /// `NxM` array of spline coefficients where `N` is `ndim` and `M` is row of pieces of coefficients
     coeffs: Array2<T>,
+
+    /// The smoothing parameter which has been used for computing spline
+    smooth: Option<T>,
+
+    /// The flag of the periodic spline which is evaluated with wrapping the data sites into the period
+    periodic: bool,
 }
 
-impl<'a, T> NdSpline<'a, T>
+impl<T> NdSpline<T>
 where
     T: Real<T>,
 {
@@ -47,14 +81,20 @@ where
     ///
     /// # Arguments
     ///
-    /// - `breaks` -- The breaks (data sites) which have been used for computing spline
+    /// - `breaks` -- The breaks (data sites) array-like which have been used for computing spline.
+    ///   The breaks are copied to the owned array.
     /// - `coeffs` -- The NxM array of spline coefficients where N is `ndim` and M is row of pieces of coefficients
     ///
     /// # Notes
     ///
     /// - `NdSpline` struct should not be created directly by a user in most cases.
     ///
-    pub fn new(breaks: ArrayView1<'a, T>, coeffs: Array2<T>) -> NdSpline<'a, T> {
+    pub fn new<'b, B>(breaks: B, coeffs: Array2<T>) -> NdSpline<T>
+    where
+        T: 'b,
+        B: AsArray<'b, T>,
+    {
+        let breaks = breaks.into().to_owned();
         let c_shape = coeffs.shape();
         let ndim = c_shape[0];
         let pieces = breaks.len() - 1;
@@ -66,6 +106,8 @@ where
             pieces,
             breaks,
             coeffs,
+            smooth: None,
+            periodic: false,
         }
     }
 
@@ -94,16 +136,190 @@ where
         self.coeffs.view()
     }
 
+    /// Returns the smoothing parameter which has been used for computing spline or None
+    ///
+    /// The smoothing parameter is None if the spline has been created directly by `new`.
+    pub fn smooth(&self) -> Option<T> {
+        self.smooth
+    }
+
+    /// Returns `true` if the spline is periodic
+    ///
+    /// The periodic spline is evaluated with wrapping the data sites into the breaks range,
+    /// so it is never extrapolated.
+    pub fn periodic(&self) -> bool {
+        self.periodic
+    }
+
     /// Evaluates the spline on the given data sites
-    pub fn evaluate(&self, xi: ArrayView1<'a, T>) -> Array2<T> {
+    pub fn evaluate(&self, xi: ArrayView1<'_, T>) -> Array2<T> {
         Self::evaluate_spline(
             self.order,
             self.pieces,
             self.breaks.view(),
             self.coeffs.view(),
             xi,
+            self.periodic,
         )
     }
+
+    /// Evaluates the spline on the given data sites into the given output array
+    ///
+    /// `out` must have shape `[ndim, xi.len()]`. The values are written into `out` without
+    /// allocating, so the spline can be evaluated many times into the same buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the shape of `out` is not equal to `[ndim, xi.len()]`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Array2};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![0.5, 1.2, 3.4, 2.5];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let spline = s.spline().unwrap();
+    ///
+    /// let xi = Array1::linspace(1., 4., 31);
+    /// let mut yi = Array2::<f64>::zeros((1, 31));
+    ///
+    /// for _ in 0..10 {
+    ///     spline.evaluate_into(xi.view(), &mut yi).unwrap();
+    /// }
+    ///
+    /// assert_eq!(yi, spline.evaluate(xi.view()));
+    /// ```
+    ///
+    pub fn evaluate_into<'b, O>(&self, xi: ArrayView1<'_, T>, out: O) -> Result<()>
+    where
+        O: Into<ArrayViewMut2<'b, T>>,
+        T: 'b,
+    {
+        let out = out.into();
+        validate_output_shape(out.shape(), [self.ndim, xi.len()])?;
+
+        Self::evaluate_spline_into(
+            self.order,
+            self.pieces,
+            self.breaks.view(),
+            self.coeffs.view(),
+            xi,
+            self.periodic,
+            out,
+            Axis(1),
+        );
+
+        Ok(())
+    }
+
+    /// Evaluates the 1-d spline at the single data site
+    ///
+    /// The piece containing the data site is found by the binary search and nothing is allocated,
+    /// so the method is suitable for evaluating the spline in the inner loops. The value is equal
+    /// to the value computed by `evaluate`.
+    ///
+    /// # Errors
+    ///
+    /// - If the spline dimensionality is not 1 (use `eval_at_into` for the multivariate splines)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![0.5, 1.2, 3.4, 2.5];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let spline = s.spline().unwrap();
+    ///
+    /// assert_eq!(spline.eval_at(2.5).unwrap(), spline.evaluate(array![2.5].view())[[0, 0]]);
+    /// ```
+    ///
+    pub fn eval_at(&self, x: T) -> Result<T> {
+        self.validate_univariate("eval_at")?;
+
+        let (j, t) = self.locate_site(x);
+        Ok(self.eval_piece(0, j, t))
+    }
+
+    /// Evaluates the spline at the single data site into the given slice
+    ///
+    /// `out` must have `ndim` elements, the value of every dimension of the spline is written
+    /// to the corresponding element. Nothing is allocated.
+    ///
+    /// # Errors
+    ///
+    /// - If `out.len()` is not equal to the spline dimensionality
+    ///
+    pub fn eval_at_into(&self, x: T, out: &mut [T]) -> Result<()> {
+        if out.len() != self.ndim {
+            return Err(
+                InvalidInputData(
+                    format!("The size of `out` ({}) is not equal to the spline dimensionality ({})",
+                            out.len(), self.ndim)
+                )
+            )
+        }
+
+        let (j, t) = self.locate_site(x);
+
+        for (dim, v) in out.iter_mut().enumerate() {
+            *v = self.eval_piece(dim, j, t);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the iterator evaluating the 1-d spline at the stream of the data sites
+    ///
+    /// The search of the piece starts from the piece of the previous data site, so evaluating
+    /// the sorted (or close) data sites takes the constant time per data site. Nothing is allocated
+    /// per data site.
+    ///
+    /// # Errors
+    ///
+    /// - If the spline dimensionality is not 1
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![0.5, 1.2, 3.4, 2.5];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let spline = s.spline().unwrap();
+    ///
+    /// let xi = Array1::linspace(1., 4., 100);
+    /// let yi: Vec<f64> = spline.eval_iter(xi.iter().copied()).unwrap().collect();
+    ///
+    /// assert_eq!(yi, spline.evaluate(xi.view()).row(0).to_vec());
+    /// ```
+    ///
+    pub fn eval_iter<'s, I>(&'s self, xs: I) -> Result<impl Iterator<Item = T> + 's>
+        where
+            I: IntoIterator<Item = T>,
+            I::IntoIter: 's
+    {
+        self.validate_univariate("eval_iter")?;
+
+        let mut search = PieceSearch::new(self.breaks.view(), self.pieces);
+
+        Ok(xs.into_iter().map(move |x| {
+            let x = if self.periodic { Self::wrap_site(self.breaks.view(), x) } else { x };
+            let j = search.find(x);
+
+            self.eval_piece(0, j, x - self.breaks[j])
+        }))
+    }
 }
 
 /// N-dimensional (univariate/multivariate) smoothing spline calculator/evaluator
@@ -171,8 +387,53 @@ where
     /// The optional smoothing parameter
     smooth: Option<T>,
 
+    /// The optional target effective degrees of freedom for selecting the smoothing parameter
+    dof: Option<T>,
+
+    /// The optional residual tolerance for selecting the smoothing parameter
+    tolerance: Option<T>,
+
+    /// The optional cross-validation criterion for selecting the smoothing parameter
+    cross_validation: Option<CrossValidation>,
+
+    /// The flag of the normalized smoothing parameter mode
+    normalized_smooth: bool,
+
+    /// The smoothing parameter which has been used for computing the spline
+    selected_smooth: Option<T>,
+
+    /// The cross-validation score for the selected smoothing parameter
+    cv_score: Option<T>,
+
+    /// The conditions at the first and the last data sites
+    end_conditions: (EndCondition<T>, EndCondition<T>),
+
+    /// The flag of the periodic spline
+    periodic: bool,
+
+    /// The flag of sorting the data sites and merging the duplicate data sites
+    sort_data_sites: bool,
+
+    /// The handling mode of the missing (NaN) values in Y-data
+    missing_values: MissingValues,
+
+    /// The optional robust loss for fitting the spline by iteratively reweighted least squares
+    robust: Option<RobustLoss<T>>,
+
+    /// The maximum number of the robustness iterations
+    robust_iterations: usize,
+
+    /// The robustness weights which have been used for computing the robust spline
+    robustness_weights: Option<Array1<T>>,
+
+    /// The flags of the data sites which have been treated as outliers by the robust fitting
+    outliers: Option<Array1<bool>>,
+
+    /// The extrapolation mode for evaluating the spline out of the data sites range
+    extrapolation: Extrapolation,
+
     /// `NdSpline` struct with computed spline
-    spline: Option<NdSpline<'a, T>>,
+    spline: Option<NdSpline<T>>,
 }
 
 impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
@@ -188,6 +449,7 @@ where
     /// # Arguments
     ///
     /// - `x` -- the X-data sites 1-d array-like. Must strictly increasing: `x1 < x2 < x3 < ... < xN`
+    ///   unless the data sites are sorted by `with_sort_data_sites`
     /// - `y` -- The Y-data values n-d array-like. `ndim` can be from 1 to N. The splines will be computed for
     ///   all data by given axis. By default the axis parameter is equal to the last axis of Y data.
     ///   For example, for 1-d axis is equal to 0, for 2-d axis is equal to 1, for 3-d axis is
@@ -204,6 +466,21 @@ where
             axis: None,
             weights: None,
             smooth: None,
+            dof: None,
+            tolerance: None,
+            cross_validation: None,
+            normalized_smooth: false,
+            selected_smooth: None,
+            cv_score: None,
+            end_conditions: (EndCondition::Natural, EndCondition::Natural),
+            periodic: false,
+            sort_data_sites: false,
+            missing_values: MissingValues::default(),
+            robust: None,
+            robust_iterations: robust::DEFAULT_ITERATIONS,
+            robustness_weights: None,
+            outliers: None,
+            extrapolation: Extrapolation::default(),
             spline: None,
         }
     }
@@ -276,10 +553,13 @@ where
     /// where bounds are:
     ///
     ///  - 0: The smoothing spline is the least-squares straight line fit to the data
-    ///  - 1: The cubic spline interpolant with natural boundary condition
+    ///  - 1: The cubic spline interpolant with the given end conditions (natural by default)
+    ///
+    /// The criteria for selecting the smoothing parameter are reset.
     ///
     pub fn with_smooth(mut self, smooth: T) -> Self {
         self.invalidate();
+        self.reset_smooth_criteria();
         self.smooth = Some(smooth);
         self
     }
@@ -287,16 +567,339 @@ where
     /// Sets the smoothing parameter in `Option` wrap
     pub fn with_optional_smooth(mut self, smooth: Option<T>) -> Self {
         self.invalidate();
+        if smooth.is_some() {
+            self.reset_smooth_criteria();
+        }
         self.smooth = smooth;
         self
     }
 
+    /// Sets the cross-validation criterion for selecting the smoothing parameter
+    ///
+    /// The smoothing parameter is chosen by minimizing the cross-validation score
+    /// instead of computing it from the traces ratio heuristic. The explicitly set smoothing
+    /// parameter is reset, the chosen smoothing parameter and the score can be got by `smooth`
+    /// and `cv_score` methods after making the spline.
+    ///
+    /// The selection requires solving the system for many smoothing parameter values
+    /// and computing the diagonal of the influence matrix, so it is much slower than
+    /// computing the spline with the given smoothing parameter. The diagonal is computed
+    /// in linear time from the banded factorization, but for the periodic spline the system
+    /// is solved for every data site, so the cost is quadratic in the number of the data sites.
+    ///
+    pub fn with_cross_validation(mut self, cv: CrossValidation) -> Self {
+        self.invalidate();
+        self.reset_smooth_criteria();
+        self.cross_validation = Some(cv);
+        self
+    }
+
+    /// Sets the cross-validation criterion for selecting the smoothing parameter in `Option` wrap
+    pub fn with_optional_cross_validation(mut self, cv: Option<CrossValidation>) -> Self {
+        self.invalidate();
+        if cv.is_some() {
+            self.reset_smooth_criteria();
+        }
+        self.cross_validation = cv;
+        self
+    }
+
+    /// Sets the target effective degrees of freedom for selecting the smoothing parameter
+    ///
+    /// The smoothing parameter is chosen so that the trace of the influence (hat) matrix
+    /// of the smoothing spline is equal to `dof`. The value should be in range `[2, n]`
+    /// where `n` is the number of the data sites, where bounds are:
+    ///
+    ///  - 2: The least-squares straight line fit to the data (`smooth = 0`)
+    ///  - n: The cubic spline interpolant (`smooth = 1`)
+    ///
+    /// The explicitly set smoothing parameter and the other selection criteria are reset,
+    /// the chosen smoothing parameter can be got by `smooth` method after making the spline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::Array1;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = Array1::linspace(0., 6., 25);
+    /// let y = x.mapv(f64::sin);
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_dof(6.0)
+    ///     .make().unwrap();
+    ///
+    /// println!("smooth: {:?}", s.smooth());
+    /// ```
+    ///
+    pub fn with_dof(mut self, dof: T) -> Self {
+        self.invalidate();
+        self.reset_smooth_criteria();
+        self.dof = Some(dof);
+        self
+    }
+
+    /// Sets the residual tolerance for selecting the smoothing parameter
+    ///
+    /// The smoothing parameter is chosen so that the weighted sum of squared residuals
+    /// `sum(w_i * |y_i - f(x_i)|^2)` is equal to `tolerance` (as in MATLAB `spaps`),
+    /// i.e. the smoothest spline within the given tolerance is computed. For multivariate data
+    /// the squared residuals are summed over all dimensions. If `tolerance` is greater than
+    /// the residual of the least-squares straight line fit, the straight line is computed.
+    ///
+    /// The explicitly set smoothing parameter and the other selection criteria are reset,
+    /// the chosen smoothing parameter can be got by `smooth` method after making the spline.
+    ///
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.invalidate();
+        self.reset_smooth_criteria();
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Sets the normalized smoothing parameter mode
+    ///
+    /// In the normalized mode the smoothing parameter `s` in range `[0, 1]` is invariant
+    /// to the `x` data range and the data sites density. The normalized value is mapped
+    /// to the smoothing parameter `p` by the formula `p = s / (s + (1 - s) * r)` where `r`
+    /// is the traces ratio used for computing the smoothing parameter automatically,
+    /// so `s = 0.5` is equal to the automatically computed smoothing parameter.
+    /// If the smoothing parameter is not set, `s = 0.5` is used.
+    ///
+    /// `smooth` method returns the normalized value after making the spline in this mode,
+    /// including the values selected by the other criteria.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = array![0.5, 1.2, 3.4, 2.5, 1.8];
+    /// let x_ms = &x * 1000.0;
+    ///
+    /// let ys = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth(0.7)
+    ///     .with_normalized_smooth(true)
+    ///     .make().unwrap()
+    ///     .evaluate(&x).unwrap();
+    ///
+    /// let ys_ms = CubicSmoothingSpline::new(&x_ms, &y)
+    ///     .with_smooth(0.7)
+    ///     .with_normalized_smooth(true)
+    ///     .make().unwrap()
+    ///     .evaluate(&x_ms).unwrap();
+    ///
+    /// assert!((ys - ys_ms).iter().all(|v: &f64| v.abs() < 1e-6));
+    /// ```
+    ///
+    pub fn with_normalized_smooth(mut self, normalized_smooth: bool) -> Self {
+        self.invalidate();
+        self.normalized_smooth = normalized_smooth;
+        self
+    }
+
+    /// Sets the end conditions at the first and the last data sites
+    ///
+    /// By default the natural end conditions (zero second derivative) are used at both ends.
+    /// The other conditions are the prescribed first derivative (clamped spline), the prescribed
+    /// second derivative and not-a-knot condition, they can be combined arbitrarily. The conditions
+    /// are applied for both the smoothing and the interpolating (`smooth = 1`) spline.
+    ///
+    /// The automatic smoothing parameter and the selection criteria (`dof`, `tolerance` and
+    /// cross-validation) are computed for the natural end conditions. The smoothing spline
+    /// for `smooth = 0` with non-natural end conditions is approximated by the limit
+    /// for the small smoothing parameter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use csaps::{CubicSmoothingSpline, EndCondition};
+    ///
+    /// let x = array![0., 1., 2., 3., 4.];
+    /// let y = array![0., 1., 8., 27., 64.];
+    ///
+    /// // The not-a-knot interpolant reproduces the cubic polynomial
+    /// let yi: Array1<f64> = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth(1.0)
+    ///     .with_end_conditions(EndCondition::NotAKnot, EndCondition::NotAKnot)
+    ///     .make().unwrap()
+    ///     .evaluate(&array![0.5, 2.5]).unwrap();
+    ///
+    /// assert!((yi[0] - 0.125).abs() < 1e-10);
+    /// assert!((yi[1] - 15.625).abs() < 1e-10);
+    /// ```
+    ///
+    pub fn with_end_conditions(mut self, start: EndCondition<T>, end: EndCondition<T>) -> Self {
+        self.invalidate();
+        self.end_conditions = (start, end);
+        self
+    }
+
+    /// Sets the periodic spline mode
+    ///
+    /// The periodic spline joins the first and the last data sites with matching value, slope
+    /// and curvature, so it is suitable for angular or cyclic data. The period is `x[n-1] - x[0]`,
+    /// the last data site is the same site as the first one in the next period, so their data values
+    /// are merged with their weights (the data values are usually equal for the periodic data).
+    /// The periodic spline is evaluated with wrapping the data sites into the period instead of
+    /// extrapolating.
+    ///
+    /// The periodic mode cannot be combined with the end conditions. The number of the data sites
+    /// must be greater or equal to 3. The effective degrees of freedom of the periodic spline
+    /// are in range `[1, n - 1]`, the smoothing spline for `smooth = 0` is the weighted mean.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use std::f64::consts::PI;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = Array1::linspace(0., 2. * PI, 13);
+    /// let y = x.mapv(f64::sin);
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth(1.0)
+    ///     .with_periodic(true)
+    ///     .make().unwrap();
+    ///
+    /// // The data sites out of the period are wrapped into the period
+    /// let yi: Array1<f64> = s.evaluate(&array![1., 1. + 2. * PI]).unwrap();
+    /// assert!((yi[0] - yi[1]).abs() < 1e-12);
+    /// ```
+    ///
+    pub fn with_periodic(mut self, periodic: bool) -> Self {
+        self.invalidate();
+        self.periodic = periodic;
+        self
+    }
+
+    /// Sets the mode of sorting the data sites and merging the duplicate data sites
+    ///
+    /// By default the data sites must be strictly increasing. In this mode the data sites
+    /// are sorted (the data values and the weights are permuted along the axis) and the duplicate
+    /// or almost equal data sites are merged: the merged value is the weighted mean of the values
+    /// and the merged weight is the sum of the weights (as in MATLAB `csaps`). The missing (NaN) values
+    /// are skipped while averaging and the weights of the data sites which are missing in all data
+    /// series are not summed. The number of the unique data sites must be greater or equal to 2.
+    ///
+    /// The spline breaks, the robustness weights and the outliers flags are computed for
+    /// the sorted unique data sites.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![3., 1., 2., 2., 4.];
+    /// let y = array![3., 1., 1.5, 2.5, 4.];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth(1.0)
+    ///     .with_sort_data_sites(true)
+    ///     .make().unwrap();
+    ///
+    /// assert_eq!(s.spline().unwrap().breaks(), array![1., 2., 3., 4.]);
+    ///
+    /// let yi: Array1<f64> = s.evaluate(&array![2.]).unwrap();
+    /// assert!((yi[0] - 2.).abs() < 1e-12);
+    /// ```
+    ///
+    pub fn with_sort_data_sites(mut self, sort_data_sites: bool) -> Self {
+        self.invalidate();
+        self.sort_data_sites = sort_data_sites;
+        self
+    }
+
+    /// Sets the handling mode of the missing (NaN) values in Y-data
+    ///
+    /// By default the missing values are ignored (`MissingValues::Ignore`): they have zero weights,
+    /// so the spline is computed from the other values, but it is still defined over the whole
+    /// data sites range. The smoothing parameter is selected for the data sites without
+    /// missing values. The missing values are not supported for the periodic spline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::{CubicSmoothingSpline, MissingValues};
+    ///
+    /// let x = array![1., 2., 3., 4., 5.];
+    /// let y = array![
+    ///     [1., f64::NAN, 3., 4., 5.],
+    ///     [2., 4., 6., f64::NAN, 10.],
+    /// ];
+    ///
+    /// let yi = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_smooth(1.0)
+    ///     .with_missing_values(MissingValues::PerSeries)
+    ///     .make().unwrap()
+    ///     .evaluate(&x).unwrap();
+    ///
+    /// assert!((yi[[0, 1]] - 2.).abs() < 1e-12);
+    /// assert!((yi[[1, 3]] - 8.).abs() < 1e-12);
+    /// ```
+    ///
+    pub fn with_missing_values(mut self, missing_values: MissingValues) -> Self {
+        self.invalidate();
+        self.missing_values = missing_values;
+        self
+    }
+
+    /// Sets the robust fitting mode with the given loss
+    ///
+    /// In the robust mode the spline is computed by iteratively reweighted least squares:
+    /// the spline is computed repeatedly with the data weights multiplied by the robustness weights
+    /// which are computed from the residuals of the previous fit by the given loss (see `RobustLoss`).
+    /// For multivariate data the residuals are the euclidean norms of the residual vectors.
+    /// The smoothing parameter is selected on every iteration if it is not set explicitly.
+    ///
+    /// The final robustness weights and the flags of the outliers (the data sites with the scaled
+    /// residuals greater than the tuning constant) can be got by `robustness_weights` and `outliers`
+    /// methods after making the spline.
+    ///
+    pub fn with_robust(mut self, loss: RobustLoss<T>) -> Self {
+        self.invalidate();
+        self.robust = Some(loss);
+        self
+    }
+
+    /// Sets the maximum number of the robustness iterations
+    ///
+    /// The iterations are stopped earlier if the robustness weights have converged.
+    /// The default number of the iterations is 5.
+    ///
+    pub fn with_robust_iterations(mut self, iterations: usize) -> Self {
+        self.invalidate();
+        self.robust_iterations = iterations;
+        self
+    }
+
+    /// Sets the extrapolation mode
+    ///
+    /// The extrapolation mode defines the spline values for the data sites out of
+    /// the `x` data sites range. By default the first and the last polynomial pieces
+    /// are extrapolated (`Extrapolation::Polynomial`).
+    ///
+    /// The extrapolation mode does not affect the computed spline, so the spline
+    /// is not invalidated.
+    ///
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
     /// Evaluates the computed spline on the given data sites
     ///
     /// # Errors
     ///
     /// - If the `xi` data is invalid
     /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
     ///
     pub fn evaluate<X>(&self, xi: X) -> Result<Array<T, D>>
     where
@@ -305,24 +908,129 @@ where
         let xi = xi.into();
         self.evaluate_validate(xi)?;
 
-        let yi = self.evaluate_spline(xi)?;
+        let yi = self.evaluate_spline(xi, 0)?;
         Ok(yi)
     }
 
     /// Returns the smoothing parameter or None
+    ///
+    /// After making the spline the smoothing parameter which has been used for computing it
+    /// is returned, including the values selected by the criteria, otherwise the explicitly
+    /// set value is returned. In the normalized smoothing parameter mode the normalized value is returned.
     pub fn smooth(&self) -> Option<T> {
-        self.smooth
+        self.selected_smooth.or(self.smooth)
+    }
+
+    /// Returns `true` if the normalized smoothing parameter mode is set
+    pub fn normalized_smooth(&self) -> bool {
+        self.normalized_smooth
+    }
+
+    /// Returns the cross-validation score for the selected smoothing parameter or None
+    ///
+    /// The score is available only if the smoothing parameter has been selected
+    /// by cross-validation while making the spline.
+    pub fn cv_score(&self) -> Option<T> {
+        self.cv_score
+    }
+
+    /// Returns the end conditions at the first and the last data sites
+    pub fn end_conditions(&self) -> (EndCondition<T>, EndCondition<T>) {
+        self.end_conditions
+    }
+
+    /// Returns `true` if the periodic spline mode is set
+    pub fn periodic(&self) -> bool {
+        self.periodic
+    }
+
+    /// Returns `true` if the mode of sorting the data sites and merging the duplicate data sites is set
+    pub fn sort_data_sites(&self) -> bool {
+        self.sort_data_sites
+    }
+
+    /// Returns the handling mode of the missing values
+    pub fn missing_values(&self) -> MissingValues {
+        self.missing_values
+    }
+
+    /// Returns the robust loss or None
+    pub fn robust(&self) -> Option<RobustLoss<T>> {
+        self.robust
+    }
+
+    /// Returns the maximum number of the robustness iterations
+    pub fn robust_iterations(&self) -> usize {
+        self.robust_iterations
+    }
+
+    /// Returns the robustness weights for the data sites or None
+    ///
+    /// The weights are available only if the spline has been computed in the robust mode.
+    /// The spline has been computed with the data weights multiplied by these weights.
+    pub fn robustness_weights(&self) -> Option<ArrayView1<'_, T>> {
+        self.robustness_weights.as_ref().map(|w| w.view())
+    }
+
+    /// Returns the flags of the data sites which have been treated as outliers or None
+    ///
+    /// The flags are available only if the spline has been computed in the robust mode.
+    pub fn outliers(&self) -> Option<ArrayView1<'_, bool>> {
+        self.outliers.as_ref().map(|o| o.view())
+    }
+
+    /// Returns the extrapolation mode
+    pub fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
     }
 
     /// Returns the ref to `NdSpline` struct with data of computed spline or None
-    pub fn spline(&self) -> Option<&NdSpline<'a, T>> {
+    pub fn spline(&self) -> Option<&NdSpline<T>> {
         self.spline.as_ref()
     }
 
+    /// Consumes the struct and returns the owned `NdSpline` struct with computed spline or None
+    ///
+    /// The returned spline does not borrow the input data, so it can outlive it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::{CubicSmoothingSpline, NdSpline};
+    ///
+    /// fn fit() -> NdSpline<f64> {
+    ///     let x = array![1.0, 2.0, 3.0, 4.0];
+    ///     let y = array![0.5, 1.2, 3.4, 2.5];
+    ///
+    ///     CubicSmoothingSpline::new(&x, &y)
+    ///         .make().unwrap()
+    ///         .into_spline().unwrap()
+    /// }
+    ///
+    /// let spline = fit();
+    /// let yi = spline.evaluate(array![1.5, 2.5].view());
+    /// ```
+    ///
+    pub fn into_spline(self) -> Option<NdSpline<T>> {
+        self.spline
+    }
+
     /// Invalidate computed spline
     fn invalidate(&mut self) {
         self.spline = None;
+        self.selected_smooth = None;
+        self.cv_score = None;
+    }
+
+    /// Resets the smoothing parameter and all criteria for selecting it
+    fn reset_smooth_criteria(&mut self) {
+        self.smooth = None;
+        self.dof = None;
+        self.tolerance = None;
+        self.cross_validation = None;
     }
+
     /// Makes (computes) the spline for given data and parameters
     ///
     /// # Errors
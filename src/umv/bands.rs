@@ -0,0 +1,248 @@
+use ndarray::{prelude::*, RemoveAxis};
+
+use crate::{
+    CsapsError::InvalidInputData,
+    Real,
+    RealRef,
+    Result,
+    util::normal_quantile,
+};
+
+use super::{
+    CubicSmoothingSpline,
+    influence::{Influence, Smoother},
+};
+
+
+/// The pointwise standard errors and the confidence and prediction intervals of the smoothing spline
+///
+/// The bands are computed from the smoother (hat) matrix of the penalized system: the spline values
+/// are linear in the data values, so the variance of the spline value at `xi` is `sigma^2 * sum(l_j^2 / w_j)`
+/// where `l_j` are the weights of the data values in the spline value and `sigma^2` is the residual
+/// variance estimate `sum(w_j * r_j^2) / (n - tr(H))`. The intervals are computed by the normal
+/// approximation, so they are pointwise (not simultaneous) intervals.
+///
+/// All arrays have the shape of the evaluated spline values. The residual variance and the effective
+/// degrees of freedom are computed for every data series (the 1-d lanes of Y-data along the axis).
+///
+#[derive(Debug, Clone)]
+pub struct SmoothingBands<T, D>
+    where
+        T: Real<T>,
+        D: Dimension + RemoveAxis,
+{
+    /// The confidence level of the intervals
+    level: T,
+
+    /// The spline values
+    values: Array<T, D>,
+
+    /// The standard errors of the spline values
+    std_errors: Array<T, D>,
+
+    /// The standard errors of the predicted new observations
+    prediction_std_errors: Array<T, D>,
+
+    /// The lower and the upper bounds of the confidence intervals
+    confidence: (Array<T, D>, Array<T, D>),
+
+    /// The lower and the upper bounds of the prediction intervals
+    prediction: (Array<T, D>, Array<T, D>),
+
+    /// The residual variance estimates
+    residual_variance: Array<T, D::Smaller>,
+
+    /// The effective degrees of freedom (the traces of the smoother matrices)
+    dof: Array<T, D::Smaller>,
+}
+
+
+impl<T, D> SmoothingBands<T, D>
+    where
+        T: Real<T>,
+        D: Dimension + RemoveAxis,
+{
+    /// Returns the confidence level of the intervals
+    pub fn level(&self) -> T {
+        self.level
+    }
+
+    /// Returns the view to the spline values
+    pub fn values(&self) -> ArrayView<'_, T, D> {
+        self.values.view()
+    }
+
+    /// Returns the view to the standard errors of the spline values
+    pub fn std_errors(&self) -> ArrayView<'_, T, D> {
+        self.std_errors.view()
+    }
+
+    /// Returns the view to the standard errors of the predicted new observations with unit weights
+    pub fn prediction_std_errors(&self) -> ArrayView<'_, T, D> {
+        self.prediction_std_errors.view()
+    }
+
+    /// Returns the views to the lower and the upper bounds of the confidence intervals
+    pub fn confidence(&self) -> (ArrayView<'_, T, D>, ArrayView<'_, T, D>) {
+        (self.confidence.0.view(), self.confidence.1.view())
+    }
+
+    /// Returns the views to the lower and the upper bounds of the prediction intervals
+    pub fn prediction(&self) -> (ArrayView<'_, T, D>, ArrayView<'_, T, D>) {
+        (self.prediction.0.view(), self.prediction.1.view())
+    }
+
+    /// Returns the view to the residual variance estimates
+    ///
+    /// The variance is NaN if there are no residual degrees of freedom (the interpolating spline).
+    pub fn residual_variance(&self) -> ArrayView<'_, T, D::Smaller> {
+        self.residual_variance.view()
+    }
+
+    /// Returns the view to the effective degrees of freedom of the smoothing spline
+    pub fn dof(&self) -> ArrayView<'_, T, D::Smaller> {
+        self.dof.view()
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension + RemoveAxis
+{
+    /// Computes the pointwise standard errors and the confidence and prediction intervals
+    /// of the computed spline on the given data sites
+    ///
+    /// The bands are computed for the smoothing parameter and the weights (including the robustness
+    /// weights) which have been used for computing the spline. The prediction intervals are computed
+    /// for the new observations with unit weights.
+    ///
+    /// Computing the bands requires the smoother matrix columns (the splines for the unit data vectors)
+    /// evaluated on `xi`. The columns are computed by the chunks and the system of the natural and
+    /// the periodic splines is factorized once, so the memory is linear in the number of the data sites `n`
+    /// and the evaluated data sites `k`, but the cost is `O(n * (n + k))`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `xi` data is invalid
+    /// - If the spline yet has not been computed
+    /// - If `level` is not in range `(0, 1)`
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = Array1::linspace(0., 6., 25);
+    /// let y = x.mapv(f64::sin) + array![
+    ///     0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+    ///     0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+    ///
+    /// let xi = Array1::linspace(0., 6., 61);
+    ///
+    /// let bands = CubicSmoothingSpline::new(&x, &y)
+    ///     .make().unwrap()
+    ///     .bands(&xi, 0.95).unwrap();
+    ///
+    /// let (lower, upper) = bands.confidence();
+    /// assert!(lower.iter().zip(upper.iter()).all(|(l, u)| l < u));
+    /// ```
+    ///
+    pub fn bands<X>(&self, xi: X, level: T) -> Result<SmoothingBands<T, D>>
+        where
+            X: AsArray<'a, T>
+    {
+        let xi = xi.into();
+        self.evaluate_validate(xi)?;
+
+        if !(level > T::zero() && level < T::one()) {
+            return Err(
+                InvalidInputData(
+                    format!("The confidence level must be in range (0, 1), given {:?}", level)
+                )
+            )
+        }
+
+        let influence = self.influence()?;
+        let values = self.evaluate_spline(xi, 0)?;
+
+        let (m, k) = (influence.residuals.nrows(), xi.len());
+
+        let mut variance = Array2::<T>::zeros((m, k));
+        let mut residual_variance = Array1::<T>::zeros(m);
+        let mut dof = Array1::<T>::zeros(m);
+
+        // The variances are computed once for every distinct smoother matrix
+        let smoother_variance = influence.smoothers().iter()
+            .map(|smoother| self.variance_factors(&influence, smoother, xi))
+            .collect::<Result<Vec<_>>>()?;
+
+        for series in 0..m {
+            let statistics = influence.statistics(series);
+
+            // There are no residual degrees of freedom for the interpolating spline
+            let df = statistics.count - statistics.dof;
+
+            residual_variance[series] = if df > T::epsilon().sqrt() * statistics.count {
+                statistics.wrss / df
+            } else {
+                T::nan()
+            };
+            dof[series] = statistics.dof;
+
+            variance.row_mut(series).assign(&smoother_variance[influence.smoother_index(series)]);
+        }
+
+        let z = T::from(normal_quantile(0.5 + level.to_f64().unwrap() / 2.0)).unwrap();
+
+        let sigma2 = residual_variance.view().insert_axis(Axis(1));
+        let std_errors = (&variance * &sigma2).mapv(T::sqrt);
+        let prediction_std_errors = ((&variance + T::one()) * sigma2).mapv(T::sqrt);
+
+        let std_errors = self.reshape_from_2d(&std_errors)?;
+        let prediction_std_errors = self.reshape_from_2d(&prediction_std_errors)?;
+
+        let axis = self.axis.unwrap();
+        let series_shape = self.y.raw_dim().remove_axis(axis);
+
+        Ok(SmoothingBands {
+            level,
+            confidence: (&values - &(&std_errors * z), &values + &(&std_errors * z)),
+            prediction: (&values - &(&prediction_std_errors * z), &values + &(&prediction_std_errors * z)),
+            values,
+            std_errors,
+            prediction_std_errors,
+            residual_variance: residual_variance.into_shape(series_shape.clone()).unwrap(),
+            dof: dof.into_shape(series_shape).unwrap(),
+        })
+    }
+
+    /// Computes the factors `sum(l_j^2 / w_j)` of the variance of the spline values at `xi` for the smoother matrix
+    ///
+    /// The smoother matrix columns are evaluated by the chunks, so the full smoother matrix is not stored.
+    fn variance_factors(&self, influence: &Influence<T>, smoother: &Smoother<T>, xi: ArrayView1<'_, T>) -> Result<Array1<T>> {
+        let mut factors = Array1::<T>::zeros(xi.raw_dim());
+
+        let (breaks, weights) = (influence.breaks.view(), influence.weights.view());
+
+        self.for_each_smoother_columns(breaks, weights, smoother.mask.view(), |first, columns| {
+            let values = columns.evaluate_extrapolated(xi, self.extrapolation)?;
+
+            for (j, row) in (first..).zip(values.outer_iter()) {
+                if smoother.mask[j] {
+                    let w = weights[j];
+                    factors.zip_mut_with(&row, |v, &l| *v += l * l / w);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok(factors)
+    }
+}
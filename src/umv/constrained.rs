@@ -0,0 +1,236 @@
+use ndarray::prelude::*;
+
+use crate::{
+    Real,
+    Result,
+    EndCondition,
+    CsapsError::InvalidInputData,
+    banded::BandedMatrix,
+    ndarrayext::diff,
+};
+
+use super::make::piecewise_coeffs;
+
+
+/// The number of the unknowns per data site: the value, the second derivative and the multiplier
+const SITE_UNKNOWNS: usize = 3;
+
+/// The bandwidth of the system matrix
+///
+/// The widest rows are the not-a-knot conditions which couple the second derivatives
+/// at three data sites with the multiplier of the first or the last data site.
+const BANDWIDTH: usize = 7;
+
+
+/// The linear system for computing cubic smoothing spline with the given end conditions
+///
+/// The spline is represented by the values `f` and the second derivatives `m` at the data sites.
+/// The system is KKT system for minimizing `p * sum(w * (y - f)^2) + (1 - p) * integral(f''^2)`
+/// subject to the continuity of the first derivative at the interior data sites and the end
+/// conditions. The unknowns are ordered by the data sites as `[f_i, m_i, l_i]` where `l_i` is
+/// the Lagrange multiplier of the condition for `i`-th data site (the continuity condition
+/// for the interior data sites and the end conditions for the first and the last data sites),
+/// so the matrix is banded and it is solved by LU factorization.
+pub(super) struct ConstrainedSystem<T>
+    where
+        T: Real<T>
+{
+    /// The differences of the data sites
+    dx: Array1<T>,
+
+    /// The data weights
+    weights: Array1<T>,
+
+    /// The condition at the first data site
+    start: EndCondition<T>,
+
+    /// The condition at the last data site
+    end: EndCondition<T>,
+}
+
+
+impl<T> ConstrainedSystem<T>
+    where
+        T: Real<T>
+{
+    /// Creates the system for the given data sites, weights and end conditions
+    ///
+    /// The number of the data sites must be greater or equal to 2.
+    pub(super) fn new(
+        breaks: ArrayView1<'_, T>,
+        weights: ArrayView1<'_, T>,
+        start: EndCondition<T>,
+        end: EndCondition<T>,
+    ) -> Self {
+        ConstrainedSystem {
+            dx: diff(breaks, None),
+            weights: weights.to_owned(),
+            start,
+            end,
+        }
+    }
+
+    /// Returns the number of the data sites
+    fn size(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn f(i: usize) -> usize { SITE_UNKNOWNS * i }
+    fn m(i: usize) -> usize { SITE_UNKNOWNS * i + 1 }
+    fn l(i: usize) -> usize { SITE_UNKNOWNS * i + 2 }
+
+    /// Returns the coefficients and the right-hand side value of the condition for `i`-th data site
+    fn condition(&self, i: usize) -> (Vec<(usize, T)>, T) {
+        let one = T::one();
+        let three = T::from::<f64>(3.0).unwrap();
+        let six = T::from::<f64>(6.0).unwrap();
+
+        let n = self.size();
+        let dx = &self.dx;
+
+        let (f, m) = (Self::f, Self::m);
+
+        // Both not-a-knot conditions for 3 data sites refer to the same data site,
+        // so the whole spline is the single parabola with zero third derivative
+        let parabola = n == 3
+            && self.start == EndCondition::NotAKnot
+            && self.end == EndCondition::NotAKnot;
+
+        if i == 0 {
+            let h = dx[0];
+
+            match self.start {
+                EndCondition::Natural => (vec![(m(0), one)], T::zero()),
+                EndCondition::SecondDerivative(value) => (vec![(m(0), one)], value),
+                EndCondition::Clamped(value) => (
+                    vec![(f(0), -one / h), (f(1), one / h), (m(0), -h / three), (m(1), -h / six)],
+                    value,
+                ),
+                EndCondition::NotAKnot if n == 2 || parabola => (vec![(m(0), -one), (m(1), one)], T::zero()),
+                EndCondition::NotAKnot => {
+                    let h1 = dx[1];
+                    (vec![(m(0), -one / h), (m(1), one / h + one / h1), (m(2), -one / h1)], T::zero())
+                },
+            }
+        } else if i == n - 1 {
+            let h = dx[n - 2];
+
+            match self.end {
+                EndCondition::Natural => (vec![(m(n - 1), one)], T::zero()),
+                EndCondition::SecondDerivative(value) => (vec![(m(n - 1), one)], value),
+                EndCondition::Clamped(value) => (
+                    vec![(f(n - 2), -one / h), (f(n - 1), one / h), (m(n - 2), h / six), (m(n - 1), h / three)],
+                    value,
+                ),
+                EndCondition::NotAKnot if n == 2 || parabola => (vec![(m(n - 2), -one), (m(n - 1), one)], T::zero()),
+                EndCondition::NotAKnot => {
+                    let h0 = dx[n - 3];
+                    (vec![(m(n - 3), one / h0), (m(n - 2), -one / h0 - one / h), (m(n - 1), one / h)], T::zero())
+                },
+            }
+        } else {
+            let (h0, h1) = (dx[i - 1], dx[i]);
+
+            let coeffs = vec![
+                (f(i - 1), -one / h0),
+                (f(i), one / h0 + one / h1),
+                (f(i + 1), -one / h1),
+                (m(i - 1), h0 / six),
+                (m(i), (h0 + h1) / three),
+                (m(i + 1), h1 / six),
+            ];
+
+            (coeffs, T::zero())
+        }
+    }
+
+    /// Returns true if the value at `i`-th data site is fixed by the interpolation condition
+    fn is_fixed(&self, i: usize, smooth: T) -> bool {
+        smooth == T::one() && self.weights[i] > T::zero()
+    }
+
+    /// Returns the system matrix for the given smoothing parameter
+    ///
+    /// For the unit smoothing parameter the values at the data sites with positive weights are
+    /// fixed by the interpolation conditions instead of the data term, so the integral of
+    /// the squared second derivative is still minimized for the data sites with zero weights.
+    fn matrix(&self, smooth: T) -> BandedMatrix<T> {
+        let one = T::one();
+        let three = T::from::<f64>(3.0).unwrap();
+        let six = T::from::<f64>(6.0).unwrap();
+
+        let n = self.size();
+        let s1 = if smooth == one { one } else { one - smooth };
+
+        let mut a = BandedMatrix::zeros(SITE_UNKNOWNS * n, BANDWIDTH, BANDWIDTH);
+
+        for i in 0..n {
+            if self.is_fixed(i, smooth) {
+                a.add(Self::f(i), Self::f(i), one);
+            } else {
+                a.add(Self::f(i), Self::f(i), smooth * self.weights[i]);
+            }
+
+            for (j, c) in self.condition(i).0 {
+                a.add(Self::l(i), j, c);
+
+                if j % SITE_UNKNOWNS != 0 || !self.is_fixed(j / SITE_UNKNOWNS, smooth) {
+                    a.add(j, Self::l(i), c);
+                }
+            }
+        }
+
+        // The integral of the squared second derivative is the quadratic form of
+        // the second derivatives at the data sites
+        for (j, &h) in self.dx.iter().enumerate() {
+            a.add(Self::m(j), Self::m(j), s1 * h / three);
+            a.add(Self::m(j + 1), Self::m(j + 1), s1 * h / three);
+            a.add(Self::m(j), Self::m(j + 1), s1 * h / six);
+            a.add(Self::m(j + 1), Self::m(j), s1 * h / six);
+        }
+
+        a
+    }
+
+    /// Solves the system for the given 2-d `y` and the smoothing parameter
+    ///
+    /// The system is singular for zero smoothing parameter because the spline is not unique,
+    /// so the smoothing parameter must be positive.
+    ///
+    /// Returns the smoothed values and the second derivatives divided by 6 at the data sites,
+    /// both arrays have shape `[n, m]` where `n` is the number of the data sites.
+    pub(super) fn solve(&self, y: ArrayView2<'_, T>, smooth: T) -> Result<(Array2<T>, Array2<T>)> {
+        let n = self.size();
+        let six = T::from::<f64>(6.0).unwrap();
+
+        let lu = self.matrix(smooth)
+            .factorize()
+            .ok_or_else(|| InvalidInputData(
+                "The system for the given end conditions is singular".to_string()
+            ))?;
+
+        let mut b = Array2::<T>::zeros((SITE_UNKNOWNS * n, y.nrows()));
+
+        for i in 0..n {
+            if self.is_fixed(i, smooth) {
+                b.row_mut(Self::f(i)).assign(&y.column(i));
+            } else {
+                b.row_mut(Self::f(i)).assign(&(&y.column(i) * (smooth * self.weights[i])));
+            }
+            b.row_mut(Self::l(i)).fill(self.condition(i).1);
+        }
+
+        let x = lu.solve(b.view());
+
+        let yi = x.slice(s![0..;SITE_UNKNOWNS, ..]).to_owned();
+        let c3 = x.slice(s![1..;SITE_UNKNOWNS, ..]).mapv(|v| v / six);
+
+        Ok((yi, c3))
+    }
+
+    /// Computes and concatenates the spline coefficients from the smoothed values
+    /// and the second derivatives divided by 6
+    pub(super) fn coeffs(&self, yi: &Array2<T>, c3: &Array2<T>) -> Array2<T> {
+        piecewise_coeffs(self.dx.view(), yi, c3)
+    }
+}
@@ -0,0 +1,449 @@
+use ndarray::prelude::*;
+
+use crate::{
+    CsapsError::InvalidInputData,
+    Parameterization,
+    Real,
+    RealRef,
+    Result,
+    validate::validate_smooth_value,
+};
+
+use super::{CubicSmoothingSpline, NdSpline, geometry::ArcLength};
+
+
+/// Parametric cubic smoothing spline curve through the sequence of the points
+///
+/// The points of 2-d, 3-d (or any dimensional) curve are parameterized by the distances between
+/// the consecutive points (see `Parameterization`), the parameter is normalized to the range `[0, 1]`,
+/// and the coordinates of the points are smoothed by the multivariate cubic smoothing spline
+/// of the parameter.
+///
+/// The curve can be evaluated by the normalized parameter or by the arc length, so the points
+/// evaluated at the uniformly spaced arc lengths are uniformly spaced along the curve.
+/// The curvature, the torsion and the tangent and normal vectors of the curve are computed
+/// by the spline returned by `spline` method.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array1};
+/// use csaps::CubicSmoothingCurve;
+///
+/// let points = array![
+///     [0.0, 0.0],
+///     [1.0, 0.2],
+///     [2.1, 0.9],
+///     [2.9, 2.1],
+///     [3.2, 3.0],
+/// ];
+///
+/// let curve = CubicSmoothingCurve::new(&points)
+///     .with_smooth(0.95)
+///     .make().unwrap();
+///
+/// // The points evaluated by the normalized parameter with shape `[10, 2]`
+/// let ci = curve.evaluate(&Array1::linspace(0., 1., 10)).unwrap();
+///
+/// // The points uniformly spaced along the curve
+/// let length = curve.length().unwrap();
+/// let cs = curve.evaluate_arc_length(&Array1::linspace(0., length, 10)).unwrap();
+///
+/// assert_eq!(ci.shape(), &[10, 2]);
+/// assert_eq!(cs.shape(), &[10, 2]);
+/// ```
+///
+pub struct CubicSmoothingCurve<'a, T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+{
+    /// The points of the curve with shape `[npoints, ndim]`
+    points: ArrayView2<'a, T>,
+
+    /// The optional weights of the points
+    weights: Option<ArrayView1<'a, T>>,
+
+    /// The optional smoothing parameter
+    smooth: Option<T>,
+
+    /// The flag of the normalized smoothing parameter mode
+    normalized_smooth: bool,
+
+    /// The parameterization mode of the points
+    parameterization: Parameterization,
+
+    /// The smoothing parameter which has been used for computing the curve
+    selected_smooth: Option<T>,
+
+    /// The normalized parameter values of the points which are computed by `make`
+    parameter: Option<Array1<T>>,
+
+    /// The spline of the coordinates by the normalized parameter
+    spline: Option<NdSpline<T>>,
+
+    /// The arc length of the computed curve
+    arc_length: Option<ArcLength<T>>,
+}
+
+
+impl<'a, T> CubicSmoothingCurve<'a, T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+{
+    /// Creates `CubicSmoothingCurve` struct from the given points
+    ///
+    /// `points` is 2-d array-like with shape `[npoints, ndim]`, every row is the point of the curve.
+    pub fn new<P>(points: P) -> Self
+        where
+            P: AsArray<'a, T, Ix2>
+    {
+        CubicSmoothingCurve {
+            points: points.into(),
+            weights: None,
+            smooth: None,
+            normalized_smooth: false,
+            parameterization: Parameterization::default(),
+            selected_smooth: None,
+            parameter: None,
+            spline: None,
+            arc_length: None,
+        }
+    }
+
+    /// Sets the weights of the points
+    ///
+    /// `weights.len()` must be equal to the number of the points
+    pub fn with_weights<W>(mut self, weights: W) -> Self
+        where
+            W: AsArray<'a, T>
+    {
+        self.invalidate();
+        self.weights = Some(weights.into());
+        self
+    }
+
+    /// Sets the weights of the points in `Option` wrap
+    pub fn with_optional_weights<W>(mut self, weights: Option<W>) -> Self
+        where
+            W: AsArray<'a, T>
+    {
+        self.invalidate();
+        self.weights = weights.map(|w| w.into());
+        self
+    }
+
+    /// Sets the smoothing parameter
+    ///
+    /// See `CubicSmoothingSpline::with_smooth` for details. The smoothing parameter depends on
+    /// the normalized parameter of the points, so the normalized smoothing parameter mode
+    /// is useful for choosing the smoothing parameter independently of the number of the points.
+    pub fn with_smooth(mut self, smooth: T) -> Self {
+        self.invalidate();
+        self.smooth = Some(smooth);
+        self
+    }
+
+    /// Sets the smoothing parameter in `Option` wrap
+    pub fn with_optional_smooth(mut self, smooth: Option<T>) -> Self {
+        self.invalidate();
+        self.smooth = smooth;
+        self
+    }
+
+    /// Sets the normalized smoothing parameter mode
+    ///
+    /// See `CubicSmoothingSpline::with_normalized_smooth` for details.
+    pub fn with_normalized_smooth(mut self, normalized_smooth: bool) -> Self {
+        self.invalidate();
+        self.normalized_smooth = normalized_smooth;
+        self
+    }
+
+    /// Sets the parameterization mode of the points
+    ///
+    /// By default the chord-length parameterization is used.
+    pub fn with_parameterization(mut self, parameterization: Parameterization) -> Self {
+        self.invalidate();
+        self.parameterization = parameterization;
+        self
+    }
+
+    /// Returns the smoothing parameter or None
+    ///
+    /// After making the curve the smoothing parameter which has been used for computing it
+    /// is returned. In the normalized smoothing parameter mode the normalized value is returned.
+    pub fn smooth(&self) -> Option<T> {
+        self.selected_smooth.or(self.smooth)
+    }
+
+    /// Returns the flag of the normalized smoothing parameter mode
+    pub fn normalized_smooth(&self) -> bool {
+        self.normalized_smooth
+    }
+
+    /// Returns the parameterization mode of the points
+    pub fn parameterization(&self) -> Parameterization {
+        self.parameterization
+    }
+
+    /// Returns the normalized parameter values of the points or None if the curve has not been computed
+    pub fn parameter(&self) -> Option<ArrayView1<'_, T>> {
+        self.parameter.as_ref().map(|p| p.view())
+    }
+
+    /// Returns the ref to the spline of the coordinates by the normalized parameter
+    /// or None if the curve has not been computed
+    ///
+    /// The spline dimensionality is equal to the dimensionality of the points.
+    pub fn spline(&self) -> Option<&NdSpline<T>> {
+        self.spline.as_ref()
+    }
+
+    /// Returns the length of the curve or None if the curve has not been computed
+    pub fn length(&self) -> Option<T> {
+        self.arc_length.as_ref().map(|a| a.length())
+    }
+
+    /// Invalidate computed curve
+    fn invalidate(&mut self) {
+        self.selected_smooth = None;
+        self.parameter = None;
+        self.spline = None;
+        self.arc_length = None;
+    }
+
+    /// Makes (computes) the parameter of the points and the spline of the curve
+    ///
+    /// # Errors
+    ///
+    /// - If the points or the parameters are invalid
+    /// - If any consecutive points coincide for chord-length and centripetal parameterizations
+    ///
+    pub fn make(mut self) -> Result<Self> {
+        self.invalidate();
+        self.make_validate()?;
+
+        let parameter = self.make_parameter()?;
+
+        let (smooth, spline) = {
+            // The views are reborrowed for the lifetime of the parameter
+            let (points, weights) = (self.points, self.weights);
+
+            let s = CubicSmoothingSpline::new(&parameter, points.t())
+                .with_optional_weights(weights.as_ref())
+                .with_optional_smooth(self.smooth)
+                .with_normalized_smooth(self.normalized_smooth)
+                .make()?;
+
+            (s.smooth(), s.into_spline().unwrap())
+        };
+
+        self.selected_smooth = smooth;
+
+        self.arc_length = Some(ArcLength::new(&spline));
+        self.spline = Some(spline);
+        self.parameter = Some(parameter);
+
+        Ok(self)
+    }
+
+    /// Evaluates the curve at the given normalized parameter values
+    ///
+    /// Returns 2-d array with shape `[u.len(), ndim]` of the points of the curve. The curve is
+    /// extrapolated by the polynomial pieces for the parameter values out of the range `[0, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// - If `u` is empty
+    /// - If the curve yet has not been computed
+    ///
+    pub fn evaluate<'b, U>(&self, u: U) -> Result<Array2<T>>
+        where
+            U: AsArray<'b, T>,
+            T: 'b
+    {
+        let u = u.into();
+        self.evaluate_validate(u)?;
+
+        let spline = self.spline.as_ref().unwrap();
+        let mut values = Array2::zeros((u.len(), spline.ndim()));
+
+        spline.evaluate_into(u, values.view_mut().reversed_axes())?;
+
+        Ok(values)
+    }
+
+    /// Evaluates the curve at the given arc lengths from the first point
+    ///
+    /// Returns 2-d array with shape `[s.len(), ndim]` of the points of the curve.
+    ///
+    /// # Errors
+    ///
+    /// - If `s` is empty
+    /// - If the curve yet has not been computed
+    /// - If any arc length is out of the range `[0, length]`
+    ///
+    pub fn evaluate_arc_length<'b, S>(&self, s: S) -> Result<Array2<T>>
+        where
+            S: AsArray<'b, T>,
+            T: 'b
+    {
+        let u = self.arc_length_parameter(s)?;
+        self.evaluate(&u)
+    }
+
+    /// Computes the arc lengths from the first point to the given normalized parameter values
+    ///
+    /// The arc lengths are negative for the parameter values less than 0.
+    ///
+    /// # Errors
+    ///
+    /// - If `u` is empty
+    /// - If the curve yet has not been computed
+    ///
+    pub fn arc_length<'b, U>(&self, u: U) -> Result<Array1<T>>
+        where
+            U: AsArray<'b, T>,
+            T: 'b
+    {
+        let u = u.into();
+        self.evaluate_validate(u)?;
+
+        let arc_length = self.arc_length.as_ref().unwrap();
+
+        Ok(u.mapv(|u| arc_length.arc_length(u)))
+    }
+
+    /// Computes the normalized parameter values at the given arc lengths from the first point
+    ///
+    /// This is the arc-length reparameterization of the curve, the inverse of `arc_length`.
+    ///
+    /// # Errors
+    ///
+    /// - If `s` is empty
+    /// - If the curve yet has not been computed
+    /// - If any arc length is out of the range `[0, length]`
+    ///
+    pub fn arc_length_parameter<'b, S>(&self, s: S) -> Result<Array1<T>>
+        where
+            S: AsArray<'b, T>,
+            T: 'b
+    {
+        let s = s.into();
+        self.evaluate_validate(s)?;
+
+        let arc_length = self.arc_length.as_ref().unwrap();
+        let length = arc_length.length();
+
+        if let Some(s) = s.iter().find(|&&s| !(s >= T::zero() && s <= length)) {
+            return Err(
+                InvalidInputData(
+                    format!("The arc length {:?} is out of the curve length range [0, {:?}]", s, length)
+                )
+            )
+        }
+
+        Ok(s.mapv(|s| arc_length.parameter(s)))
+    }
+
+    fn make_validate(&self) -> Result<()> {
+        let (npoints, ndim) = self.points.dim();
+
+        if npoints < 2 {
+            return Err(
+                InvalidInputData(
+                    "The number of the points must be greater or equal to 2".to_string()
+                )
+            )
+        }
+
+        if ndim < 1 {
+            return Err(
+                InvalidInputData(
+                    "The points must have at least 1 coordinate".to_string()
+                )
+            )
+        }
+
+        if self.points.iter().any(|v| !v.is_finite()) {
+            return Err(
+                InvalidInputData(
+                    "`points` data contains missing (NaN) or infinite values".to_string()
+                )
+            )
+        }
+
+        if let Some(weights) = self.weights {
+            let w_size = weights.len();
+
+            if w_size != npoints {
+                return Err(
+                    InvalidInputData(
+                        format!("`weights` size ({}) is not equal to the number of the points ({})", w_size, npoints)
+                    )
+                )
+            }
+        }
+
+        if let Some(smooth) = self.smooth {
+            validate_smooth_value(smooth)?;
+        }
+
+        Ok(())
+    }
+
+    /// Computes the normalized parameter of the points
+    fn make_parameter(&self) -> Result<Array1<T>> {
+        let npoints = self.points.nrows();
+        let mut parameter = Array1::<T>::zeros(npoints);
+
+        for i in 1..npoints {
+            let distance = (&self.points.row(i) - &self.points.row(i - 1))
+                .fold(T::zero(), |acc, &d| acc + d * d)
+                .sqrt();
+
+            let increment = match self.parameterization {
+                Parameterization::Uniform => T::one(),
+                Parameterization::ChordLength => distance,
+                Parameterization::Centripetal => distance.sqrt(),
+            };
+
+            if increment <= T::zero() {
+                return Err(
+                    InvalidInputData(
+                        format!("The consecutive points {} and {} coincide", i - 1, i)
+                    )
+                )
+            }
+
+            parameter[i] = parameter[i - 1] + increment;
+        }
+
+        let total = parameter[npoints - 1];
+        parameter.mapv_inplace(|p| p / total);
+
+        Ok(parameter)
+    }
+
+    fn evaluate_validate(&self, u: ArrayView1<'_, T>) -> Result<()> {
+        if u.is_empty() {
+            return Err(
+                InvalidInputData(
+                    "The size of the evaluated vector must be greater or equal to 1".to_string()
+                )
+            )
+        }
+
+        if self.spline.is_none() {
+            return Err(
+                InvalidInputData(
+                    "The curve has not been computed, use `make` method before".to_string()
+                )
+            )
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use ndarray::{prelude::*, s};
+
+use crate::{Real, RealRef, Result};
+
+use super::{CubicSmoothingSpline, NdSpline};
+
+
+impl<T> NdSpline<T>
+    where
+        T: Real<T>
+{
+    /// Returns the derivative of the given order `nu` of the spline as a new PP-form spline
+    ///
+    /// The order of the returned spline is reduced by `nu`. If `nu` is greater or equal
+    /// to the spline order, the returned spline is zero spline of order 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![1., 3., 5., 7.];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let ds = s.spline().unwrap().derivative(1);
+    ///
+    /// assert_eq!(ds.order(), 3);
+    /// assert_eq!(ds.evaluate(x.view()), array![[2., 2., 2., 2.]]);
+    /// ```
+    ///
+    pub fn derivative(&self, nu: usize) -> NdSpline<T> {
+        let (_, coeffs) = Self::derivative_coeffs(
+            self.order, self.pieces, self.coeffs.view(), nu);
+
+        NdSpline {
+            smooth: self.smooth,
+            periodic: self.periodic,
+            ..NdSpline::new(&self.breaks, coeffs)
+        }
+    }
+
+    /// Evaluates the derivative of the given order `nu` of the spline on the given data sites
+    pub fn evaluate_derivative(&self, xi: ArrayView1<'_, T>, nu: usize) -> Array2<T> {
+        let (order, coeffs) = Self::derivative_coeffs(
+            self.order, self.pieces, self.coeffs.view(), nu);
+
+        Self::evaluate_spline(order, self.pieces, self.breaks.view(), coeffs.view(), xi, self.periodic)
+    }
+
+    /// Implements computing the coefficients of the spline derivative
+    ///
+    /// Returns the order of the derivative spline and its coefficients array.
+    /// The coefficients of the pieces are stored by descending powers, so the derivative
+    /// coefficients are the first `order - nu` coefficient blocks scaled by the power factors.
+    pub(crate) fn derivative_coeffs(
+        order: usize,
+        pieces: usize,
+        coeffs: ArrayView2<'_, T>,
+        nu: usize,
+    ) -> (usize, Array2<T>) {
+        if nu >= order {
+            return (1, Array2::zeros((coeffs.nrows(), pieces)))
+        }
+
+        let d_order = order - nu;
+        let mut d_coeffs = coeffs.slice(s![.., ..d_order * pieces]).to_owned();
+
+        for k in 0..d_order {
+            let power = order - 1 - k;
+            let factor = ((power - nu + 1)..=power)
+                .fold(T::one(), |acc, p| acc * T::from(p).unwrap());
+
+            d_coeffs
+                .slice_mut(s![.., k * pieces..(k + 1) * pieces])
+                .mapv_inplace(|c| c * factor);
+        }
+
+        (d_order, d_coeffs)
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension
+{
+    /// Evaluates the derivative of the given order `nu` of the computed spline on the given data sites
+    ///
+    /// The output array has the same shape as `evaluate` output.
+    /// For cubic splines the 1st, 2nd and 3rd derivatives are non-zero in general,
+    /// the derivatives of higher orders are always zero.
+    ///
+    /// # Errors
+    ///
+    /// - If the `xi` data is invalid
+    /// - If the spline yet has not been computed
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![[1., 2., 3., 4.], [2., 4., 6., 8.]];
+    ///
+    /// let dy = CubicSmoothingSpline::new(&x, &y)
+    ///     .make().unwrap()
+    ///     .evaluate_derivative(&x, 1).unwrap();
+    ///
+    /// assert_eq!(dy, array![[1., 1., 1., 1.], [2., 2., 2., 2.]]);
+    /// ```
+    ///
+    pub fn evaluate_derivative<X>(&self, xi: X, nu: usize) -> Result<Array<T, D>>
+        where
+            X: AsArray<'a, T>
+    {
+        let xi = xi.into();
+        self.evaluate_validate(xi)?;
+
+        let yi = self.evaluate_spline(xi, nu)?;
+        Ok(yi)
+    }
+}
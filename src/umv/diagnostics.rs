@@ -0,0 +1,174 @@
+use ndarray::{prelude::*, RemoveAxis};
+
+use crate::{Real, RealRef, Result};
+
+use super::CubicSmoothingSpline;
+
+
+/// The diagnostics of the computed smoothing spline fit
+///
+/// The residuals and the diagonal of the influence (hat) matrix have the shape of Y-data,
+/// the residuals of the missing values are NaN and their influence is zero. In the data sites
+/// sorting mode they are computed for the sorted unique data sites. The statistics are computed for every data series
+/// (the 1-d lanes of Y-data along the axis) and for all data:
+///
+/// - the weighted residual sum of squares `sum(w_i * r_i^2)`
+/// - the effective degrees of freedom `tr(H)`
+/// - the generalized cross-validation score `n * sum(w_i * r_i^2) / (n - tr(H))^2`
+///
+/// The weights include the robustness weights for the robust spline.
+///
+#[derive(Debug, Clone)]
+pub struct FitDiagnostics<T, D>
+    where
+        T: Real<T>,
+        D: Dimension + RemoveAxis,
+{
+    /// The residuals `y - f(x)`
+    residuals: Array<T, D>,
+
+    /// The diagonal of the influence matrix
+    influence_diag: Array<T, D>,
+
+    /// The weighted residual sums of squares of the data series
+    series_wrss: Array<T, D::Smaller>,
+
+    /// The effective degrees of freedom of the data series
+    series_dof: Array<T, D::Smaller>,
+
+    /// The generalized cross-validation scores of the data series
+    series_gcv: Array<T, D::Smaller>,
+}
+
+
+impl<T, D> FitDiagnostics<T, D>
+    where
+        T: Real<T>,
+        D: Dimension + RemoveAxis,
+{
+    /// Returns the view to the residuals `y - f(x)`
+    pub fn residuals(&self) -> ArrayView<'_, T, D> {
+        self.residuals.view()
+    }
+
+    /// Returns the view to the diagonal of the influence (hat) matrix (the leverages of the data values)
+    pub fn influence_diag(&self) -> ArrayView<'_, T, D> {
+        self.influence_diag.view()
+    }
+
+    /// Returns the weighted residual sum of squares over all data series
+    pub fn wrss(&self) -> T {
+        self.series_wrss.sum()
+    }
+
+    /// Returns the effective degrees of freedom averaged over the data series
+    ///
+    /// The degrees of freedom are equal for all data series unless the missing values
+    /// are handled per series.
+    pub fn dof(&self) -> T {
+        self.series_dof.sum() / T::from(self.series_dof.len()).unwrap()
+    }
+
+    /// Returns the generalized cross-validation score averaged over the data series
+    ///
+    /// The score is equal to the cross-validation score of `CrossValidation::Generalized`
+    /// criterion for the computed smoothing parameter.
+    pub fn gcv(&self) -> T {
+        self.series_gcv.sum() / T::from(self.series_gcv.len()).unwrap()
+    }
+
+    /// Returns the view to the weighted residual sums of squares of the data series
+    pub fn series_wrss(&self) -> ArrayView<'_, T, D::Smaller> {
+        self.series_wrss.view()
+    }
+
+    /// Returns the view to the effective degrees of freedom of the data series
+    pub fn series_dof(&self) -> ArrayView<'_, T, D::Smaller> {
+        self.series_dof.view()
+    }
+
+    /// Returns the view to the generalized cross-validation scores of the data series
+    pub fn series_gcv(&self) -> ArrayView<'_, T, D::Smaller> {
+        self.series_gcv.view()
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension + RemoveAxis
+{
+    /// Computes the diagnostics of the computed spline fit
+    ///
+    /// The influence matrix diagonal of the natural spline is computed from the banded factorization,
+    /// so the cost is linear in the number of the data sites. For the periodic spline the cyclic system
+    /// is solved for every data site and for the end conditions or the missing values the spline
+    /// is computed for the unit data vectors, so the cost is quadratic in these cases.
+    ///
+    /// # Errors
+    ///
+    /// - If the spline yet has not been computed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = Array1::linspace(0., 6., 25);
+    /// let y = x.mapv(f64::sin) + array![
+    ///     0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+    ///     0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+    ///
+    /// let diagnostics = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_dof(8.0)
+    ///     .make().unwrap()
+    ///     .diagnostics().unwrap();
+    ///
+    /// assert!((diagnostics.dof() - 8.0).abs() < 1e-6);
+    /// println!("wrss: {}, gcv: {}", diagnostics.wrss(), diagnostics.gcv());
+    /// ```
+    ///
+    pub fn diagnostics(&self) -> Result<FitDiagnostics<T, D>> {
+        let influence = self.influence()?;
+
+        let residuals = &influence.residuals;
+        let m = residuals.nrows();
+
+        let mut influence_diag = Array2::<T>::zeros(residuals.raw_dim());
+        let mut series_wrss = Array1::<T>::zeros(m);
+        let mut series_dof = Array1::<T>::zeros(m);
+        let mut series_gcv = Array1::<T>::zeros(m);
+
+        for series in 0..m {
+            let smoother = influence.smoother(series);
+            let statistics = influence.statistics(series);
+
+            let mut diag = influence_diag.row_mut(series);
+
+            for j in smoother.sites() {
+                diag[j] = smoother.diag[j];
+            }
+
+            let df = statistics.count - statistics.dof;
+
+            series_wrss[series] = statistics.wrss;
+            series_dof[series] = statistics.dof;
+            series_gcv[series] = statistics.count * statistics.wrss / (df * df);
+        }
+
+        let axis = self.axis.unwrap();
+        let series_shape = self.y.raw_dim().remove_axis(axis);
+
+        Ok(FitDiagnostics {
+            residuals: self.reshape_from_2d(residuals)?,
+            influence_diag: self.reshape_from_2d(&influence_diag)?,
+            series_wrss: series_wrss.into_shape(series_shape.clone()).unwrap(),
+            series_dof: series_dof.into_shape(series_shape.clone()).unwrap(),
+            series_gcv: series_gcv.into_shape(series_shape).unwrap(),
+        })
+    }
+}
@@ -1,68 +1,112 @@
-use ndarray::{concatenate, prelude::*, s};
+use ndarray::{prelude::*, s, RemoveAxis};
 
 use crate::{
-    ndarrayext::{digitize, from_2d},
+    ndarrayext::from_2d,
+    parallel::for_each_chunk,
+    search::{search_piece, PieceSearch},
     util::dim_from_vec,
+    validate::validate_output_shape,
+    CsapsError::InvalidInputData,
     Real, RealRef, Result,
 };
 
 use super::{CubicSmoothingSpline, NdSpline};
 
-impl<'a, T> NdSpline<'a, T>
+
+/// The minimum number of the spline values which are evaluated by one parallel task with `rayon` feature
+const MIN_CHUNK_SIZE: usize = 1024;
+
+
+impl<T> NdSpline<T>
 where
     T: Real<T>,
 {
     /// Implements evaluating the spline on the given mesh of Xi-sites
     ///
-    /// The internal method to avoid copying coeffs array
+    /// The internal method to avoid copying coeffs array. The data sites are wrapped
+    /// into the period if `periodic` is true.
     pub(crate) fn evaluate_spline(
         order: usize,
         pieces: usize,
         breaks: ArrayView1<'_, T>,
         coeffs: ArrayView2<'_, T>,
-        xi: ArrayView1<'a, T>,
+        xi: ArrayView1<'_, T>,
+        periodic: bool,
     ) -> Array2<T> {
-        let edges = {
-            let mesh = breaks.slice(s![1 as i32..-1]);
-            let one = Array1::<T>::ones((1,));
-            let left_bound = &one * T::neg_infinity();
-            let right_bound = &one * T::infinity();
-
-            concatenate![Axis(0), left_bound, mesh, right_bound]
-        };
-
-        let mut indices = digitize(&xi, &edges);
-
-        // Go to local coordinates
-        let xi = {
-            let indexed_breaks = indices.mapv(|i| breaks[i]);
-            &xi - &indexed_breaks
-        };
-
-        // Apply nested multiplication
+        let mut values = Array2::<T>::zeros((coeffs.nrows(), xi.len()));
+        Self::evaluate_spline_into(order, pieces, breaks, coeffs, xi, periodic, values.view_mut(), Axis(1));
+        values
+    }
 
-        // Returns NxM array of coeffs values for given 1xM indices array
-        // where N is ndim and M is the size of xi
-        let get_indexed_coeffs = |inds: &Array1<usize>| {
-            // Returns Nx1 2-d array of coeffs by given index
-            let coeffs_by_index = |&index| coeffs.slice(s![.., index]).insert_axis(Axis(1));
+    /// Implements evaluating the spline on the given mesh of Xi-sites into the values array
+    ///
+    /// The values at `xi[j]` are written to `j`-th subview of `values` along the axis in the logical
+    /// order of its elements, which is the order of the rows of `coeffs`. The data sites are wrapped
+    /// into the period if `periodic` is true. The pieces containing the data sites are found by
+    /// the hinted search, so evaluating is linear in the number of the data sites for the sorted
+    /// data sites or uniformly spaced breaks and the data sites may be in any order.
+    /// Nothing is allocated, the chunks of the data sites are evaluated in parallel with `rayon` feature.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn evaluate_spline_into<D>(
+        order: usize,
+        pieces: usize,
+        breaks: ArrayView1<'_, T>,
+        coeffs: ArrayView2<'_, T>,
+        xi: ArrayView1<'_, T>,
+        periodic: bool,
+        values: ArrayViewMut<'_, T, D>,
+        axis: Axis,
+    )
+        where
+            D: Dimension + RemoveAxis
+    {
+        let search = PieceSearch::new(breaks, pieces);
+        let min_chunk = MIN_CHUNK_SIZE.div_ceil(coeffs.nrows().max(1));
+
+        for_each_chunk(values, axis, min_chunk, |start, mut values| {
+            let mut search = search.clone();
+            let xi = xi.slice(s![start..start + values.len_of(axis)]);
+
+            for (mut site_values, &x) in values.axis_iter_mut(axis).zip(xi.iter()) {
+                let x = if periodic { Self::wrap_site(breaks, x) } else { x };
+                let j = search.find(x);
+                let t = x - breaks[j];
+
+                // Apply nested multiplication
+                for (v, coeffs_row) in site_values.iter_mut().zip(coeffs.outer_iter()) {
+                    *v = (1..order).fold(coeffs_row[j], |acc, k| acc * t + coeffs_row[k * pieces + j]);
+                }
+            }
+        });
+    }
 
-            // Get the M-sized vector of coeffs values Nx1 arrays
-            // for all dimensions for 1xM indices array
-            let indexed_coeffs: Vec<_> = inds.iter().map(coeffs_by_index).collect();
+    /// Returns the piece containing the single data site and the offset of the data site in the piece
+    ///
+    /// The data site is wrapped into the period for the periodic spline.
+    pub(super) fn locate_site(&self, x: T) -> (usize, T) {
+        let x = if self.periodic { Self::wrap_site(self.breaks.view(), x) } else { x };
+        let j = search_piece(self.breaks.view(), self.pieces, x);
 
-            concatenate(Axis(1), &indexed_coeffs).unwrap()
-        };
+        (j, x - self.breaks[j])
+    }
 
-        // Vectorized computing the spline pieces (polynoms) on the given data sites
-        let mut values = get_indexed_coeffs(&indices);
+    /// Evaluates the polynomial piece `j` of the given dimension of the spline at the offset `t`
+    pub(super) fn eval_piece(&self, dim: usize, j: usize, t: T) -> T {
+        let coeffs_row = self.coeffs.row(dim);
+        (1..self.order).fold(coeffs_row[j], |acc, k| acc * t + coeffs_row[k * self.pieces + j])
+    }
 
-        for _ in 1..order {
-            indices += pieces;
-            values = values * &xi + get_indexed_coeffs(&indices);
+    /// Returns the error if the spline is multivariate
+    pub(super) fn validate_univariate(&self, method: &str) -> Result<()> {
+        if self.ndim != 1 {
+            return Err(
+                InvalidInputData(
+                    format!("`{}` requires the univariate spline, the spline dimensionality is {}", method, self.ndim)
+                )
+            )
         }
 
-        values
+        Ok(())
     }
 }
 
@@ -73,16 +117,106 @@ where
 
     D: Dimension,
 {
-    pub(super) fn evaluate_spline(&self, xi: ArrayView1<'a, T>) -> Result<Array<T, D>> {
+    pub(super) fn evaluate_spline(&self, xi: ArrayView1<'a, T>, nu: usize) -> Result<Array<T, D>> {
+        let spline = self.spline.as_ref().unwrap();
+
+        let yi_2d = NdSpline::evaluate_spline_extrapolated(
+            spline.order,
+            spline.pieces,
+            spline.breaks.view(),
+            spline.coeffs.view(),
+            xi,
+            spline.periodic,
+            nu,
+            self.extrapolation,
+        )?;
+
+        self.reshape_from_2d(&yi_2d)
+    }
+
+    /// Reshapes 2-d array with shape `[m, k]` to the shape of `y` data with size `k` along the axis
+    pub(super) fn reshape_from_2d(&self, values: &Array2<T>) -> Result<Array<T, D>> {
         let axis = self.axis.unwrap();
         let mut shape_tmp = self.y.shape().to_owned();
-        shape_tmp[axis.0] = xi.len();
+        shape_tmp[axis.0] = values.ncols();
 
         let shape: D = dim_from_vec(self.y.ndim(), shape_tmp);
 
-        let yi_2d = self.spline.as_ref().unwrap().evaluate(xi);
-        let yi = from_2d(&yi_2d, shape, axis)?.to_owned();
+        Ok(from_2d(values, shape, axis)?.to_owned())
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+where
+    T: Real<T>,
+    for<'r> &'r T: RealRef<&'r T, T>,
+
+    D: Dimension + RemoveAxis,
+{
+    /// Evaluates the computed spline on the given data sites into the given output array
+    ///
+    /// `out` must have the shape of Y-data with `xi.len()` size along the axis. The values
+    /// are written into `out` without allocating, so the spline can be evaluated many times
+    /// into the same buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the `xi` data is invalid
+    /// - If the spline yet has not been computed
+    /// - If the shape of `out` is invalid
+    /// - If the extrapolation mode is `Extrapolation::Error` and `xi` is out of the data sites range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Array2, Axis};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![1., 2., 3., 4.];
+    /// let y = array![[0.5, 1.0], [1.2, 2.0], [3.4, 1.5], [2.5, 0.5]];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_axis(Axis(0))
+    ///     .make().unwrap();
+    ///
+    /// let xi = Array1::linspace(1., 4., 31);
+    /// let mut yi = Array2::<f64>::zeros((31, 2));
+    ///
+    /// s.evaluate_into(&xi, &mut yi).unwrap();
+    ///
+    /// assert_eq!(yi, s.evaluate(&xi).unwrap());
+    /// ```
+    ///
+    pub fn evaluate_into<'b, X, O>(&self, xi: X, out: O) -> Result<()>
+    where
+        X: AsArray<'a, T>,
+        O: Into<ArrayViewMut<'b, T, D>>,
+        T: 'b,
+    {
+        let xi = xi.into();
+        let out = out.into();
+
+        self.evaluate_validate(xi)?;
 
-        Ok(yi)
+        let axis = self.axis.unwrap();
+        let shape = self.y.shape().iter().enumerate().map(|(ax, &size)| if ax == axis.0 { xi.len() } else { size });
+
+        validate_output_shape(out.shape(), shape)?;
+
+        let spline = self.spline.as_ref().unwrap();
+
+        NdSpline::evaluate_spline_extrapolated_into(
+            spline.order,
+            spline.pieces,
+            spline.breaks.view(),
+            spline.coeffs.view(),
+            xi,
+            spline.periodic,
+            0,
+            self.extrapolation,
+            out,
+            axis,
+        )
     }
 }
@@ -0,0 +1,232 @@
+use ndarray::{prelude::*, RemoveAxis};
+
+use crate::{CsapsError::InvalidInputData, Extrapolation, Real, Result, search::search_piece};
+
+use super::NdSpline;
+
+
+impl<T> NdSpline<T>
+    where
+        T: Real<T>
+{
+    /// Evaluates the spline on the given data sites with the given extrapolation mode
+    ///
+    /// The periodic spline is never extrapolated, the data sites are wrapped into the period.
+    ///
+    /// # Errors
+    ///
+    /// - If `extrapolation` is `Extrapolation::Error` and any data site is out of the breaks range
+    ///
+    pub fn evaluate_extrapolated(&self, xi: ArrayView1<'_, T>, extrapolation: Extrapolation) -> Result<Array2<T>> {
+        Self::evaluate_spline_extrapolated(
+            self.order,
+            self.pieces,
+            self.breaks.view(),
+            self.coeffs.view(),
+            xi,
+            self.periodic,
+            0,
+            extrapolation,
+        )
+    }
+
+    /// Wraps the data sites into the period `[x1, xN)` given by the first and the last breaks
+    pub(crate) fn wrap_periodic(breaks: ArrayView1<'_, T>, xi: ArrayView1<'_, T>) -> Array1<T> {
+        xi.mapv(|x| Self::wrap_site(breaks, x))
+    }
+
+    /// Wraps the data site into the period `[x1, xN)` given by the first and the last breaks
+    pub(crate) fn wrap_site(breaks: ArrayView1<'_, T>, x: T) -> T {
+        let x_first = breaks[0];
+        let period = breaks[breaks.len() - 1] - x_first;
+
+        let t = (x - x_first) % period;
+        if t < T::zero() { x_first + t + period } else { x_first + t }
+    }
+
+    /// Implements evaluating the spline or its derivative of order `nu` with the given extrapolation mode
+    ///
+    /// The derivatives of the extrapolated spline are computed for the extrapolated spline, so for
+    /// `Linear` mode the 1st derivative is equal to the slope at the boundary and the higher derivatives
+    /// are zero, and for `Constant` mode all derivatives are zero out of the breaks range.
+    /// The data sites are wrapped into the period if `periodic` is true.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn evaluate_spline_extrapolated(
+        order: usize,
+        pieces: usize,
+        breaks: ArrayView1<'_, T>,
+        coeffs: ArrayView2<'_, T>,
+        xi: ArrayView1<'_, T>,
+        periodic: bool,
+        nu: usize,
+        extrapolation: Extrapolation,
+    ) -> Result<Array2<T>> {
+        let mut values = Array2::<T>::zeros((coeffs.nrows(), xi.len()));
+
+        Self::evaluate_spline_extrapolated_into(
+            order, pieces, breaks, coeffs, xi, periodic, nu, extrapolation, values.view_mut(), Axis(1))?;
+
+        Ok(values)
+    }
+
+    /// Implements evaluating the spline or its derivative of order `nu` into the values array
+    ///
+    /// The values are written as in `evaluate_spline_into`. The periodic spline is not extrapolated,
+    /// the data sites are wrapped into the period. Nothing is allocated for evaluating the spline values
+    /// (the derivative coefficients are computed for `nu > 0`).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn evaluate_spline_extrapolated_into<D>(
+        order: usize,
+        pieces: usize,
+        breaks: ArrayView1<'_, T>,
+        coeffs: ArrayView2<'_, T>,
+        xi: ArrayView1<'_, T>,
+        periodic: bool,
+        nu: usize,
+        extrapolation: Extrapolation,
+        mut values: ArrayViewMut<'_, T, D>,
+        axis: Axis,
+    ) -> Result<()>
+        where
+            D: Dimension + RemoveAxis
+    {
+        let extrapolation = if periodic { Extrapolation::Polynomial } else { extrapolation };
+
+        let x_first = breaks[0];
+        let x_last = breaks[breaks.len() - 1];
+
+        let is_out_of_range = |x: T| x < x_first || x > x_last;
+
+        if extrapolation == Extrapolation::Error {
+            if let Some(x) = xi.iter().find(|&&x| is_out_of_range(x)) {
+                return Err(
+                    InvalidInputData(
+                        format!("`xi` value {:?} is out of the breaks range [{:?}, {:?}]", x, x_first, x_last)
+                    )
+                )
+            }
+        }
+
+        if nu == 0 {
+            Self::evaluate_spline_into(order, pieces, breaks, coeffs, xi, periodic, values.view_mut(), axis);
+        } else {
+            let (d_order, d_coeffs) = Self::derivative_coeffs(order, pieces, coeffs, nu);
+            Self::evaluate_spline_into(d_order, pieces, breaks, d_coeffs.view(), xi, periodic, values.view_mut(), axis);
+        }
+
+        if extrapolation == Extrapolation::Polynomial || extrapolation == Extrapolation::Error {
+            return Ok(())
+        }
+
+        // The spline value and slope at the boundary which are needed for the extrapolation
+        let bound_value = |row: ArrayView1<'_, T>, j: usize, t: T| {
+            (1..order).fold(row[j], |acc, k| acc * t + row[k * pieces + j])
+        };
+
+        let bound_slope = |row: ArrayView1<'_, T>, j: usize, t: T| {
+            let power = |k: usize| T::from(order - 1 - k).unwrap();
+            (1..order.saturating_sub(1)).fold(row[j] * power(0), |acc, k| acc * t + row[k * pieces + j] * power(k))
+        };
+
+        for (mut site_values, &x) in values.axis_iter_mut(axis).zip(xi.iter()) {
+            if !is_out_of_range(x) {
+                continue
+            }
+
+            let (j, b) = if x < x_first { (0, x_first) } else { (pieces - 1, x_last) };
+            let t = b - breaks[j];
+
+            match extrapolation {
+                Extrapolation::Nan => site_values.fill(T::nan()),
+                Extrapolation::Constant => {
+                    if nu == 0 {
+                        for (v, row) in site_values.iter_mut().zip(coeffs.outer_iter()) {
+                            *v = bound_value(row, j, t);
+                        }
+                    } else {
+                        site_values.fill(T::zero());
+                    }
+                },
+                Extrapolation::Linear => {
+                    for (v, row) in site_values.iter_mut().zip(coeffs.outer_iter()) {
+                        *v = match nu {
+                            0 => bound_value(row, j, t) + bound_slope(row, j, t) * (x - b),
+                            1 => bound_slope(row, j, t),
+                            _ => T::zero(),
+                        };
+                    }
+                },
+                Extrapolation::Polynomial | Extrapolation::Error => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the weights of the polynomial coefficients of the piece for evaluating the spline
+    /// or its derivative of order `nu` at the single data site with the given extrapolation mode
+    ///
+    /// Writes `order` weights `w` to `weights` and returns the piece index `j`, so the value of the
+    /// spline is `sum(w[k] * coeffs[.., k * pieces + j])`. Returns None if the value is NaN
+    /// (`x` is NaN or `extrapolation` is `Extrapolation::Nan` and `x` is out of the breaks range).
+    /// `Extrapolation::Error` is handled as `Extrapolation::Polynomial`, so the caller should check
+    /// the breaks range before. The derivatives are computed for the extrapolated spline
+    /// as in `evaluate_spline_extrapolated`.
+    pub(crate) fn piece_weights(
+        pieces: usize,
+        breaks: ArrayView1<'_, T>,
+        x: T,
+        nu: usize,
+        extrapolation: Extrapolation,
+        weights: &mut [T],
+    ) -> Option<usize> {
+        if x.is_nan() {
+            return None
+        }
+
+        let order = weights.len();
+        let x_first = breaks[0];
+        let x_last = breaks[pieces];
+
+        // The derivative of order `nu` of the power `t^p` where `p = order - 1 - k`
+        let power = |t: T, k: usize, nu: usize| {
+            let p = order - 1 - k;
+
+            if p < nu {
+                T::zero()
+            } else {
+                let factor = ((p - nu + 1)..=p).fold(T::one(), |acc, v| acc * T::from(v).unwrap());
+                factor * t.powi((p - nu) as i32)
+            }
+        };
+
+        let is_out_of_range = x < x_first || x > x_last;
+
+        if !is_out_of_range || extrapolation == Extrapolation::Polynomial || extrapolation == Extrapolation::Error {
+            let j = search_piece(breaks, pieces, x);
+            let t = x - breaks[j];
+
+            weights.iter_mut().enumerate().for_each(|(k, w)| *w = power(t, k, nu));
+            return Some(j)
+        }
+
+        let (j, b) = if x < x_first { (0, x_first) } else { (pieces - 1, x_last) };
+        let t = b - breaks[j];
+
+        match (extrapolation, nu) {
+            (Extrapolation::Nan, _) => return None,
+            (Extrapolation::Constant, 0) | (Extrapolation::Linear, 1) => {
+                weights.iter_mut().enumerate().for_each(|(k, w)| *w = power(t, k, nu));
+            },
+            (Extrapolation::Linear, 0) => {
+                weights.iter_mut().enumerate().for_each(|(k, w)| *w = power(t, k, 0) + power(t, k, 1) * (x - b));
+            },
+            (Extrapolation::Constant, _) | (Extrapolation::Linear, _) => {
+                weights.iter_mut().for_each(|w| *w = T::zero());
+            },
+            (Extrapolation::Polynomial, _) | (Extrapolation::Error, _) => unreachable!(),
+        }
+
+        Some(j)
+    }
+}
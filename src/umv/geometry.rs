@@ -0,0 +1,343 @@
+use ndarray::prelude::*;
+
+use crate::{CsapsError::InvalidInputData, Real, Result, search::search_piece};
+
+use super::NdSpline;
+
+
+/// The nodes and the weights of 5-point Gauss-Legendre quadrature on `[-1, 1]`
+const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+];
+
+/// The maximum depth of the bisection of the adaptive quadrature
+const MAX_QUADRATURE_DEPTH: usize = 16;
+
+/// The maximum number of the iterations of inverting the arc length
+const MAX_INVERSE_ITERATIONS: usize = 60;
+
+
+/// The arc length of the curve represented by the multivariate spline
+///
+/// The arc length is the integral of the speed (the norm of the 1st derivative) of the spline.
+/// The lengths of the pieces are computed by adaptive Gauss-Legendre quadrature once, so the arc
+/// length at any parameter is the cumulative length of the previous pieces plus the integral
+/// over the part of the piece.
+pub(crate) struct ArcLength<T>
+    where
+        T: Real<T>
+{
+    /// The 1st derivative of the spline
+    velocity: NdSpline<T>,
+
+    /// The cumulative arc length at the breaks
+    lengths: Array1<T>,
+
+    /// The relative tolerance of the quadrature
+    tolerance: T,
+}
+
+
+impl<T> ArcLength<T>
+    where
+        T: Real<T>
+{
+    /// Computes the lengths of the pieces of the spline
+    pub(crate) fn new(spline: &NdSpline<T>) -> Self {
+        let pieces = spline.pieces();
+        let breaks = spline.breaks();
+
+        let mut arc_length = ArcLength {
+            velocity: spline.derivative(1),
+            lengths: Array1::zeros(pieces + 1),
+            tolerance: T::epsilon().sqrt() * T::from(1e-3).unwrap(),
+        };
+
+        for j in 0..pieces {
+            let length = arc_length.piece_length(j, T::zero(), breaks[j + 1] - breaks[j]);
+            arc_length.lengths[j + 1] = arc_length.lengths[j] + length;
+        }
+
+        arc_length
+    }
+
+    /// Returns the total length of the curve over the breaks range
+    pub(crate) fn length(&self) -> T {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Returns the arc length from the first break to the parameter `u`
+    ///
+    /// The arc length is negative for `u` before the first break, the first and the last pieces
+    /// are extrapolated out of the breaks range. The arc length of the periodic spline is counted
+    /// along the periods.
+    pub(crate) fn arc_length(&self, u: T) -> T {
+        let breaks = self.velocity.breaks();
+
+        if self.velocity.periodic() {
+            let period = breaks[breaks.len() - 1] - breaks[0];
+            let wrapped = NdSpline::wrap_site(breaks, u);
+            let periods = ((u - wrapped) / period).round();
+
+            return periods * self.length() + self.arc_length_in_range(wrapped)
+        }
+
+        self.arc_length_in_range(u)
+    }
+
+    /// Returns the arc length from the first break to the parameter `u` by the pieces lengths
+    fn arc_length_in_range(&self, u: T) -> T {
+        let breaks = self.velocity.breaks();
+        let j = search_piece(breaks, self.velocity.pieces(), u);
+
+        self.lengths[j] + self.piece_length(j, T::zero(), u - breaks[j])
+    }
+
+    /// Returns the parameter at the arc length `s` in the range `[0, length]`
+    ///
+    /// The equation `arc_length(u) = s` is solved in the piece containing the arc length
+    /// by Newton's method safeguarded by the bisection.
+    pub(crate) fn parameter(&self, s: T) -> T {
+        let breaks = self.velocity.breaks();
+        let j = search_piece(self.lengths.view(), self.velocity.pieces(), s);
+
+        let h = breaks[j + 1] - breaks[j];
+        let target = s - self.lengths[j];
+        let piece_length = self.lengths[j + 1] - self.lengths[j];
+
+        if piece_length <= T::zero() {
+            return breaks[j]
+        }
+
+        let tolerance = self.tolerance * self.length();
+
+        let (mut lo, mut hi) = (T::zero(), h);
+        let mut t = (h * target / piece_length).max(lo).min(hi);
+
+        for _ in 0..MAX_INVERSE_ITERATIONS {
+            let f = self.piece_length(j, T::zero(), t) - target;
+
+            if f.abs() <= tolerance {
+                break
+            }
+
+            if f > T::zero() { hi = t } else { lo = t }
+
+            let speed = self.speed(j, t);
+            let newton = t - f / speed;
+
+            t = if speed > T::zero() && newton > lo && newton < hi {
+                newton
+            } else {
+                (lo + hi) / T::from(2.0).unwrap()
+            };
+
+            if hi - lo <= T::epsilon() * h {
+                break
+            }
+        }
+
+        breaks[j] + t
+    }
+
+    /// Returns the speed of the curve at the offset `t` in the piece `j`
+    fn speed(&self, j: usize, t: T) -> T {
+        (0..self.velocity.ndim())
+            .map(|dim| self.velocity.eval_piece(dim, j, t).powi(2))
+            .fold(T::zero(), |acc, v| acc + v)
+            .sqrt()
+    }
+
+    /// Computes the length of the curve between the offsets `a` and `b` in the piece `j`
+    fn piece_length(&self, j: usize, a: T, b: T) -> T {
+        let whole = self.gauss_legendre(j, a, b);
+        let tolerance = self.tolerance * whole.abs();
+
+        self.adaptive_quadrature(j, a, b, whole, tolerance, MAX_QUADRATURE_DEPTH)
+    }
+
+    /// Integrates the speed by bisecting the interval until the halves agree with the whole
+    fn adaptive_quadrature(&self, j: usize, a: T, b: T, whole: T, tolerance: T, depth: usize) -> T {
+        let mid = (a + b) / T::from(2.0).unwrap();
+
+        let left = self.gauss_legendre(j, a, mid);
+        let right = self.gauss_legendre(j, mid, b);
+        let sum = left + right;
+        let error = (sum - whole).abs();
+
+        if depth == 0 || error <= tolerance || error.is_nan() {
+            return sum
+        }
+
+        let half_tolerance = tolerance / T::from(2.0).unwrap();
+
+        self.adaptive_quadrature(j, a, mid, left, half_tolerance, depth - 1)
+            + self.adaptive_quadrature(j, mid, b, right, half_tolerance, depth - 1)
+    }
+
+    /// Integrates the speed over the interval by 5-point Gauss-Legendre quadrature
+    fn gauss_legendre(&self, j: usize, a: T, b: T) -> T {
+        let half = (b - a) / T::from(2.0).unwrap();
+        let mid = a + half;
+
+        GAUSS_LEGENDRE_5.iter()
+            .fold(T::zero(), |acc, &(node, weight)| {
+                acc + T::from(weight).unwrap() * self.speed(j, mid + half * T::from(node).unwrap())
+            }) * half
+    }
+}
+
+
+impl<T> NdSpline<T>
+    where
+        T: Real<T>
+{
+    /// Computes the length of the curve represented by the spline over the breaks range
+    ///
+    /// The multivariate spline with `ndim` dimensions is the curve in `ndim`-dimensional space
+    /// parameterized by the data sites. The length is the integral of the speed (the norm of
+    /// the 1st derivative) computed by adaptive Gauss-Legendre quadrature over every piece.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::NdSpline;
+    ///
+    /// // The straight line from (0, 0) to (3, 4)
+    /// let spline = NdSpline::new(&array![0., 1.], array![[3., 0.], [4., 0.]]);
+    ///
+    /// let length: f64 = spline.length();
+    /// assert!((length - 5.).abs() < 1e-12);
+    /// ```
+    ///
+    pub fn length(&self) -> T {
+        ArcLength::new(self).length()
+    }
+
+    /// Computes the arc lengths of the curve from the first break to the given parameters
+    ///
+    /// The arc lengths are negative for the parameters before the first break, the spline
+    /// is extrapolated out of the breaks range as in `evaluate`. The arc lengths of the periodic
+    /// spline are counted along the periods. The lengths of the pieces are computed once
+    /// for all parameters.
+    pub fn arc_length(&self, u: ArrayView1<'_, T>) -> Array1<T> {
+        let arc_length = ArcLength::new(self);
+        u.mapv(|u| arc_length.arc_length(u))
+    }
+
+    /// Computes the unit tangent vectors of the curve at the given parameters
+    ///
+    /// Returns 2-d array with shape `[ndim, u.len()]` as `evaluate`. The tangent vector is NaN
+    /// where the speed of the curve is zero.
+    pub fn tangent(&self, u: ArrayView1<'_, T>) -> Array2<T> {
+        let mut tangent = self.evaluate_derivative(u, 1);
+
+        for mut column in tangent.axis_iter_mut(Axis(1)) {
+            let norm = column.dot(&column).sqrt();
+            column.mapv_inplace(|v| v / norm);
+        }
+
+        tangent
+    }
+
+    /// Computes the unit principal normal vectors of the curve at the given parameters
+    ///
+    /// The normal vector is the component of the 2nd derivative orthogonal to the tangent
+    /// normalized to the unit length, so it points to the center of the curvature. Returns 2-d
+    /// array with shape `[ndim, u.len()]` as `evaluate`. The normal vector is NaN where
+    /// the curvature or the speed of the curve is zero.
+    pub fn normal(&self, u: ArrayView1<'_, T>) -> Array2<T> {
+        let d1 = self.evaluate_derivative(u, 1);
+        let mut normal = self.evaluate_derivative(u, 2);
+
+        for (mut column, d1_column) in normal.axis_iter_mut(Axis(1)).zip(d1.axis_iter(Axis(1))) {
+            let projection = column.dot(&d1_column) / d1_column.dot(&d1_column);
+            column.zip_mut_with(&d1_column, |v, &d| *v -= projection * d);
+
+            let norm = column.dot(&column).sqrt();
+            column.mapv_inplace(|v| v / norm);
+        }
+
+        normal
+    }
+
+    /// Computes the curvature of the curve at the given parameters
+    ///
+    /// The curvature is `sqrt(|c'|^2 |c''|^2 - (c' . c'')^2) / |c'|^3` in any dimensional space,
+    /// so it is unsigned for 2-d curves. The curvature is NaN where the speed of the curve is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::NdSpline;
+    ///
+    /// // The parabola (u, u^2) has the curvature 2 at the vertex
+    /// let spline = NdSpline::new(&array![0., 1.], array![[0., 1., 0.], [1., 0., 0.]]);
+    ///
+    /// assert_eq!(spline.curvature(array![0.].view()), array![2.]);
+    /// ```
+    ///
+    pub fn curvature(&self, u: ArrayView1<'_, T>) -> Array1<T> {
+        let d1 = self.evaluate_derivative(u, 1);
+        let d2 = self.evaluate_derivative(u, 2);
+
+        d1.axis_iter(Axis(1))
+            .zip(d2.axis_iter(Axis(1)))
+            .map(|(a, b)| {
+                let aa = a.dot(&a);
+                let bb = b.dot(&b);
+                let ab = a.dot(&b);
+
+                (aa * bb - ab * ab).max(T::zero()).sqrt() / (aa * aa.sqrt())
+            })
+            .collect()
+    }
+
+    /// Computes the torsion of 3-d curve at the given parameters
+    ///
+    /// The torsion is `((c' x c'') . c''') / |c' x c''|^2`, it is NaN where the curvature
+    /// or the speed of the curve is zero.
+    ///
+    /// # Errors
+    ///
+    /// - If the spline dimensionality is not 3
+    ///
+    pub fn torsion(&self, u: ArrayView1<'_, T>) -> Result<Array1<T>> {
+        if self.ndim != 3 {
+            return Err(
+                InvalidInputData(
+                    format!("The torsion is defined for 3-d curves, the spline dimensionality is {}", self.ndim)
+                )
+            )
+        }
+
+        let d1 = self.evaluate_derivative(u, 1);
+        let d2 = self.evaluate_derivative(u, 2);
+        let d3 = self.evaluate_derivative(u, 3);
+
+        let torsion = d1.axis_iter(Axis(1))
+            .zip(d2.axis_iter(Axis(1)))
+            .zip(d3.axis_iter(Axis(1)))
+            .map(|((a, b), c)| {
+                let cross = [
+                    a[1] * b[2] - a[2] * b[1],
+                    a[2] * b[0] - a[0] * b[2],
+                    a[0] * b[1] - a[1] * b[0],
+                ];
+
+                let triple = cross[0] * c[0] + cross[1] * c[1] + cross[2] * c[2];
+                let cross_norm2 = cross.iter().fold(T::zero(), |acc, &v| acc + v * v);
+
+                triple / cross_norm2
+            })
+            .collect();
+
+        Ok(torsion)
+    }
+}
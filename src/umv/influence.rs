@@ -0,0 +1,267 @@
+use ndarray::prelude::*;
+
+use crate::{
+    CsapsError::InvalidInputData,
+    EndCondition,
+    MissingValues,
+    Real,
+    RealRef,
+    Result,
+};
+
+use super::{CubicSmoothingSpline, CubicSmoother, NdSpline, robust::robust_data_weights};
+
+
+/// The number of the smoother matrix columns which are computed at once
+const COLUMNS_CHUNK_SIZE: usize = 64;
+
+
+/// The smoother (influence) matrix diagonal of the computed spline for the data sites mask
+pub(super) struct Smoother<T>
+    where
+        T: Real<T>
+{
+    /// The mask of the data sites which are used for computing the spline
+    pub(super) mask: Array1<bool>,
+
+    /// The diagonal of the smoother matrix
+    pub(super) diag: Array1<T>,
+}
+
+
+impl<T> Smoother<T>
+    where
+        T: Real<T>
+{
+    /// Returns the indices of the data sites in the mask
+    pub(super) fn sites(&self) -> impl Iterator<Item = usize> + '_ {
+        self.mask.iter()
+            .enumerate()
+            .filter(|(_, &m)| m)
+            .map(|(j, _)| j)
+    }
+}
+
+
+/// The smoother matrices and the residuals of the computed spline for every data series
+pub(super) struct Influence<T>
+    where
+        T: Real<T>
+{
+    /// The data sites which have been used for computing the spline
+    pub(super) breaks: Array1<T>,
+
+    /// The data weights (including the robustness weights) which have been used for computing the spline
+    pub(super) weights: Array1<T>,
+
+    /// The residuals with shape `[m, n]`, the residuals of the missing values are NaN
+    pub(super) residuals: Array2<T>,
+
+    /// The smoother matrices for the distinct data sites masks
+    smoothers: Vec<Smoother<T>>,
+
+    /// The indices of the smoother matrices for the data series
+    series: Vec<usize>,
+}
+
+
+/// The statistics of the fit for the data series
+pub(super) struct SeriesStatistics<T> {
+    /// The number of the data values
+    pub(super) count: T,
+
+    /// The effective degrees of freedom (the trace of the smoother matrix)
+    pub(super) dof: T,
+
+    /// The weighted residual sum of squares
+    pub(super) wrss: T,
+}
+
+
+impl<T> Influence<T>
+    where
+        T: Real<T>
+{
+    /// Returns the distinct smoother matrices
+    pub(super) fn smoothers(&self) -> &[Smoother<T>] {
+        &self.smoothers
+    }
+
+    /// Returns the index of the smoother matrix for the data series
+    pub(super) fn smoother_index(&self, series: usize) -> usize {
+        self.series[series]
+    }
+
+    /// Returns the smoother matrix for the data series
+    pub(super) fn smoother(&self, series: usize) -> &Smoother<T> {
+        &self.smoothers[self.smoother_index(series)]
+    }
+
+    /// Computes the statistics of the fit for the data series
+    pub(super) fn statistics(&self, series: usize) -> SeriesStatistics<T> {
+        let smoother = self.smoother(series);
+        let residuals = self.residuals.row(series);
+
+        SeriesStatistics {
+            count: T::from(smoother.sites().count()).unwrap(),
+            dof: smoother.sites().fold(T::zero(), |acc, j| acc + smoother.diag[j]),
+            wrss: smoother.sites().fold(T::zero(), |acc, j| acc + self.weights[j] * residuals[j] * residuals[j]),
+        }
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension
+{
+    /// Computes the smoother matrices diagonals and the residuals of the computed spline
+    ///
+    /// The smoother matrix diagonal is computed for the data sites without missing values
+    /// for every data series with the missing values per series.
+    pub(super) fn influence(&self) -> Result<Influence<T>> {
+        let spline = self.spline.as_ref().ok_or_else(|| {
+            InvalidInputData(
+                "The spline has not been computed, use `make` method before".to_string()
+            )
+        })?;
+
+        let (x, y, weights) = self.prepared_data()?;
+
+        let weights = match self.robustness_weights {
+            Some(ref robustness_weights) => robust_data_weights(weights.view(), robustness_weights.view()),
+            None => weights,
+        };
+
+        let residuals = &y - &spline.evaluate(x.view());
+
+        let valid = y.mapv(|v| !v.is_nan());
+        let complete = valid.map_axis(Axis(0), |column| column.iter().all(|&v| v));
+        let per_series = self.missing_values == MissingValues::PerSeries;
+
+        let mut smoothers: Vec<Smoother<T>> = Vec::new();
+        let mut series = Vec::with_capacity(y.nrows());
+
+        for row in valid.outer_iter() {
+            let mask = if per_series { row.to_owned() } else { complete.clone() };
+
+            let index = match smoothers.iter().position(|s| s.mask == mask) {
+                Some(index) => index,
+                None => {
+                    smoothers.push(self.smoother(x.view(), weights.view(), mask)?);
+                    smoothers.len() - 1
+                },
+            };
+
+            series.push(index);
+        }
+
+        Ok(Influence { breaks: x.into_owned(), weights, residuals, smoothers, series })
+    }
+
+    /// Computes the smoother matrix diagonal for the data sites mask
+    ///
+    /// The diagonal of the natural and the periodic splines without missing values is computed
+    /// from the factorized system. Otherwise the spline is computed by the constrained system,
+    /// so the diagonal is taken from the smoother matrix columns.
+    fn smoother(&self, x: ArrayView1<'_, T>, weights: ArrayView1<'_, T>, mask: Array1<bool>) -> Result<Smoother<T>> {
+        if let Some(smoother) = self.factorized_smoother(x.view(), weights.view(), mask.view())? {
+            let diag = smoother.influence_diag()?;
+            return Ok(Smoother { mask, diag })
+        }
+
+        let mut diag = Array1::<T>::zeros(x.raw_dim());
+
+        self.for_each_smoother_columns(x, weights, mask.view(), |start, columns| {
+            let sites = s![start..start + columns.ndim()];
+            diag.slice_mut(sites).assign(&columns.evaluate(x.slice(sites)).diag());
+            Ok(())
+        })?;
+
+        Ok(Smoother { mask, diag })
+    }
+
+    /// Makes the smoother of the natural or the periodic spline for the data sites mask without missing values
+    ///
+    /// Returns None if the spline is computed by the constrained system.
+    fn factorized_smoother<'s>(
+        &self,
+        x: ArrayView1<'s, T>,
+        weights: ArrayView1<'s, T>,
+        mask: ArrayView1<'_, bool>,
+    ) -> Result<Option<CubicSmoother<'s, T>>> {
+        let (start, end) = self.end_conditions;
+        let is_natural = start == EndCondition::Natural && end == EndCondition::Natural;
+
+        if !(self.periodic || is_natural) || !mask.iter().all(|&m| m) {
+            return Ok(None)
+        }
+
+        let smoother = CubicSmoother::new(x)
+            .with_weights(weights)
+            .with_optional_smooth(self.smooth())
+            .with_normalized_smooth(self.normalized_smooth)
+            .with_periodic(self.periodic)
+            .make()?;
+
+        Ok(Some(smoother))
+    }
+
+    /// Computes the smoother matrix columns for the data sites mask by the chunks
+    ///
+    /// The columns of the smoother matrix are computed as the spline for the unit data vectors
+    /// with the same parameters, the data sites which are not in the mask are missing, so
+    /// the smoother matrix can be evaluated on any data sites. `f` is called with the index
+    /// of the first column of the chunk and the spline with the data series for the columns.
+    ///
+    /// The natural and the periodic systems are factorized once for all chunks, the constrained
+    /// system is computed for every chunk.
+    pub(super) fn for_each_smoother_columns<F>(
+        &self,
+        x: ArrayView1<'_, T>,
+        weights: ArrayView1<'_, T>,
+        mask: ArrayView1<'_, bool>,
+        mut f: F,
+    ) -> Result<()>
+        where
+            F: FnMut(usize, NdSpline<T>) -> Result<()>
+    {
+        let n = x.len();
+        let (start, end) = self.end_conditions;
+
+        let smoother = self.factorized_smoother(x.view(), weights.view(), mask)?;
+
+        for first in (0..n).step_by(COLUMNS_CHUNK_SIZE) {
+            let count = COLUMNS_CHUNK_SIZE.min(n - first);
+
+            let unit = Array2::<T>::from_shape_fn((count, n), |(i, j)| {
+                match (mask[j], first + i == j) {
+                    (false, _) => T::nan(),
+                    (true, true) => T::one(),
+                    (true, false) => T::zero(),
+                }
+            });
+
+            let columns = match smoother {
+                Some(ref smoother) => smoother.fit(&unit)?,
+                // The views are reborrowed for the lifetime of the unit data
+                None => CubicSmoothingSpline::new(x.view(), &unit)
+                    .with_weights(weights.view())
+                    .with_optional_smooth(self.smooth())
+                    .with_normalized_smooth(self.normalized_smooth)
+                    .with_end_conditions(start, end)
+                    .with_missing_values(self.missing_values)
+                    .make()?
+                    .into_spline()
+                    .unwrap(),
+            };
+
+            f(first, columns)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use ndarray::{prelude::*, RemoveAxis};
+
+use crate::{CsapsError::InvalidInputData, Real, RealRef, Result};
+
+use super::{CubicSmoothingSpline, NdSpline};
+
+
+impl<T> NdSpline<T>
+    where
+        T: Real<T>
+{
+    /// Returns the antiderivative (indefinite integral) of the spline as a new PP-form spline
+    ///
+    /// The order of the returned spline is increased by one, so for cubic spline
+    /// the antiderivative is the spline of order 5. The integration constants are chosen
+    /// so that the antiderivative is continuous and equal to zero at the first break.
+    ///
+    /// The antiderivative of the periodic spline is not periodic in general,
+    /// so the returned spline is not periodic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![0., 1., 2., 3.];
+    /// let y = array![2., 2., 2., 2.];
+    ///
+    /// let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+    /// let is = s.spline().unwrap().antiderivative();
+    ///
+    /// assert_eq!(is.order(), 5);
+    /// assert_eq!(is.evaluate(x.view()), array![[0., 2., 4., 6.]]);
+    /// ```
+    ///
+    pub fn antiderivative(&self) -> NdSpline<T> {
+        let order = self.order;
+        let pieces = self.pieces;
+        let i_order = order + 1;
+
+        let mut coeffs = Array2::<T>::zeros((self.ndim, i_order * pieces));
+
+        for k in 0..order {
+            let power = T::from(order - k).unwrap();
+
+            coeffs
+                .slice_mut(s![.., k * pieces..(k + 1) * pieces])
+                .assign(&(&self.coeffs.slice(s![.., k * pieces..(k + 1) * pieces]) / power));
+        }
+
+        // The integration constants provide the continuity of the antiderivative
+        let const_offset = order * pieces;
+
+        for dim in 0..self.ndim {
+            let mut constant = T::zero();
+
+            for piece in 0..pieces {
+                coeffs[[dim, const_offset + piece]] = constant;
+
+                let h = self.breaks[piece + 1] - self.breaks[piece];
+
+                constant = (0..i_order)
+                    .fold(T::zero(), |acc, k| acc * h + coeffs[[dim, k * pieces + piece]]);
+            }
+        }
+
+        NdSpline { smooth: self.smooth, ..NdSpline::new(&self.breaks, coeffs) }
+    }
+
+    /// Computes the definite integral of the spline over the interval `[a, b]`
+    ///
+    /// Returns the 1-d array of the integrals for every spline dimension.
+    /// If the interval is out of the breaks range, the integral is computed for
+    /// the extrapolated spline pieces. For the periodic spline the integral is computed
+    /// over the periodic continuation of the spline.
+    pub fn integrate(&self, a: T, b: T) -> Array1<T> {
+        let antiderivative = self.antiderivative();
+
+        if !self.periodic {
+            let bounds = array![a, b];
+            let values = antiderivative.evaluate(bounds.view());
+
+            return &values.column(1) - &values.column(0)
+        }
+
+        // The integral over the whole periods is the integral over the breaks range
+        // multiplied by the number of the periods between the bounds
+        let x_first = self.breaks[0];
+        let x_last = self.breaks[self.breaks.len() - 1];
+        let period = x_last - x_first;
+
+        let bounds = Self::wrap_periodic(self.breaks.view(), array![a, b].view());
+        let values = antiderivative.evaluate(bounds.view());
+        let period_values = antiderivative.evaluate(array![x_last].view());
+
+        let periods = ((b - x_first) / period).floor() - ((a - x_first) / period).floor();
+
+        &values.column(1) - &values.column(0) + &period_values.column(0) * periods
+    }
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension
+{
+    /// Computes the definite integral of the computed spline over the interval `[a, b]`
+    ///
+    /// The integral is computed along the `axis` of Y-data, so the output array shape is
+    /// the shape of Y-data without `axis`. For example, for `y` with shape `[3, 10]` and `axis = 1`
+    /// the output array has shape `[3]`.
+    ///
+    /// # Errors
+    ///
+    /// - If the integration bounds are not finite
+    /// - If the spline yet has not been computed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use csaps::CubicSmoothingSpline;
+    ///
+    /// let x = array![0., 1., 2., 3.];
+    /// let y = array![[1., 2.], [2., 4.], [3., 6.], [4., 8.]];
+    ///
+    /// let area = CubicSmoothingSpline::new(&x, &y)
+    ///     .with_axis(Axis(0))
+    ///     .make().unwrap()
+    ///     .integrate(0., 3.).unwrap();
+    ///
+    /// assert_eq!(area, array![7.5, 15.]);
+    /// ```
+    ///
+    pub fn integrate(&self, a: T, b: T) -> Result<Array<T, D::Smaller>>
+        where
+            D: RemoveAxis
+    {
+        if !a.is_finite() || !b.is_finite() {
+            return Err(
+                InvalidInputData(
+                    format!("The integration bounds must be finite, given [{:?}, {:?}]", a, b)
+                )
+            )
+        }
+
+        let spline = self.spline.as_ref().ok_or_else(|| {
+            InvalidInputData(
+                "The spline has not been computed, use `make` method before".to_string()
+            )
+        })?;
+
+        let axis = self.axis.unwrap();
+        let shape = self.y.raw_dim().remove_axis(axis);
+        let values = spline.integrate(a, b);
+
+        Ok(values.into_shape(shape).unwrap())
+    }
+}
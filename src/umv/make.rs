@@ -1,74 +1,72 @@
-
-use std::ops::{Add, Mul};
-
-use ndarray::{prelude::*, concatenate, s};
-use sprs::MulAcc;
+use ndarray::{prelude::*, concatenate, stack, s};
+use sprs::CsMat;
+use sprs_ldl::LdlNumeric;
 
 
 use crate::{
     Real,
     Result,
-    ndarrayext::{diff, to_2d},
+    CsapsError::InvalidInputData,
+    EndCondition,
+    banded::{BandedLdl, SymmetricBandedMatrix},
+    ndarrayext::{diff, roll, to_2d},
+    parallel::for_each_chunk,
     sprsext, RealRef
 };
 
-use super::{NdSpline, CubicSmoothingSpline};
+use super::{NdSpline, CubicSmoothingSpline, constrained::ConstrainedSystem, sort::sort_data_sites};
 
 
-impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
-    where
-    T: Real<T>,
-    for<'r> &'r T: RealRef<&'r T, T>,
+/// The data sites, 2-d `y` and the data weights for computing the spline
+pub(super) type PreparedData<'a, T> = (CowArray<'a, T, Ix1>, CowArray<'a, T, Ix2>, Array1<T>);
 
-    // T: MulAcc,
-    // for<'r> &'r T: Add<&'r T, Output = T>,
-    // for<'r> &'r T: Mul<&'r T, Output = T>,
 
-    D: Dimension
+/// The matrices of the linear system for computing cubic smoothing spline
+///
+/// The system does not depend on the data values and the smoothing parameter,
+/// so it can be used for solving the system for different smoothing parameter values.
+pub(super) struct SmoothingSystem<T>
+    where
+        T: Real<T>
 {
-    pub(super) fn make_spline(&mut self) -> Result<()> {
-        let one = T::one();
-        let two = T::from::<f64>(2.0).unwrap();
-        let three = T::from::<f64>(3.0).unwrap();
-        let six = T::from::<f64>(6.0).unwrap();
+    /// The differences of the data sites
+    pub(super) dx: Array1<T>,
 
-        let breaks = self.x;
+    /// The data weights
+    pub(super) weights: Array1<T>,
 
-        let weights_default = Array1::ones(breaks.raw_dim());
-        let weights = self.weights
-            .map(|v| v.reborrow()) // without it we will get an error: "[E0597] `weights_default` does not live long enough"
-            .unwrap_or_else(|| weights_default.view());
+    /// `Q' * W^-1 * Q` matrix where `Q'` is the matrix of the second divided differences
+    pub(super) qtwq: CsMat<T>,
 
-        let dx = diff(breaks.view(), None);
-
-        let axis = self.axis.unwrap_or_else(|| Axis(self.y.ndim() - 1));
-        self.axis = Some(axis);
+    /// The tridiagonal `R` matrix
+    pub(super) r: CsMat<T>,
 
-        let y = to_2d(self.y.view(), axis)?;
-        let dydx = diff(y.view(), Some(Axis(1))) / &dx;
-
-        let pcount = breaks.len();
+    /// The flag of the periodic system with the cyclic matrices
+    pub(super) periodic: bool,
+}
 
-        // The corner case for Nx2 data (2 data points)
-        if pcount == 2 {
-            drop(dx);
-            let yi = y.slice(s![.., 0 as i32]).insert_axis(Axis(1));
-            let coeffs = concatenate![Axis(1), dydx, yi];
 
-            self.smooth = Some(one);
-            self.spline = Some(NdSpline::new(breaks, coeffs));
+impl<T> SmoothingSystem<T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>
+{
+    /// Creates the system for the given data sites and weights
+    ///
+    /// The number of the data sites must be greater or equal to 3.
+    pub(super) fn new(breaks: ArrayView1<'_, T>, weights: ArrayView1<'_, T>) -> Self {
+        let two = T::from::<f64>(2.0).unwrap();
 
-            return Ok(())
-        }
+        let dx = diff(breaks.view(), None);
+        let pcount = breaks.len();
 
-        // General computing cubic smoothing spline for NxM data (3 and more data points)
         let ones = |n| Array1::<T>::ones((n, ));
 
         let qtwq = {
             let qt = {
                 let odx = ones(pcount - 1) / &dx;
-                let odx_head = odx.slice(s![..-1 as i32]).insert_axis(Axis(0)).into_owned();
-                let odx_tail = odx.slice(s![1 as i32..]).insert_axis(Axis(0)).into_owned();
+                let odx_head = odx.slice(s![..-1]).insert_axis(Axis(0)).into_owned();
+                let odx_tail = odx.slice(s![1..]).insert_axis(Axis(0)).into_owned();
                 drop(odx);
                 let odx_body = -(&odx_tail + &odx_head);
                 let diags_qt = concatenate![Axis(0), odx_head, odx_body, odx_tail];
@@ -77,100 +75,579 @@ impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
             };
 
 
-            let diags_sqrw = (ones(pcount) / weights.mapv(T::sqrt)).insert_axis(Axis(0));
-            let sqrw = sprsext::diags(diags_sqrw, &[0], (pcount, pcount));
-            let qtw = &qt * &sqrw;
-            drop(sqrw);
-            drop(qt);
-            let qtw_t = qtw.transpose_view();
-
-            &qtw * &qtw_t
+            Self::qtwq(qt, weights)
         };
 
         let r = {
-            let dx_head = dx.slice(s![..-1 as i32]).insert_axis(Axis(0)).into_owned();
-            let dx_tail = dx.slice(s![1 as i32..]).insert_axis(Axis(0)).into_owned();
+            let dx_head = dx.slice(s![..-1]).insert_axis(Axis(0)).into_owned();
+            let dx_tail = dx.slice(s![1..]).insert_axis(Axis(0)).into_owned();
             let dx_body = (&dx_tail + &dx_head) * two;
             let diags_r = concatenate![Axis(0), dx_tail, dx_body, dx_head];
 
             sprsext::diags(diags_r, &[-1, 0, 1], (pcount - 2, pcount - 2))
         };
 
-        let auto_smooth = || {
-            let trace = |m| { sprsext::diagonal(m, 0).sum() };
-            one / (one + trace(&r) / (six * trace(&qtwq)))
+        SmoothingSystem {
+            dx,
+            weights: weights.to_owned(),
+            qtwq,
+            r,
+            periodic: false,
+        }
+    }
+
+    /// Creates the periodic system for the given data sites and weights
+    ///
+    /// `breaks` are the data sites of one period including the end of the period, which is
+    /// the same site as the first one, so `weights` do not contain the last data site weight.
+    /// The `Q'` and `R` matrices are cyclic. The number of the data sites must be greater or equal to 3.
+    pub(super) fn new_periodic(breaks: ArrayView1<'_, T>, weights: ArrayView1<'_, T>) -> Self {
+        let two = T::from::<f64>(2.0).unwrap();
+
+        let dx = diff(breaks.view(), None);
+        let size = dx.len();
+
+        let qtwq = {
+            let qt = {
+                let odx = Array1::<T>::ones((size, )) / &dx;
+                let odx_prev = roll(&odx, 1, Axis(0));
+                let odx_body = -(&odx_prev + &odx);
+                let diags_qt = stack![Axis(0), odx_prev, odx_body, odx];
+
+                sprsext::cyclic_diags(diags_qt, &[-1, 0, 1], size)
+            };
+
+            Self::qtwq(qt, weights)
+        };
+
+        let r = {
+            let dx_prev = roll(&dx, 1, Axis(0));
+            let dx_body = (&dx_prev + &dx) * two;
+            let diags_r = stack![Axis(0), dx_prev, dx_body, dx];
+
+            sprsext::cyclic_diags(diags_r, &[-1, 0, 1], size)
         };
 
-        let smooth = self.smooth.unwrap_or_else(auto_smooth);
-        let s1 = six * (one - smooth);
+        SmoothingSystem {
+            dx,
+            weights: weights.to_owned(),
+            qtwq,
+            r,
+            periodic: true,
+        }
+    }
+
+    /// Computes `Q' * W^-1 * Q` matrix for the given `Q'` matrix and the weights
+    fn qtwq(qt: CsMat<T>, weights: ArrayView1<'_, T>) -> CsMat<T> {
+        let pcount = weights.len();
 
-        // Solve linear system Ax = b for the 2nd derivatives
-        let usol = {
-            let a = {
+        let diags_sqrw = (Array1::<T>::ones((pcount, )) / weights.mapv(T::sqrt)).insert_axis(Axis(0));
+        let sqrw = sprsext::diags(diags_sqrw, &[0], (pcount, pcount));
+        let qtw = &qt * &sqrw;
+        drop(sqrw);
+        drop(qt);
+        let qtw_t = qtw.transpose_view();
 
+        &qtw * &qtw_t
+    }
 
-                // cannot multiply `&CsMatBase<T, usize, Vec<usize>, Vec<usize>, Vec<T>>` by `T`
-                // the trait `Mul<T>` is not implemented for `&CsMatBase<T, usize, Vec<usize>, Vec<usize>, Vec<T>>`
-                
-                let a1 = qtwq.map(|el| s1 * *el );
-                let a2 = r.map(|el| *el * smooth);
+    /// Returns the number of the data sites
+    pub(super) fn size(&self) -> usize {
+        self.weights.len()
+    }
 
+    /// Returns the ratio of the matrices traces `tr(R) / (6 * tr(Q' * W^-1 * Q))`
+    ///
+    /// The ratio scales as the cube of the data sites step, so it is used for normalizing
+    /// the smoothing parameter.
+    pub(super) fn traces_ratio(&self) -> T {
+        let six = T::from::<f64>(6.0).unwrap();
 
-                // let a1 = &qtwq * s1;
-                // let a2 = &r * smooth;
-                drop(qtwq);
-                drop(r);
+        let trace = |m| { sprsext::diagonal(m, 0).sum() };
+        trace(&self.r) / (six * trace(&self.qtwq))
+    }
 
+    /// Computes the smoothing parameter automatically from the ratio of the matrices traces
+    pub(super) fn auto_smooth(&self) -> T {
+        let one = T::one();
+        one / (one + self.traces_ratio())
+    }
 
-                &a1 + &a2 // was &a1 + &a2
-            };
+    /// Maps the normalized smoothing parameter to the smoothing parameter
+    pub(super) fn smooth_from_normalized(&self, normalized: T) -> T {
+        let one = T::one();
+        normalized / (normalized + (one - normalized) * self.traces_ratio())
+    }
+
+    /// Maps the smoothing parameter to the normalized smoothing parameter
+    pub(super) fn smooth_to_normalized(&self, smooth: T) -> T {
+        let one = T::one();
+        let pr = smooth * self.traces_ratio();
+        pr / (one - smooth + pr)
+    }
 
-            let b = diff(&dydx, Some(Axis(1))).t().to_owned();
-            drop(dydx);
+    /// Returns the matrix `A = 6 * (1 - p) * Q' * W^-1 * Q + p * R` for the given smoothing parameter
+    ///
+    /// The periodic matrix is singular for zero smoothing parameter because the constant
+    /// is in the null space of the cyclic `Q`, but the system is consistent and the smoothed
+    /// values do not depend on the constant, so the small smoothing parameter is used instead.
+    pub(super) fn matrix(&self, smooth: T) -> CsMat<T> {
+        let six = T::from::<f64>(6.0).unwrap();
 
-            sprsext::solve(&a, &b)
+        let smooth = if self.periodic {
+            smooth.max(self.smooth_from_normalized(T::epsilon()))
+        } else {
+            smooth
         };
 
-        // Compute and concatenatespline coefficients
-        let coeffs = {
-            let vpad = |arr: &Array2<T>| -> Array2<T> {
-                let pad = Array2::<T>::zeros((1, arr.shape()[1]));
-                concatenate(Axis(0), &[pad.view(), arr.view(), pad.view()]).unwrap()
-            };
+        let s1 = six * (T::one() - smooth);
 
-            let dx = dx.insert_axis(Axis(1));
+        // cannot multiply `&CsMatBase<T, usize, Vec<usize>, Vec<usize>, Vec<T>>` by `T`
+        // the trait `Mul<T>` is not implemented for `&CsMatBase<T, usize, Vec<usize>, Vec<usize>, Vec<T>>`
+        let a1 = self.qtwq.map(|el| s1 * *el);
+        let a2 = self.r.map(|el| *el * smooth);
 
-            let yi = {
-                let d1 = diff(&vpad(&usol), Some(Axis(0))) / &dx;
-                let d2 = diff(&vpad(&d1), Some(Axis(0)));
+        &a1 + &a2
+    }
 
-                let diags_w = (ones(pcount) / weights).insert_axis(Axis(0));
-                let w = sprsext::diags(diags_w, &[0], (pcount, pcount));
-                let wd2 = &w * &d2;
+    /// Returns the right-hand side of the system (the second divided differences) for the given 2-d `y`
+    pub(super) fn rhs(&self, y: ArrayView2<'_, T>) -> Array2<T> {
+        if self.periodic {
+            let dydx = (roll(y, -1, Axis(1)) - y) / &self.dx;
+            return (&dydx - &roll(&dydx, 1, Axis(1))).t().to_owned()
+        }
 
-                drop(d1);
-                drop(d2);
+        let dydx = diff(y, Some(Axis(1))) / &self.dx;
+        diff(&dydx, Some(Axis(1))).t().to_owned()
+    }
 
-                &y.t() - &wd2
-            };
+    /// Returns the pentadiagonal matrix `A` of the non-periodic system for the given smoothing parameter
+    ///
+    /// The matrix is built from the columns of `Q'` directly, so building it is linear
+    /// in the number of the data sites.
+    fn banded_matrix(&self, smooth: T) -> SymmetricBandedMatrix<T> {
+        let two = T::from::<f64>(2.0).unwrap();
+        let six = T::from::<f64>(6.0).unwrap();
+        let s1 = six * (T::one() - smooth);
+
+        let dx = &self.dx;
+        let qcount = self.size() - 2;
+
+        let mut a = SymmetricBandedMatrix::zeros(qcount, 2);
+
+        for (i, &w) in self.weights.iter().enumerate() {
+            let column = self.qt_column(i);
+
+            for &(j, vj) in &column {
+                for &(k, vk) in column.iter().filter(|&&(k, _)| k <= j) {
+                    a.add(j, k, s1 * vj * vk / w);
+                }
+            }
+        }
+
+        for j in 0..qcount {
+            a.add(j, j, smooth * two * (dx[j] + dx[j + 1]));
+
+            if j + 1 < qcount {
+                a.add(j + 1, j, smooth * dx[j + 1]);
+            }
+        }
+
+        a
+    }
+
+    /// Factorizes the matrix `A` for the given smoothing parameter
+    ///
+    /// The pentadiagonal matrix of the non-periodic system is factorized by banded LDL',
+    /// the cyclic matrix of the periodic system is factorized by sparse LDL'.
+    pub(super) fn factorize(&self, smooth: T) -> Result<Factorization<T>> {
+        let singular = || InvalidInputData(
+            "The spline system is singular for the given data sites and weights".to_string()
+        );
+
+        if self.periodic {
+            let a = self.matrix(smooth);
+            let ldl = LdlNumeric::new(a.view()).map_err(|_| singular())?;
+
+            // The sparse factorization fails only on the exactly zero pivots
+            if !ldl.d().iter().all(|d| d.is_finite()) {
+                return Err(singular())
+            }
+
+            Ok(Factorization::Sparse(Box::new(ldl)))
+        } else {
+            self.banded_matrix(smooth)
+                .factorize()
+                .map(Factorization::Banded)
+                .ok_or_else(singular)
+        }
+    }
+
+    /// Solves the linear system `Ax = b` for the 2nd derivatives
+    pub(super) fn solve(&self, smooth: T, b: &Array2<T>) -> Result<Array2<T>> {
+        Ok(self.factorize(smooth)?.solve(b.view()))
+    }
+
+    /// Pads the array with zero rows at the top and at the bottom
+    fn vpad(arr: &Array2<T>) -> Array2<T> {
+        let pad = Array2::<T>::zeros((1, arr.shape()[1]));
+        concatenate(Axis(0), &[pad.view(), arr.view(), pad.view()]).unwrap()
+    }
+
+    /// Computes the smoothed data values for the given 2-d `y` and the solution of the system
+    ///
+    /// Returns the array with shape `[n, m]` where `n` is the number of the data sites.
+    pub(super) fn smoothed_values(&self, y: ArrayView2<'_, T>, usol: &Array2<T>, smooth: T) -> Array2<T> {
+        let six = T::from::<f64>(6.0).unwrap();
+        let s1 = six * (T::one() - smooth);
+
+        let pcount = self.size();
+        let dx = self.dx.view().insert_axis(Axis(1));
+
+        let d2 = if self.periodic {
+            let d1 = (roll(usol, -1, Axis(0)) - usol) / dx;
+            &d1 - &roll(&d1, 1, Axis(0))
+        } else {
+            let d1 = diff(&Self::vpad(usol), Some(Axis(0))) / dx;
+            diff(&Self::vpad(&d1), Some(Axis(0)))
+        };
+
+        let diags_w = (Array1::<T>::ones((pcount, )) / &self.weights).insert_axis(Axis(0));
+        let w = sprsext::diags(diags_w, &[0], (pcount, pcount));
+        let wd2 = &w * &d2;
+        drop(d2);
+
+        &y.t() - &(wd2 * s1)
+    }
+
+    /// Computes and concatenates the spline coefficients from the smoothed values and the solution
+    ///
+    /// The periodic values are wrapped, so the last piece ends with the values at the first data site.
+    pub(super) fn coeffs(&self, yi: &Array2<T>, usol: &Array2<T>, smooth: T) -> Array2<T> {
+        if self.periodic {
+            let wrap = |a: &Array2<T>| concatenate![Axis(0), a.view(), a.slice(s![..1, ..])];
+            return piecewise_coeffs(self.dx.view(), &wrap(yi), &wrap(&(usol * smooth)))
+        }
+
+        let c3 = Self::vpad(&(usol * smooth));
+        piecewise_coeffs(self.dx.view(), yi, &c3)
+    }
+
+    /// Returns the row indices and the values of the non-zero elements of `i`-th column of `Q'`
+    ///
+    /// The rows of the cyclic `Q'` may be repeated for 2 data sites in the period.
+    pub(super) fn qt_column(&self, i: usize) -> Vec<(usize, T)> {
+        let dx = &self.dx;
+
+        if self.periodic {
+            let size = self.size();
+            let prev = (i + size - 1) % size;
+
+            return vec![
+                (prev, T::one() / dx[prev]),
+                (i, -(T::one() / dx[prev] + T::one() / dx[i])),
+                ((i + 1) % size, T::one() / dx[i]),
+            ]
+        }
 
-            let c3 = vpad(&(usol * smooth));
-            let c3_head = c3.slice(s![..-1 as i32, ..]);
-            let c3_tail = c3.slice(s![1 as i32.., ..]);
+        // The non-zero elements of i-th column of Q' are placed in rows i-2, i-1 and i
+        let qcount = self.size() - 2;
 
-            let p1 = diff(&c3, Some(Axis(0))) / &dx;
-            let p2 = &c3_head * three;
-            let p3 = diff(&yi, Some(Axis(0))) / &dx - (&c3_head * two + c3_tail) * dx;
-            let p4 = yi.slice(s![..-1 as i32, ..]); // was yi.view()
+        (i.saturating_sub(2)..(i + 1).min(qcount))
+            .map(|j| {
+                let value = match i - j {
+                    0 => T::one() / dx[i],
+                    1 => -(T::one() / dx[i - 1] + T::one() / dx[i]),
+                    _ => T::one() / dx[i - 1],
+                };
 
-            drop(c3);
+                (j, value)
+            })
+            .collect()
+    }
+}
+
+
+/// The factorization of the system matrix `A`
+pub(super) enum Factorization<T>
+    where
+        T: Real<T>
+{
+    Banded(BandedLdl<T>),
+    Sparse(Box<LdlNumeric<T, usize>>),
+}
+
+
+impl<T> Factorization<T>
+    where
+        T: Real<T>
+{
+    /// Solves the linear system `Ax = b` for all columns of `b`
+    ///
+    /// The columns are solved in parallel with `rayon` feature.
+    pub(super) fn solve(&self, b: ArrayView2<'_, T>) -> Array2<T> {
+        match self {
+            Factorization::Banded(ldl) => ldl.solve(b),
+            Factorization::Sparse(ldl) => {
+                let mut x = b.to_owned();
+
+                for_each_chunk(x.view_mut(), Axis(1), 1, |_, mut chunk| {
+                    for mut column in chunk.axis_iter_mut(Axis(1)) {
+                        let b_vec = column.to_vec();
+                        column.assign(&Array1::from(ldl.solve(&b_vec)));
+                    }
+                });
+
+                x
+            },
+        }
+    }
+}
+
+
+/// Computes and concatenates the spline coefficients from the values and the second derivatives
+///
+/// `yi` is the array of the spline values at the data sites with shape `[n, m]` and `c3` is the array
+/// of the second derivatives at the data sites divided by 6 with the same shape.
+pub(super) fn piecewise_coeffs<T>(dx: ArrayView1<'_, T>, yi: &Array2<T>, c3: &Array2<T>) -> Array2<T>
+    where
+        T: Real<T>
+{
+    let two = T::from::<f64>(2.0).unwrap();
+    let three = T::from::<f64>(3.0).unwrap();
+
+    let dx = dx.insert_axis(Axis(1));
+
+    let c3_head = c3.slice(s![..-1, ..]);
+    let c3_tail = c3.slice(s![1.., ..]);
+
+    let p1 = diff(c3, Some(Axis(0))) / dx;
+    let p2 = &c3_head * three;
+    let p3 = diff(yi, Some(Axis(0))) / dx - (&c3_head * two + c3_tail) * dx;
+    // The constant coefficients are the values at the start of every piece, so the last value is not used
+    let p4 = yi.slice(s![..-1, ..]);
+
+    concatenate(Axis(0), &[p1.view(), p2.view(), p3.view(), p4]).unwrap().t().to_owned()
+}
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+    T: Real<T>,
+    for<'r> &'r T: RealRef<&'r T, T>,
+
+    // T: MulAcc,
+    // for<'r> &'r T: Add<&'r T, Output = T>,
+    // for<'r> &'r T: Mul<&'r T, Output = T>,
+
+    D: Dimension
+{
+    pub(super) fn make_spline(&mut self) -> Result<()> {
+        let axis = self.axis.unwrap_or_else(|| Axis(self.y.ndim() - 1));
+        self.axis = Some(axis);
+
+        let (x, y, weights) = self.prepared_data()?;
+
+        if self.sort_data_sites {
+            self.validate_data(x.view(), y.view())?;
+        }
+
+        match self.robust {
+            Some(loss) => self.make_robust_spline(x.view(), y.view(), weights.view(), loss),
+            None => {
+                self.robustness_weights = None;
+                self.outliers = None;
+                self.make_weighted_spline(x.view(), y.view(), weights.view())
+            },
+        }
+    }
+
+    /// Returns the data sites, 2-d `y` and the data weights for computing the spline
+    ///
+    /// The data sites are sorted and the duplicate data sites are merged in the data sites sorting mode.
+    pub(super) fn prepared_data(&self) -> Result<PreparedData<'a, T>> {
+        let axis = self.axis.unwrap_or_else(|| Axis(self.y.ndim() - 1));
+        let y = to_2d(self.y.clone(), axis)?;
+
+        let weights = self.weights
+            .map_or_else(|| Array1::ones(self.x.raw_dim()), |w| w.to_owned());
+
+        if self.sort_data_sites {
+            let (x, y, weights) = sort_data_sites(self.x, y.view(), weights.view());
+            Ok((CowArray::from(x), CowArray::from(y), weights))
+        } else {
+            Ok((CowArray::from(self.x), y, weights))
+        }
+    }
 
-            concatenate(Axis(0), &[p1.view(), p2.view(), p3.view(), p4]).unwrap().t().to_owned()
+    /// Computes the spline for the given data sites, 2-d `y` and the data weights
+    pub(super) fn make_weighted_spline(
+        &mut self,
+        breaks: ArrayView1<'_, T>,
+        y: ArrayView2<'_, T>,
+        weights: ArrayView1<'_, T>,
+    ) -> Result<()> {
+        let one = T::one();
+
+        if y.iter().any(|v| v.is_nan()) {
+            return self.make_missing_spline(breaks, y, weights)
+        }
+
+        // The last data site of the periodic data is the first data site of the next period,
+        // so the system is built for the data sites of one period without the last data site
+        let (y, weights) = if self.periodic {
+            let (y, weights) = merge_periodic_ends(y.view(), weights);
+            (CowArray::from(y), CowArray::from(weights))
+        } else {
+            (CowArray::from(y), CowArray::from(weights))
+        };
+        let weights = weights.view();
+
+        let pcount = breaks.len();
+
+        let (start, end) = self.end_conditions;
+        let is_natural = |c| matches!(c, EndCondition::Natural);
+
+        // The corner case for Nx2 data (2 data points) without the derivatives conditions
+        let is_linear = |c| matches!(c, EndCondition::Natural | EndCondition::NotAKnot);
+
+        if pcount == 2 && is_linear(start) && is_linear(end) {
+            let dx = diff(breaks.view(), None);
+            let dydx = diff(y.view(), Some(Axis(1))) / &dx;
+            let yi = y.slice(s![.., 0]).insert_axis(Axis(1));
+            let coeffs = concatenate![Axis(1), dydx, yi];
+
+            self.selected_smooth = Some(one);
+            self.cv_score = None;
+            self.spline = Some(NdSpline { smooth: self.selected_smooth, ..NdSpline::new(breaks, coeffs) });
+
+            return Ok(())
+        }
+
+        // The spline for 2 data points with the derivatives conditions interpolates the data values
+        // if the smoothing parameter is not set. The system is singular for zero smoothing parameter,
+        // so the smoothing parameter is limited by the machine epsilon because there is no scale
+        // of the smoothing parameter without the interior data sites
+        if pcount == 2 {
+            let smooth = self.smooth.unwrap_or(one);
+
+            let constrained = ConstrainedSystem::new(breaks, weights, start, end);
+            let (yi, c3) = constrained.solve(y.view(), smooth.max(T::epsilon()))?;
+            let coeffs = constrained.coeffs(&yi, &c3);
+
+            self.selected_smooth = Some(smooth);
+            self.cv_score = None;
+            self.spline = Some(NdSpline { smooth: self.selected_smooth, ..NdSpline::new(breaks, coeffs) });
+
+            return Ok(())
+        }
+
+        // General computing cubic smoothing spline for NxM data (3 and more data points)
+        let system = if self.periodic {
+            SmoothingSystem::new_periodic(breaks, weights)
+        } else {
+            SmoothingSystem::new(breaks, weights)
+        };
+        let b = system.rhs(y.view());
+
+        let (smooth, cv_score) = self.select_smooth(&system, y.view(), &b)?;
+
+        let coeffs = if self.periodic || (is_natural(start) && is_natural(end)) {
+            // Solve linear system Ax = b for the 2nd derivatives
+            let usol = system.solve(smooth, &b)?;
+            drop(b);
+
+            // Compute and concatenate spline coefficients
+            let yi = system.smoothed_values(y.view(), &usol, smooth);
+            system.coeffs(&yi, &usol, smooth)
+        } else {
+            drop(b);
+
+            // The limit for zero smoothing parameter is approximated by the small smoothing
+            // parameter which is relative to the scale of the data sites
+            let min_smooth = system.smooth_from_normalized(T::epsilon());
+
+            let constrained = ConstrainedSystem::new(breaks, weights, start, end);
+            let (yi, c3) = constrained.solve(y.view(), smooth.max(min_smooth))?;
+            constrained.coeffs(&yi, &c3)
         };
 
-        self.smooth = Some(smooth);
-        self.spline = Some(NdSpline::new(breaks, coeffs));
+        self.selected_smooth = if self.normalized_smooth {
+            Some(self.smooth.unwrap_or_else(|| system.smooth_to_normalized(smooth)))
+        } else {
+            Some(smooth)
+        };
+        self.cv_score = cv_score;
+        self.spline = Some(NdSpline {
+            smooth: self.selected_smooth,
+            periodic: self.periodic,
+            ..NdSpline::new(breaks, coeffs)
+        });
 
         Ok(())
     }
+
+    /// Selects the smoothing parameter by the given criteria for the system and 2-d `y`
+    ///
+    /// Returns the smoothing parameter (not normalized) and the cross-validation score.
+    pub(super) fn select_smooth(&self, system: &SmoothingSystem<T>, y: ArrayView2<'_, T>, b: &Array2<T>) -> Result<(T, Option<T>)> {
+        // The normalized value 0.5 is equal to the auto smoothing parameter,
+        // so it is not needed to handle the default normalized value specially
+        let explicit_smooth = match (self.smooth, self.normalized_smooth) {
+            (Some(smooth), true) => Some(system.smooth_from_normalized(smooth)),
+            (smooth, _) => smooth,
+        };
+
+        let selected = match (explicit_smooth, self.dof, self.tolerance, self.cross_validation) {
+            (Some(smooth), ..) => (smooth, None),
+            (None, Some(dof), ..) => (system.select_smooth_dof(dof)?, None),
+            (None, None, Some(tolerance), _) => (system.select_smooth_tolerance(y, b, tolerance)?, None),
+            (None, None, None, Some(cv)) => {
+                let (smooth, score) = system.select_smooth_cv(y, b, cv)?;
+                (smooth, Some(score))
+            },
+            (None, None, None, None) => (system.auto_smooth(), None),
+        };
+
+        Ok(selected)
+    }
+}
+
+
+/// Merges the values and the weights of the first and the last data sites of the periodic data
+///
+/// The merged value is the weighted mean of the values and the merged weight is the sum of the weights.
+/// Returns the 2-d values array with shape `[m, n - 1]` and the weights of the data sites of one period.
+pub(super) fn merge_periodic_ends<T>(y: ArrayView2<'_, T>, weights: ArrayView1<'_, T>) -> (Array2<T>, Array1<T>)
+    where
+        T: Real<T>
+{
+    let last = weights.len() - 1;
+    let (w_first, w_last) = (weights[0], weights[last]);
+    let w_merged = merge_periodic_weights(weights);
+    let w_sum = w_merged[0];
+
+    let y_first = (&y.column(0).mapv(|v| v * w_first) + &y.column(last).mapv(|v| v * w_last))
+        .mapv(|v| v / w_sum);
+
+    let mut y_merged = y.slice(s![.., ..last]).to_owned();
+    y_merged.column_mut(0).assign(&y_first);
+
+    (y_merged, w_merged)
+}
+
+
+/// Merges the weights of the first and the last data sites of the periodic data
+///
+/// Returns the weights of the data sites of one period, the merged weight is the sum of the weights.
+pub(super) fn merge_periodic_weights<T>(weights: ArrayView1<'_, T>) -> Array1<T>
+    where
+        T: Real<T>
+{
+    let last = weights.len() - 1;
+
+    let mut w_merged = weights.slice(s![..last]).to_owned();
+    w_merged[0] = weights[0] + weights[last];
+
+    w_merged
 }
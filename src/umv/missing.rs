@@ -0,0 +1,132 @@
+use ndarray::{prelude::*, concatenate};
+
+use crate::{
+    Real,
+    RealRef,
+    Result,
+    MissingValues,
+    ndarrayext::diff,
+};
+
+use super::{
+    CubicSmoothingSpline,
+    NdSpline,
+    constrained::ConstrainedSystem,
+    make::{SmoothingSystem, piecewise_coeffs},
+};
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension
+{
+    /// Computes the spline for 2-d `y` with the missing (NaN) values
+    ///
+    /// The missing values have zero weights, so the spline is computed by the constrained system
+    /// over all data sites which allows zero weights. The smoothing parameter is selected for
+    /// the data sites without missing values (`MissingValues::Ignore`) or for all data sites
+    /// (`MissingValues::PerSeries`) because the selection by the data sites weights and
+    /// the degrees of freedom does not depend on the data values. The smoothing parameter cannot be
+    /// selected for 2 data sites without missing values, so only the explicit smoothing parameter is used.
+    pub(super) fn make_missing_spline(
+        &mut self,
+        breaks: ArrayView1<'_, T>,
+        y: ArrayView2<'_, T>,
+        weights: ArrayView1<'_, T>,
+    ) -> Result<()> {
+        let one = T::one();
+
+        let (start, end) = self.end_conditions;
+
+        let valid = y.mapv(|v| !v.is_nan());
+        let y_filled = y.mapv(|v| if v.is_nan() { T::zero() } else { v });
+
+        let complete: Vec<usize> = valid.axis_iter(Axis(1))
+            .enumerate()
+            .filter(|(_, v)| v.iter().all(|&v| v))
+            .map(|(i, _)| i)
+            .collect();
+
+        // The smoothing parameter is selected for the data sites without missing values if there are
+        // more than 2 of them, otherwise the system for all data sites only defines the scale of
+        // the smoothing parameter
+        let selectable = self.missing_values == MissingValues::PerSeries || complete.len() > 2;
+
+        let system = if selectable && self.missing_values == MissingValues::Ignore {
+            let x_complete = breaks.select(Axis(0), &complete);
+            let w_complete = weights.select(Axis(0), &complete);
+            SmoothingSystem::new(x_complete.view(), w_complete.view())
+        } else {
+            SmoothingSystem::new(breaks, weights)
+        };
+
+        let (smooth, cv_score) = if selectable {
+            // The data values are not used for the selection with the missing values per series
+            // because the residual tolerance and cross-validation criteria are not allowed
+            let y_selection = match self.missing_values {
+                MissingValues::PerSeries => y_filled.clone(),
+                _ => y.select(Axis(1), &complete),
+            };
+
+            let b = system.rhs(y_selection.view());
+            self.select_smooth(&system, y_selection.view(), &b)?
+        } else {
+            // The corner case for 2 data sites without missing values, the spline interpolates
+            // the data values if the smoothing parameter is not set
+            match (self.smooth, self.normalized_smooth) {
+                (Some(smooth), true) => (system.smooth_from_normalized(smooth), None),
+                (Some(smooth), false) => (smooth, None),
+                (None, _) => (one, None),
+            }
+        };
+
+        // The limit for zero smoothing parameter is approximated by the small smoothing
+        // parameter which is relative to the scale of the data sites
+        let min_smooth = system.smooth_from_normalized(T::epsilon());
+        let solve_smooth = smooth.max(min_smooth);
+
+        let masked_weights = |mask: ArrayView1<'_, bool>| {
+            Array1::from_iter(weights.iter().zip(mask.iter())
+                .map(|(&w, &m)| if m { w } else { T::zero() }))
+        };
+
+        let (yi, c3) = if self.missing_values == MissingValues::PerSeries {
+            let mut yi_series = Vec::with_capacity(y.nrows());
+            let mut c3_series = Vec::with_capacity(y.nrows());
+
+            for (series, mask) in y_filled.outer_iter().zip(valid.outer_iter()) {
+                let constrained = ConstrainedSystem::new(breaks, masked_weights(mask).view(), start, end);
+                let (yi, c3) = constrained.solve(series.insert_axis(Axis(0)), solve_smooth)?;
+
+                yi_series.push(yi);
+                c3_series.push(c3);
+            }
+
+            let yi_views: Vec<_> = yi_series.iter().map(|a| a.view()).collect();
+            let c3_views: Vec<_> = c3_series.iter().map(|a| a.view()).collect();
+
+            (concatenate(Axis(1), &yi_views).unwrap(), concatenate(Axis(1), &c3_views).unwrap())
+        } else {
+            let mut mask = Array1::from_elem(breaks.len(), false);
+            complete.iter().for_each(|&i| mask[i] = true);
+
+            let constrained = ConstrainedSystem::new(breaks, masked_weights(mask.view()).view(), start, end);
+            constrained.solve(y_filled.view(), solve_smooth)?
+        };
+
+        let coeffs = piecewise_coeffs(diff(breaks, None).view(), &yi, &c3);
+
+        self.selected_smooth = if self.normalized_smooth {
+            Some(self.smooth.unwrap_or_else(|| system.smooth_to_normalized(smooth)))
+        } else {
+            Some(smooth)
+        };
+        self.cv_score = cv_score;
+        self.spline = Some(NdSpline { smooth: self.selected_smooth, ..NdSpline::new(breaks, coeffs) });
+
+        Ok(())
+    }
+}
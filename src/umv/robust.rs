@@ -0,0 +1,169 @@
+use ndarray::prelude::*;
+
+use crate::{
+    Real,
+    RealRef,
+    Result,
+    RobustLoss,
+};
+
+use super::CubicSmoothingSpline;
+
+
+/// The default maximum number of the robustness iterations
+pub(super) const DEFAULT_ITERATIONS: usize = 5;
+
+/// The tolerance of the maximum change of the robustness weights for stopping the iterations
+const WEIGHTS_TOL: f64 = 1e-6;
+
+/// The factor of the median absolute deviation for the consistent estimate of the normal scale
+const MAD_FACTOR: f64 = 0.6745;
+
+
+impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+
+        D: Dimension
+{
+    /// Computes the spline by iteratively reweighted least squares with the given robust loss
+    ///
+    /// The spline is computed with the data weights multiplied by the robustness weights
+    /// on every iteration.
+    pub(super) fn make_robust_spline(
+        &mut self,
+        x: ArrayView1<'_, T>,
+        y: ArrayView2<'_, T>,
+        weights: ArrayView1<'_, T>,
+        loss: RobustLoss<T>,
+    ) -> Result<()> {
+        let tol = T::from(WEIGHTS_TOL).unwrap();
+
+        let mut robustness_weights = Array1::<T>::ones(weights.raw_dim());
+        let mut converged = false;
+
+        for iteration in 0..=self.robust_iterations {
+            let w = robust_data_weights(weights, robustness_weights.view());
+            self.make_weighted_spline(x, y, w.view())?;
+
+            let yi = self.spline.as_ref().unwrap().evaluate(x);
+            let scaled = scaled_residuals(&(&y - &yi));
+
+            self.outliers = Some(scaled.mapv(|u| u > loss.tuning()));
+
+            if converged || iteration == self.robust_iterations || scaled.iter().all(|&u| u == T::zero()) {
+                break
+            }
+
+            let new_weights = scaled.mapv(|u| loss.weight(u));
+
+            let change = (&new_weights - &robustness_weights)
+                .fold(T::zero(), |acc, &v| acc.max(v.abs()));
+
+            converged = change <= tol;
+            robustness_weights = new_weights;
+        }
+
+        self.robustness_weights = Some(robustness_weights);
+
+        Ok(())
+    }
+}
+
+
+/// Returns the data weights multiplied by the robustness weights
+///
+/// The zero robustness weights are replaced by the small value because the system
+/// requires positive weights.
+pub(super) fn robust_data_weights<T>(weights: ArrayView1<'_, T>, robustness_weights: ArrayView1<'_, T>) -> Array1<T>
+    where
+        T: Real<T>
+{
+    let min_weight = T::epsilon().sqrt();
+    &weights * &robustness_weights.mapv(|v| v.max(min_weight))
+}
+
+
+/// Computes the absolute residuals scaled by the robust scale estimate
+///
+/// `residuals` is 2-d array with shape `[m, n]`, the residual of the data site is the euclidean norm
+/// of the column. The scale is estimated by the median absolute deviation. If the scale is zero
+/// (the most of the data is fitted exactly), the non-zero residuals are infinite.
+///
+/// The missing (NaN) residuals are skipped, the scaled residual of the data site without
+/// the residuals is zero, so it is never treated as outlier. If all residuals are missing,
+/// all scaled residuals are zero.
+fn scaled_residuals<T>(residuals: &Array2<T>) -> Array1<T>
+    where
+        T: Real<T>
+{
+    let norms = residuals.map_axis(Axis(0), |r| {
+        r.iter()
+            .filter(|v| !v.is_nan())
+            .fold(None, |acc: Option<T>, &v| Some(acc.unwrap_or_else(T::zero) + v * v))
+            .map_or(T::nan(), T::sqrt)
+    });
+
+    let scale = median(&norms).map_or(T::zero(), |m| m / T::from(MAD_FACTOR).unwrap());
+
+    norms.mapv(|v| {
+        if v.is_nan() {
+            T::zero()
+        } else if scale > T::zero() {
+            v / scale
+        } else if v > T::zero() {
+            T::infinity()
+        } else {
+            T::zero()
+        }
+    })
+}
+
+
+/// Computes the median of the values skipping NaN values
+///
+/// Returns None if all values are NaN or the values are empty.
+fn median<T>(values: &Array1<T>) -> Option<T>
+    where
+        T: Real<T>
+{
+    let mut sorted: Vec<T> = values.iter().cloned().filter(|v| !v.is_nan()).collect();
+
+    if sorted.is_empty() {
+        return None
+    }
+
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len();
+    let mid = n / 2;
+
+    if n % 2 == 1 {
+        Some(sorted[mid])
+    } else {
+        Some((sorted[mid - 1] + sorted[mid]) / T::from(2.0).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1, Array2};
+
+    use super::{median, scaled_residuals};
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&array![3., 1., f64::NAN, 2.]), Some(2.));
+        assert_eq!(median(&array![4., 1., 3., 2.]), Some(2.5));
+        assert_eq!(median(&array![f64::NAN, f64::NAN]), None);
+        assert_eq!(median(&Array1::<f64>::zeros(0)), None);
+    }
+
+    #[test]
+    fn test_scaled_residuals_all_missing() {
+        let residuals = Array2::<f64>::from_elem((2, 3), f64::NAN);
+        assert_eq!(scaled_residuals(&residuals), array![0., 0., 0.]);
+    }
+}
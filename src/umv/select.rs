@@ -0,0 +1,265 @@
+use ndarray::prelude::*;
+
+use crate::{CrossValidation, Real, RealRef, Result};
+
+use super::make::{SmoothingSystem, Factorization};
+
+
+/// The number of the grid points for the coarse search of the smoothing parameter
+const SEARCH_GRID_SIZE: usize = 25;
+
+/// The bounds of the search range in log10 scale relative to the auto smoothing parameter
+const SEARCH_LOG_BOUND: f64 = 6.0;
+
+/// The tolerance of the golden-section search in log10 scale
+const SEARCH_LOG_TOL: f64 = 1e-4;
+
+/// The maximum number of the bisection iterations for solving the smoothing parameter
+const BISECTION_MAX_ITER: usize = 100;
+
+
+impl<T> SmoothingSystem<T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>
+{
+    /// Returns the smoothing parameter for the given position in log10 scale of the search range
+    ///
+    /// The position 0 corresponds to the auto smoothing parameter. The smoothing parameter
+    /// tends to 1 for negative positions and to 0 for positive positions.
+    pub(super) fn smooth_from_log(&self, log_pos: T) -> T {
+        let one = T::one();
+        let ten = T::from::<f64>(10.0).unwrap();
+
+        let ratio = one / self.auto_smooth() - one;
+        one / (one + ratio * ten.powf(log_pos))
+    }
+
+    /// Computes the diagonal of the residual operator `I - H` for the given smoothing parameter
+    ///
+    /// `H` is the influence (hat) matrix which maps the data values to the smoothed values.
+    /// The residual operator is `6 * (1 - p) * W^-1 * Q * A^-1 * Q'`, so its diagonal
+    /// is computed from the quadratic forms of `A^-1` for the columns of `Q'`.
+    ///
+    /// The columns of `Q'` have non-zero elements only in the band of `A`, so for the banded
+    /// system only the band of `A^-1` is needed and the cost is linear in the number of
+    /// the data sites. The periodic system is solved for every column, the cost is quadratic.
+    pub(super) fn residual_diag(&self, smooth: T) -> Result<Array1<T>> {
+        let six = T::from::<f64>(6.0).unwrap();
+        let s1 = six * (T::one() - smooth);
+
+        let pcount = self.size();
+        let qcount = self.r.rows();
+
+        let factorization = self.factorize(smooth)?;
+
+        if let Factorization::Banded(ldl) = &factorization {
+            let z = &ldl.inverse_band();
+
+            return Ok(Array1::from_shape_fn((pcount, ), |i| {
+                let column = self.qt_column(i);
+
+                let qz = column.iter()
+                    .flat_map(|&(j, vj)| column.iter().map(move |&(k, vk)| vj * vk * z.get(j, k)))
+                    .fold(T::zero(), |acc, v| acc + v);
+
+                s1 * qz / self.weights[i]
+            }))
+        }
+
+        let mut diag = Array1::<T>::zeros((pcount, ));
+        let mut q = Array2::<T>::zeros((qcount, 1));
+
+        for i in 0..pcount {
+            let column = self.qt_column(i);
+
+            // The rows of the column may be repeated for the periodic system
+            let mut rows: Vec<usize> = column.iter().map(|&(j, _)| j).collect();
+            rows.sort_unstable();
+            rows.dedup();
+
+            for &(j, v) in &column {
+                q[[j, 0]] += v;
+            }
+
+            let z = factorization.solve(q.view());
+
+            let qz = rows.iter().fold(T::zero(), |acc, &j| acc + q[[j, 0]] * z[[j, 0]]);
+            diag[i] = s1 * qz / self.weights[i];
+
+            for &j in &rows {
+                q[[j, 0]] = T::zero();
+            }
+        }
+
+        Ok(diag)
+    }
+
+    /// Computes the residuals `y - f(x)` with shape `[n, m]` for the given smoothing parameter
+    fn residuals(&self, y: ArrayView2<'_, T>, b: &Array2<T>, smooth: T) -> Result<Array2<T>> {
+        let usol = self.solve(smooth, b)?;
+        Ok(&y.t() - &self.smoothed_values(y, &usol, smooth))
+    }
+
+    /// Computes the weighted residual sum of squares over all data dimensions
+    fn weighted_rss(&self, residuals: &Array2<T>) -> T {
+        residuals.outer_iter()
+            .zip(self.weights.iter())
+            .fold(T::zero(), |acc, (r, &w)| acc + w * r.dot(&r))
+    }
+
+    /// Computes the cross-validation score for the given smoothing parameter
+    ///
+    /// For multivariate data the score is averaged over the data dimensions.
+    pub(super) fn cv_score(&self, y: ArrayView2<'_, T>, b: &Array2<T>, smooth: T, cv: CrossValidation) -> Result<T> {
+        let n = T::from(self.size()).unwrap();
+        let m = T::from(y.nrows()).unwrap();
+
+        let residuals = self.residuals(y, b, smooth)?;
+        let diag = self.residual_diag(smooth)?;
+
+        let score = match cv {
+            CrossValidation::Generalized => {
+                let rss = self.weighted_rss(&residuals);
+                let trace = diag.sum();
+                n * rss / (m * trace * trace)
+            },
+            CrossValidation::LeaveOneOut => {
+                let rss = residuals.outer_iter()
+                    .zip(self.weights.iter().zip(diag.iter()))
+                    .fold(T::zero(), |acc, (r, (&w, &d))| acc + w * r.dot(&r) / (d * d));
+
+                rss / (n * m)
+            },
+        };
+
+        Ok(score)
+    }
+
+    /// Selects the smoothing parameter by minimizing the cross-validation score
+    ///
+    /// The score is minimized in log10 scale of the smoothing parameter ratio `(1 - p) / p`:
+    /// firstly the coarse grid search is performed and then the minimum is refined by
+    /// the golden-section search. Returns the smoothing parameter and the score.
+    pub(super) fn select_smooth_cv(&self, y: ArrayView2<'_, T>, b: &Array2<T>, cv: CrossValidation) -> Result<(T, T)> {
+        let score = |log_pos: T| {
+            let smooth = self.smooth_from_log(log_pos);
+            self.cv_score(y, b, smooth, cv)
+        };
+
+        let (log_pos, score) = minimize_log(score)?;
+        Ok((self.smooth_from_log(log_pos), score))
+    }
+
+    /// Selects the smoothing parameter for the given effective degrees of freedom `tr(H)`
+    ///
+    /// The degrees of freedom increase monotonically from 2 for `p = 0` to `n` for `p = 1`.
+    /// For the periodic system the degrees of freedom start from 1 (the weighted mean).
+    pub(super) fn select_smooth_dof(&self, dof: T) -> Result<T> {
+        let n = T::from(self.size()).unwrap();
+        bisect_smooth(|smooth| Ok(n - self.residual_diag(smooth)?.sum()), dof)
+    }
+
+    /// Selects the smoothing parameter for the given weighted residual sum of squares
+    ///
+    /// The residual decreases monotonically from the least-squares straight line fit residual
+    /// (the weighted mean residual for the periodic system) for `p = 0` to zero for `p = 1`.
+    pub(super) fn select_smooth_tolerance(&self, y: ArrayView2<'_, T>, b: &Array2<T>, tolerance: T) -> Result<T> {
+        let rss = |smooth| Ok(self.weighted_rss(&self.residuals(y, b, smooth)?));
+        bisect_smooth(|smooth| rss(smooth).map(|v: T| -v), -tolerance)
+    }
+}
+
+
+/// Solves `f(p) = target` for the monotonically increasing function `f` in range `[0, 1]` by bisection
+///
+/// If the target is out of the function range, the corresponding bound is returned.
+fn bisect_smooth<T, F>(f: F, target: T) -> Result<T>
+    where
+        T: Real<T>,
+        F: Fn(T) -> Result<T>
+{
+    let two = T::from(2.0).unwrap();
+
+    let mut lo = T::zero();
+    let mut hi = T::one();
+
+    if target <= f(lo)? {
+        return Ok(lo)
+    }
+    if target >= f(hi)? {
+        return Ok(hi)
+    }
+
+    for _ in 0..BISECTION_MAX_ITER {
+        let mid = (lo + hi) / two;
+
+        if mid <= lo || mid >= hi {
+            break
+        }
+
+        if f(mid)? < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok((lo + hi) / two)
+}
+
+
+/// Minimizes the function in the search range by the coarse grid search and the golden-section search
+///
+/// Returns the position of the minimum and the function value.
+fn minimize_log<T, F>(f: F) -> Result<(T, T)>
+    where
+        T: Real<T>,
+        F: Fn(T) -> Result<T>
+{
+    let bound = T::from(SEARCH_LOG_BOUND).unwrap();
+    let step = (bound + bound) / T::from(SEARCH_GRID_SIZE - 1).unwrap();
+
+    let grid = Array1::linspace(-bound, bound, SEARCH_GRID_SIZE);
+    let values = grid.iter().map(|&v| f(v)).collect::<Result<Array1<T>>>()?;
+
+    // NaN values are not allowed to be the minimum
+    let (imin, _) = values.iter().enumerate()
+        .fold((0, T::infinity()), |(im, vm), (i, &v)| if v < vm { (i, v) } else { (im, vm) });
+
+    let mut lo = grid[imin] - step;
+    let mut hi = grid[imin] + step;
+
+    let inv_phi = T::from((5.0f64.sqrt() - 1.0) / 2.0).unwrap();
+    let tol = T::from(SEARCH_LOG_TOL).unwrap();
+
+    let mut c = hi - (hi - lo) * inv_phi;
+    let mut d = lo + (hi - lo) * inv_phi;
+    let mut fc = f(c)?;
+    let mut fd = f(d)?;
+
+    while hi - lo > tol {
+        if fc < fd {
+            hi = d;
+            d = c;
+            fd = fc;
+            c = hi - (hi - lo) * inv_phi;
+            fc = f(c)?;
+        } else {
+            lo = c;
+            c = d;
+            fc = fd;
+            d = lo + (hi - lo) * inv_phi;
+            fd = f(d)?;
+        }
+    }
+
+    let pos = (lo + hi) / T::from(2.0).unwrap();
+    let value = f(pos)?;
+
+    if value <= values[imin] {
+        Ok((pos, value))
+    } else {
+        Ok((grid[imin], values[imin]))
+    }
+}
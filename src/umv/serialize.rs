@@ -0,0 +1,152 @@
+use std::convert::TryFrom;
+
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use crate::{
+    Real,
+    Result,
+    CsapsError,
+    CsapsError::InvalidSplineData,
+    serialize::{
+        SCHEMA_VERSION,
+        validate_version,
+        validate_order,
+        validate_breaks,
+        validate_coeffs,
+        validate_smooth,
+    },
+};
+
+use super::NdSpline;
+
+
+/// The serialized representation of `NdSpline`
+///
+/// The coefficients are stored as the flat vector in row-major order.
+/// The periodic flag is absent in the schema version 1.
+///
+/// `NdSpline` is serialized and deserialized through this representation. Deserializing `NdSpline`
+/// reports the invalid data as the serde error message, the data can be deserialized to
+/// `NdSplineData` and converted to `NdSpline` by `TryFrom` to get `CsapsError::InvalidSplineData`.
+///
+/// # Example
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use csaps::{CsapsError, NdSpline, NdSplineData};
+///
+/// // The number of the coefficients does not match the order and the pieces
+/// let json = r#"{"version": 2, "ndim": 1, "order": 2, "pieces": 2, "breaks": [0.0, 1.0, 2.0],
+///                "coeffs": [1.0, 0.0], "smooth": null, "periodic": false}"#;
+///
+/// let data: NdSplineData<f64> = serde_json::from_str(json).unwrap();
+///
+/// match NdSpline::try_from(data) {
+///     Err(CsapsError::InvalidSplineData(message)) => println!("{}", message),
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NdSplineData<T> {
+    version: u32,
+    ndim: usize,
+    order: usize,
+    pieces: usize,
+    breaks: Vec<T>,
+    coeffs: Vec<T>,
+    smooth: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    periodic: Option<bool>,
+}
+
+
+impl<T> From<&NdSpline<T>> for NdSplineData<T>
+    where
+        T: Real<T>
+{
+    fn from(spline: &NdSpline<T>) -> Self {
+        NdSplineData {
+            version: SCHEMA_VERSION,
+            ndim: spline.ndim,
+            order: spline.order,
+            pieces: spline.pieces,
+            breaks: spline.breaks.to_vec(),
+            coeffs: spline.coeffs.iter().cloned().collect(),
+            smooth: spline.smooth,
+            periodic: Some(spline.periodic),
+        }
+    }
+}
+
+
+impl<T> TryFrom<NdSplineData<T>> for NdSpline<T>
+    where
+        T: Real<T>
+{
+    type Error = CsapsError;
+
+    fn try_from(data: NdSplineData<T>) -> Result<Self> {
+        validate_version(data.version)?;
+        validate_order(data.order)?;
+        validate_breaks(&data.breaks, data.pieces)?;
+
+        if data.ndim == 0 {
+            return Err(
+                InvalidSplineData("The spline dimensionality must be greater or equal to 1".to_string())
+            )
+        }
+
+        let columns = data.order.checked_mul(data.pieces)
+            .ok_or_else(|| InvalidSplineData("The spline order is too large".to_string()))?;
+
+        let shape = [data.ndim, columns];
+        validate_coeffs(&data.coeffs, &shape)?;
+        validate_smooth(data.smooth)?;
+
+        let periodic = match (data.version, data.periodic) {
+            (1, _) => false,
+            (_, Some(periodic)) => periodic,
+            (_, None) => {
+                return Err(
+                    InvalidSplineData("The periodic flag is missing".to_string())
+                )
+            },
+        };
+
+        let coeffs = Array2::from_shape_vec(shape, data.coeffs)
+            .map_err(|err| InvalidSplineData(err.to_string()))?;
+
+        Ok(NdSpline {
+            ndim: data.ndim,
+            order: data.order,
+            pieces: data.pieces,
+            breaks: Array1::from(data.breaks),
+            coeffs,
+            smooth: data.smooth,
+            periodic,
+        })
+    }
+}
+
+
+impl<T> Serialize for NdSpline<T>
+    where
+        T: Real<T> + Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        NdSplineData::from(self).serialize(serializer)
+    }
+}
+
+
+impl<'de, T> Deserialize<'de> for NdSpline<T>
+    where
+        T: Real<T> + Deserialize<'de>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let data = NdSplineData::deserialize(deserializer)?;
+        NdSpline::try_from(data).map_err(D::Error::custom)
+    }
+}
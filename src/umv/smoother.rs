@@ -0,0 +1,437 @@
+use ndarray::{prelude::*, concatenate, s};
+
+use crate::{
+    CsapsError::InvalidInputData,
+    Real,
+    RealRef,
+    Result,
+    ndarrayext::diff,
+    parallel::for_each_chunk,
+    validate::{validate_data_sites, validate_data_size, validate_dof, validate_smooth_value, validate_weights},
+};
+
+use super::{
+    NdSpline,
+    make::{Factorization, SmoothingSystem, merge_periodic_ends, merge_periodic_weights},
+};
+
+
+/// The smoothing system and its factorization for the smoothing parameter
+struct FactorizedSystem<T>
+    where
+        T: Real<T>
+{
+    /// The data weights of all data sites
+    weights: Array1<T>,
+
+    /// The matrices of the system
+    system: SmoothingSystem<T>,
+
+    /// The factorization of the system matrix
+    factorization: Factorization<T>,
+
+    /// The smoothing parameter (not normalized)
+    smooth: T,
+}
+
+
+/// The state of the made smoother
+enum SmootherState<T>
+    where
+        T: Real<T>
+{
+    /// The straight line through 2 data sites
+    Linear,
+
+    /// The factorized system for 3 and more data sites
+    Factorized(Box<FactorizedSystem<T>>),
+}
+
+
+/// The reusable cubic smoothing spline smoother for the fixed data sites, weights and smoothing parameter
+///
+/// The linear system of the smoothing spline depends only on the data sites, the weights and
+/// the smoothing parameter, so `CubicSmoother` builds and factorizes the system once in `make`
+/// and then computes the splines for any number of `y` arrays (or the batches of data series)
+/// by `fit` method solving the factorized system. The spline computed by `fit` is equal
+/// to the spline computed by `CubicSmoothingSpline` with the same parameters.
+///
+/// The smoothing parameter can be set explicitly (also in the normalized mode), selected by
+/// the target effective degrees of freedom or computed automatically, because these criteria
+/// do not depend on the data values. Only the natural and the periodic splines are supported.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{Array1, Array2};
+/// use csaps::CubicSmoother;
+///
+/// let x = Array1::linspace(0., 6., 25);
+///
+/// let smoother = CubicSmoother::new(&x)
+///     .with_smooth(0.9)
+///     .make().unwrap();
+///
+/// // Every row of `y` is the data series
+/// for shift in 0..10 {
+///     let y = Array2::from_shape_fn((100, 25), |(i, j)| (x[j] + (i + shift) as f64).sin());
+///     let spline = smoother.fit(&y).unwrap();
+///     assert_eq!(spline.ndim(), 100);
+/// }
+/// ```
+///
+pub struct CubicSmoother<'a, T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+{
+    /// X data sites (also breaks)
+    x: ArrayView1<'a, T>,
+
+    /// The optional data weights
+    weights: Option<ArrayView1<'a, T>>,
+
+    /// The optional smoothing parameter
+    smooth: Option<T>,
+
+    /// The optional target effective degrees of freedom for selecting the smoothing parameter
+    dof: Option<T>,
+
+    /// The flag of the normalized smoothing parameter mode
+    normalized_smooth: bool,
+
+    /// The flag of the periodic spline
+    periodic: bool,
+
+    /// The smoothing parameter which has been used for making the smoother
+    selected_smooth: Option<T>,
+
+    /// The state which is computed by `make`
+    state: Option<SmootherState<T>>,
+}
+
+
+impl<'a, T> CubicSmoother<'a, T>
+    where
+        T: Real<T>,
+        for<'r> &'r T: RealRef<&'r T, T>,
+{
+    /// Creates `CubicSmoother` struct from the given `X` data sites
+    ///
+    /// `x` must be strictly increasing: `x1 < x2 < x3 < ... < xN`.
+    pub fn new<X>(x: X) -> Self
+        where
+            X: AsArray<'a, T>
+    {
+        CubicSmoother {
+            x: x.into(),
+            weights: None,
+            smooth: None,
+            dof: None,
+            normalized_smooth: false,
+            periodic: false,
+            selected_smooth: None,
+            state: None,
+        }
+    }
+
+    /// Sets the data weights
+    ///
+    /// `weights.len()` must be equal to `x.len()`
+    pub fn with_weights<W>(mut self, weights: W) -> Self
+        where
+            W: AsArray<'a, T>
+    {
+        self.invalidate();
+        self.weights = Some(weights.into());
+        self
+    }
+
+    /// Sets the data weights in `Option` wrap
+    pub fn with_optional_weights<W>(mut self, weights: Option<W>) -> Self
+        where
+            W: AsArray<'a, T>
+    {
+        self.invalidate();
+        self.weights = weights.map(|w| w.into());
+        self
+    }
+
+    /// Sets the smoothing parameter
+    ///
+    /// The target degrees of freedom is reset. See `CubicSmoothingSpline::with_smooth` for details.
+    pub fn with_smooth(mut self, smooth: T) -> Self {
+        self.invalidate();
+        self.reset_smooth_criteria();
+        self.smooth = Some(smooth);
+        self
+    }
+
+    /// Sets the smoothing parameter in `Option` wrap
+    pub fn with_optional_smooth(mut self, smooth: Option<T>) -> Self {
+        self.invalidate();
+        if smooth.is_some() {
+            self.reset_smooth_criteria();
+        }
+        self.smooth = smooth;
+        self
+    }
+
+    /// Sets the target effective degrees of freedom for selecting the smoothing parameter
+    ///
+    /// The explicitly set smoothing parameter is reset. See `CubicSmoothingSpline::with_dof` for details.
+    pub fn with_dof(mut self, dof: T) -> Self {
+        self.invalidate();
+        self.reset_smooth_criteria();
+        self.dof = Some(dof);
+        self
+    }
+
+    /// Sets the normalized smoothing parameter mode
+    ///
+    /// See `CubicSmoothingSpline::with_normalized_smooth` for details.
+    pub fn with_normalized_smooth(mut self, normalized_smooth: bool) -> Self {
+        self.invalidate();
+        self.normalized_smooth = normalized_smooth;
+        self
+    }
+
+    /// Sets the periodic spline mode
+    ///
+    /// See `CubicSmoothingSpline::with_periodic` for details.
+    pub fn with_periodic(mut self, periodic: bool) -> Self {
+        self.invalidate();
+        self.periodic = periodic;
+        self
+    }
+
+    /// Returns the smoothing parameter or None
+    ///
+    /// After making the smoother the computed smoothing parameter is returned
+    /// (the normalized value in the normalized mode), otherwise the explicitly set value is returned.
+    pub fn smooth(&self) -> Option<T> {
+        self.selected_smooth.or(self.smooth)
+    }
+
+    /// Returns the flag of the normalized smoothing parameter mode
+    pub fn normalized_smooth(&self) -> bool {
+        self.normalized_smooth
+    }
+
+    /// Returns the flag of the periodic spline
+    pub fn periodic(&self) -> bool {
+        self.periodic
+    }
+
+    /// Invalidate made smoother
+    fn invalidate(&mut self) {
+        self.state = None;
+        self.selected_smooth = None;
+    }
+
+    /// Resets the smoothing parameter and the target degrees of freedom
+    fn reset_smooth_criteria(&mut self) {
+        self.smooth = None;
+        self.dof = None;
+    }
+
+    /// Makes (builds and factorizes) the smoothing system for given data sites and parameters
+    ///
+    /// # Errors
+    ///
+    /// - If the data sites or parameters are invalid
+    ///
+    pub fn make(mut self) -> Result<Self> {
+        self.make_validate()?;
+        self.make_system()?;
+        Ok(self)
+    }
+
+    /// Computes the spline for the given 2-d `y` with shape `[m, n]` where `n` is the number of the data sites
+    ///
+    /// Every row of `y` is the data series, so the spline with `m` dimensions is computed.
+    /// The chunks of the data series are computed in parallel with `rayon` feature.
+    ///
+    /// # Errors
+    ///
+    /// - If the smoother yet has not been made
+    /// - If the shape of `y` does not match the data sites
+    /// - If `y` contains missing (NaN) values
+    ///
+    pub fn fit<'b, Y>(&self, y: Y) -> Result<NdSpline<T>>
+        where
+            Y: AsArray<'b, T, Ix2>,
+            T: 'b
+    {
+        let y = y.into();
+
+        let state = self.state.as_ref().ok_or_else(|| {
+            InvalidInputData(
+                "The smoother has not been computed, use `make` method before".to_string()
+            )
+        })?;
+
+        let x_size = self.x.len();
+
+        if y.ncols() != x_size {
+            return Err(
+                InvalidInputData(
+                    format!("The shape[1] ({}) of `y` data is not equal to `x` size ({})", y.ncols(), x_size)
+                )
+            )
+        }
+
+        if y.iter().any(|v| v.is_nan()) {
+            return Err(
+                InvalidInputData("`y` data contains missing (NaN) values".to_string())
+            )
+        }
+
+        // The linear spline has 1 piece of order 2, the cubic spline has n - 1 pieces of order 4
+        let coeffs_size = match state {
+            SmootherState::Linear => 2,
+            SmootherState::Factorized(_) => 4 * (x_size - 1),
+        };
+
+        let mut coeffs = Array2::<T>::zeros((y.nrows(), coeffs_size));
+
+        for_each_chunk(coeffs.view_mut(), Axis(0), 1, |start, mut coeffs| {
+            let y = y.slice(s![start..start + coeffs.nrows(), ..]);
+            coeffs.assign(&self.fit_coeffs(state, y));
+        });
+
+        Ok(NdSpline {
+            smooth: self.selected_smooth,
+            periodic: self.periodic,
+            ..NdSpline::new(self.x, coeffs)
+        })
+    }
+
+    /// Computes the spline coefficients for the given 2-d `y`
+    fn fit_coeffs(&self, state: &SmootherState<T>, y: ArrayView2<'_, T>) -> Array2<T> {
+        match state {
+            SmootherState::Linear => {
+                let dx = diff(self.x, None);
+                let dydx = diff(y, Some(Axis(1))) / &dx;
+                let yi = y.slice(s![.., 0]).insert_axis(Axis(1));
+
+                concatenate![Axis(1), dydx, yi]
+            },
+            SmootherState::Factorized(factorized) => {
+                let FactorizedSystem { weights, system, factorization, smooth } = factorized.as_ref();
+
+                let y = if self.periodic {
+                    CowArray::from(merge_periodic_ends(y, weights.view()).0)
+                } else {
+                    CowArray::from(y)
+                };
+
+                let b = system.rhs(y.view());
+                let usol = factorization.solve(b.view());
+                drop(b);
+
+                let yi = system.smoothed_values(y.view(), &usol, *smooth);
+                system.coeffs(&yi, &usol, *smooth)
+            },
+        }
+    }
+
+    /// Computes the diagonal of the influence (hat) matrix for all data sites
+    ///
+    /// The diagonal is computed from the band of the inverse system matrix without solving
+    /// the system for the unit data vectors. The merged first and last data sites of
+    /// the periodic spline share the influence in proportion to their weights.
+    pub(super) fn influence_diag(&self) -> Result<Array1<T>> {
+        let state = self.state.as_ref().ok_or_else(|| {
+            InvalidInputData(
+                "The smoother has not been computed, use `make` method before".to_string()
+            )
+        })?;
+
+        let factorized = match state {
+            // The straight line through 2 data sites is the interpolant
+            SmootherState::Linear => return Ok(Array1::ones(self.x.raw_dim())),
+            SmootherState::Factorized(factorized) => factorized.as_ref(),
+        };
+
+        let FactorizedSystem { weights, system, smooth, .. } = factorized;
+        let diag = system.residual_diag(*smooth)?.mapv(|v| T::one() - v);
+
+        if !self.periodic {
+            return Ok(diag)
+        }
+
+        let last = weights.len() - 1;
+        let w_sum = weights[0] + weights[last];
+
+        let mut full = Array1::<T>::zeros(weights.raw_dim());
+        full.slice_mut(s![..last]).assign(&diag);
+        full[0] = diag[0] * weights[0] / w_sum;
+        full[last] = diag[0] * weights[last] / w_sum;
+
+        Ok(full)
+    }
+
+    fn make_validate(&self) -> Result<()> {
+        let x_size = self.x.len();
+
+        validate_data_size(x_size, self.periodic)?;
+        validate_data_sites(self.x)?;
+
+        if let Some(weights) = self.weights {
+            validate_weights(weights, x_size)?;
+        }
+
+        if let Some(smooth) = self.smooth {
+            validate_smooth_value(smooth)?;
+        }
+
+        if let Some(dof) = self.dof {
+            validate_dof(dof, x_size, self.periodic)?;
+        }
+
+        Ok(())
+    }
+
+    fn make_system(&mut self) -> Result<()> {
+        let breaks = self.x;
+
+        if breaks.len() == 2 {
+            self.selected_smooth = Some(T::one());
+            self.state = Some(SmootherState::Linear);
+            return Ok(())
+        }
+
+        let weights = self.weights
+            .map_or_else(|| Array1::ones(breaks.raw_dim()), |w| w.to_owned());
+
+        // The system of the periodic spline is built for the data sites of one period
+        let system = if self.periodic {
+            SmoothingSystem::new_periodic(breaks, merge_periodic_weights(weights.view()).view())
+        } else {
+            SmoothingSystem::new(breaks, weights.view())
+        };
+
+        // The normalized value 0.5 is equal to the auto smoothing parameter
+        let smooth = match (self.smooth, self.normalized_smooth, self.dof) {
+            (Some(smooth), true, _) => system.smooth_from_normalized(smooth),
+            (Some(smooth), false, _) => smooth,
+            (None, _, Some(dof)) => system.select_smooth_dof(dof)?,
+            (None, _, None) => system.auto_smooth(),
+        };
+
+        self.selected_smooth = if self.normalized_smooth {
+            Some(self.smooth.unwrap_or_else(|| system.smooth_to_normalized(smooth)))
+        } else {
+            Some(smooth)
+        };
+
+        let factorization = system.factorize(smooth)?;
+
+        self.state = Some(SmootherState::Factorized(
+            Box::new(FactorizedSystem { weights, system, factorization, smooth })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use ndarray::prelude::*;
+
+use crate::Real;
+
+
+/// Sorts the data sites and merges the duplicate (almost equal) data sites
+///
+/// The values and the weights are permuted with the data sites. The merged data site is the first
+/// of the duplicate sites, the merged value is the weighted mean of the values and the merged weight
+/// is the sum of the weights (as in MATLAB `csaps`). The missing (NaN) values are skipped while
+/// averaging, so the merged value is missing only if all values of the duplicate sites are missing.
+/// The weights of the data sites which are missing in all data series are not summed, so the merged
+/// weight is not increased by the missing observations. The weights are shared by the data series,
+/// so the data sites which are missing only in some data series are summed for all data series.
+///
+/// `y` is 2-d array with shape `[m, n]`. Returns the data sites, the values and the weights
+/// of the unique data sites.
+pub(super) fn sort_data_sites<T>(x: ArrayView1<'_, T>, y: ArrayView2<'_, T>, weights: ArrayView1<'_, T>)
+    -> (Array1<T>, Array2<T>, Array1<T>)
+    where
+        T: Real<T>
+{
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_by(|&i, &j| x[i].partial_cmp(&x[j]).unwrap());
+
+    // The groups of the indices of the duplicate data sites in the sorted order
+    let mut groups: Vec<Vec<usize>> = Vec::with_capacity(x.len());
+
+    for &i in order.iter() {
+        match groups.last_mut() {
+            Some(group) if x[i].almost_equals(x[group[0]]) => group.push(i),
+            _ => groups.push(vec![i]),
+        }
+    }
+
+    let x_sorted = Array1::from_iter(groups.iter().map(|group| x[group[0]]));
+    let is_observed = |i: usize| y.column(i).iter().any(|v| !v.is_nan());
+
+    let w_sorted = Array1::from_iter(groups.iter().map(|group| {
+        let observed: Vec<usize> = group.iter().copied().filter(|&i| is_observed(i)).collect();
+
+        // The weight of the missing merged value is not used, so all weights are summed
+        let sites = if observed.is_empty() { group } else { &observed };
+        sites.iter().fold(T::zero(), |acc, &i| acc + weights[i])
+    }));
+
+    let mut y_sorted = Array2::<T>::zeros((y.nrows(), groups.len()));
+
+    for (mut column, group) in y_sorted.columns_mut().into_iter().zip(groups.iter()) {
+        if let [i] = group[..] {
+            column.assign(&y.column(i));
+            continue
+        }
+
+        for (yi, row) in column.iter_mut().zip(y.outer_iter()) {
+            let (sum, w_sum) = group.iter()
+                .filter(|&&i| !row[i].is_nan())
+                .fold((T::zero(), T::zero()), |(sum, w_sum), &i| (sum + row[i] * weights[i], w_sum + weights[i]));
+
+            *yi = sum / w_sum;
+        }
+    }
+
+    (x_sorted, y_sorted, w_sorted)
+}
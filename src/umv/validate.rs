@@ -2,14 +2,19 @@ use ndarray::{
     Dimension,
     Axis,
     ArrayView1,
+    ArrayView2,
 };
 
 use crate::{
     Real,
     CubicSmoothingSpline,
+    EndCondition,
+    MissingValues,
     CsapsError::InvalidInputData,
     Result,
-    validate::{validate_data_sites, validate_smooth_value}, RealRef,
+    ndarrayext::to_2d,
+    validate::{validate_data_sites, validate_data_size, validate_dof, validate_smooth_value, validate_weights},
+    RealRef,
 };
 
 
@@ -23,16 +28,19 @@ impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
     pub(super) fn make_validate(&self) -> Result<()> {
         let x_size = self.x.len();
 
-        if x_size < 2 {
-            return Err(
-                InvalidInputData(
-                    "The size of data vectors must be greater or equal to 2".to_string()
+        // The size of the periodic data is validated with the data after merging the data sites
+        validate_data_size(x_size, false)?;
+
+        if self.sort_data_sites {
+            if !self.x.iter().all(|v| v.is_finite()) {
+                return Err(
+                    InvalidInputData("Data site values must be finite".to_string())
                 )
-            )
+            }
+        } else {
+            validate_data_sites(self.x)?;
         }
 
-        validate_data_sites(self.x)?;
-
         if self.y.ndim() == 0 {
             return Err(
                 InvalidInputData("`y` has zero dimensionality".to_string())
@@ -63,19 +71,157 @@ impl<'a, T, D> CubicSmoothingSpline<'a, T, D>
         }
 
         if let Some(weights) = self.weights {
-            let w_size = weights.len();
+            validate_weights(weights, x_size)?;
+        }
 
-            if w_size != x_size {
+        if let Some(smooth) = self.smooth {
+            validate_smooth_value(smooth)?;
+        }
+
+        if let Some(tolerance) = self.tolerance {
+            if !(tolerance >= T::zero() && tolerance.is_finite()) {
                 return Err(
                     InvalidInputData(
-                        format!("`weights` size ({}) is not equal to `x` size ({})", w_size, x_size)
+                        format!("`tolerance` value must be non-negative and finite, given {:?}", tolerance)
                     )
                 )
             }
         }
 
-        if let Some(smooth) = self.smooth {
-            validate_smooth_value(smooth)?;
+        if let Some(loss) = self.robust {
+            let tuning = loss.tuning();
+
+            if !(tuning > T::zero() && tuning.is_finite()) {
+                return Err(
+                    InvalidInputData(
+                        format!("The robust loss tuning constant must be positive and finite, given {:?}", tuning)
+                    )
+                )
+            }
+        }
+
+        let (start, end) = self.end_conditions;
+
+        if self.periodic && (start != EndCondition::Natural || end != EndCondition::Natural) {
+            return Err(
+                InvalidInputData(
+                    "The end conditions cannot be set for the periodic spline".to_string()
+                )
+            )
+        }
+
+        for condition in [start, end] {
+            if let EndCondition::Clamped(value) | EndCondition::SecondDerivative(value) = condition {
+                if !value.is_finite() {
+                    return Err(
+                        InvalidInputData(
+                            format!("The end condition value must be finite, given {:?}", value)
+                        )
+                    )
+                }
+            }
+        }
+
+        // The sorted data is validated after sorting and merging the data sites
+        if !self.sort_data_sites {
+            self.validate_data(self.x, to_2d(self.y.clone(), axis)?.view())?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates the parameters which depend on the data sites and 2-d `y` with shape `[m, n]`
+    pub(super) fn validate_data(&self, x: ArrayView1<'_, T>, y: ArrayView2<'_, T>) -> Result<()> {
+        let x_size = x.len();
+
+        validate_data_size(x_size, self.periodic)?;
+
+        let (start, end) = self.end_conditions;
+        let is_derivative = |c| matches!(c, EndCondition::Clamped(_) | EndCondition::SecondDerivative(_));
+
+        if let (2, Some(smooth), true) = (x_size, self.smooth, self.normalized_smooth) {
+            if smooth < T::one() && (is_derivative(start) || is_derivative(end)) {
+                return Err(
+                    InvalidInputData(
+                        format!("The normalized smoothing parameter {:?} cannot be mapped for 2 data sites \
+                                 with the derivatives end conditions, only 1 is allowed", smooth)
+                    )
+                )
+            }
+        }
+
+        if let Some(dof) = self.dof {
+            validate_dof(dof, x_size, self.periodic)?;
+        }
+
+        self.validate_missing_values(y)
+    }
+
+    fn validate_missing_values(&self, y: ArrayView2<'_, T>) -> Result<()> {
+        if !y.iter().any(|v| v.is_nan()) {
+            return Ok(())
+        }
+
+        if self.missing_values == MissingValues::Error {
+            return Err(
+                InvalidInputData("`y` data contains missing (NaN) values".to_string())
+            )
+        }
+
+        if self.periodic {
+            return Err(
+                InvalidInputData(
+                    "The missing (NaN) values in `y` data are not supported for the periodic spline".to_string()
+                )
+            )
+        }
+
+        if self.missing_values == MissingValues::Ignore {
+            let complete = y.columns().into_iter()
+                .filter(|column| column.iter().all(|v| !v.is_nan()))
+                .count();
+
+            if complete < 2 {
+                return Err(
+                    InvalidInputData(
+                        format!("The number of the data sites without missing values ({}) must be greater or equal to 2",
+                                complete)
+                    )
+                )
+            }
+
+            if complete == 2 && self.smooth.is_none()
+                && (self.dof.is_some() || self.tolerance.is_some() || self.cross_validation.is_some()) {
+                return Err(
+                    InvalidInputData(
+                        "The smoothing parameter cannot be selected by the degrees of freedom, the residual tolerance \
+                         or cross-validation for 2 data sites without missing values".to_string()
+                    )
+                )
+            }
+        } else {
+            for (series, lane) in y.outer_iter().enumerate() {
+                let count = lane.iter().filter(|v| !v.is_nan()).count();
+
+                if count < 2 {
+                    return Err(
+                        InvalidInputData(
+                            format!("The number of not missing values ({}) must be greater or equal to 2 for `y` data series {}",
+                                    count, series)
+                        )
+                    )
+                }
+            }
+
+            if self.smooth.is_none() && self.dof.is_none()
+                && (self.tolerance.is_some() || self.cross_validation.is_some()) {
+                return Err(
+                    InvalidInputData(
+                        "The smoothing parameter cannot be selected by the residual tolerance or cross-validation \
+                         for the missing values per series".to_string()
+                    )
+                )
+            }
         }
 
         Ok(())
@@ -1,5 +1,4 @@
-use almost::AlmostEqual;
-use ndarray::{ArrayView1, NdFloat};
+use ndarray::ArrayView1;
 
 use crate::{Real, Result, CsapsError::InvalidInputData};
 
@@ -40,3 +39,97 @@ pub(crate) fn validate_smooth_value<T>(smooth: T) -> Result<()>
 
     Ok(())
 }
+
+
+pub(crate) fn validate_data_size(size: usize, periodic: bool) -> Result<()> {
+    if size < 2 {
+        return Err(
+            InvalidInputData(
+                "The size of data vectors must be greater or equal to 2".to_string()
+            )
+        )
+    }
+
+    if periodic && size < 3 {
+        return Err(
+            InvalidInputData(
+                "The size of data vectors must be greater or equal to 3 for the periodic spline".to_string()
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_weights<T>(weights: ArrayView1<T>, size: usize) -> Result<()>
+    where
+        T: Real<T>
+{
+    let w_size = weights.len();
+
+    if w_size != size {
+        return Err(
+            InvalidInputData(
+                format!("`weights` size ({}) is not equal to `x` size ({})", w_size, size)
+            )
+        )
+    }
+
+    validate_weights_values(weights)
+}
+
+
+pub(crate) fn validate_dof<T>(dof: T, size: usize, periodic: bool) -> Result<()>
+    where
+        T: Real<T>
+{
+    // The periodic spline has one data site less and the constant null space
+    let (dof_min, dof_max) = if periodic { (1, size - 1) } else { (2, size) };
+
+    if !(dof >= T::from(dof_min).unwrap() && dof <= T::from(dof_max).unwrap()) {
+        return Err(
+            InvalidInputData(
+                format!("`dof` value must be in range {}..{}, given {:?}", dof_min, dof_max, dof)
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_weights_values<T>(weights: ArrayView1<T>) -> Result<()>
+    where
+        T: Real<T>
+{
+    if let Some(w) = weights.iter().find(|w| !(**w > T::zero() && w.is_finite())) {
+        return Err(
+            InvalidInputData(
+                format!("`weights` values must be positive and finite, given {:?}", w)
+            )
+        )
+    }
+
+    Ok(())
+}
+
+
+pub(crate) fn validate_output_shape<I>(shape: &[usize], expected: I) -> Result<()>
+    where
+        I: IntoIterator<Item = usize>,
+        I::IntoIter: Clone
+{
+    let expected = expected.into_iter();
+
+    if !shape.iter().copied().eq(expected.clone()) {
+        return Err(
+            InvalidInputData(
+                format!("The shape of the output array {:?} is not equal to the shape of the values {:?}",
+                        shape, expected.collect::<Vec<_>>())
+            )
+        )
+    }
+
+    Ok(())
+}
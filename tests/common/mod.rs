@@ -0,0 +1,41 @@
+//! The data shared by the integration tests
+//!
+//! Every test crate uses only a part of the functions.
+#![allow(dead_code)]
+
+use ndarray::{array, Array1, Array2};
+
+
+/// Returns the sine data sites and values with the fixed noise
+pub fn noisy_sine() -> (Array1<f64>, Array1<f64>) {
+    let x = Array1::linspace(0., 6., 25);
+    let noise = array![
+        0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+        0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+    let y = x.mapv(f64::sin) + noise;
+
+    (x, y)
+}
+
+
+/// Returns `m` data series with the different frequencies for the data sites
+pub fn data_series(x: &Array1<f64>, m: usize) -> Array2<f64> {
+    Array2::from_shape_fn((m, x.len()), |(i, j)| {
+        (x[j] * (1.0 + i as f64 * 0.3)).sin() + 0.05 * ((i * 7 + j * 13) % 11) as f64
+    })
+}
+
+
+/// Returns `(x0.len() * x1.len(), 2)` array of the points of the 2-d mesh
+pub fn mesh_points(x0: &Array1<f64>, x1: &Array1<f64>) -> Array2<f64> {
+    let mut points = Array2::zeros((x0.len() * x1.len(), 2));
+
+    for (i, &v0) in x0.iter().enumerate() {
+        for (j, &v1) in x1.iter().enumerate() {
+            points[[i * x1.len() + j, 0]] = v0;
+            points[[i * x1.len() + j, 1]] = v1;
+        }
+    }
+
+    points
+}
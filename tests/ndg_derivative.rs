@@ -0,0 +1,249 @@
+mod common;
+
+use ndarray::{array, Array1, Array2};
+use approx::assert_abs_diff_eq;
+
+use csaps::{GridCubicSmoothingSpline, Extrapolation};
+
+use common::mesh_points;
+
+
+fn bilinear_data(x0: &Array1<f64>, x1: &Array1<f64>) -> Array2<f64> {
+    Array2::from_shape_fn((x0.len(), x1.len()), |(i, j)| {
+        2. * x0[i] + 3. * x1[j] + x0[i] * x1[j]
+    })
+}
+
+
+#[test]
+fn test_evaluate_derivative_surface() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+    let y = bilinear_data(&x0, &x1);
+
+    let xi0 = array![1., 1.5, 2.7, 4.];
+    let xi1 = array![1., 2.2, 3.5, 5.];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let d0 = s.evaluate_derivative(&xi, &[1, 0]).unwrap();
+    let d1 = s.evaluate_derivative(&xi, &[0, 1]).unwrap();
+    let d01 = s.evaluate_derivative(&xi, &[1, 1]).unwrap();
+    let d00 = s.evaluate_derivative(&xi, &[2, 0]).unwrap();
+
+    let d0_expected = Array2::from_shape_fn((4, 4), |(_, j)| 2. + xi1[j]);
+    let d1_expected = Array2::from_shape_fn((4, 4), |(i, _)| 3. + xi0[i]);
+
+    assert_abs_diff_eq!(d0, d0_expected, epsilon = 1e-10);
+    assert_abs_diff_eq!(d1, d1_expected, epsilon = 1e-10);
+    assert_abs_diff_eq!(d01, Array2::ones((4, 4)), epsilon = 1e-10);
+    assert_abs_diff_eq!(d00, Array2::zeros((4, 4)), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_evaluate_derivative_zero_orders() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [0.5, 1.2, 3.4, 2.5, 1.5],
+        [1.5, 2.2, 4.4, 3.5, 0.5],
+        [2.5, 3.2, 5.4, 4.5, 2.5],
+        [0.5, 1.7, 2.4, 1.5, 3.0],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(0.8)
+        .make()
+        .unwrap();
+
+    let yi = s.evaluate(&x).unwrap();
+    let dyi = s.evaluate_derivative(&x, &[0, 0]).unwrap();
+
+    assert_abs_diff_eq!(dyi, yi, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_derivative_spline() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [0.5, 1.2, 3.4, 2.5, 1.5],
+        [1.5, 2.2, 4.4, 3.5, 0.5],
+        [2.5, 3.2, 5.4, 4.5, 2.5],
+        [0.5, 1.7, 2.4, 1.5, 3.0],
+    ];
+
+    let xi0 = array![1., 1.3, 2.5, 3.1, 4.];
+    let xi1 = array![1., 1.8, 2.2, 4.6, 5.];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(0.8)
+        .make()
+        .unwrap();
+
+    let spline = s.spline().unwrap();
+
+    for orders in &[[1, 0], [0, 1], [1, 1], [2, 1], [3, 3]] {
+        let ds = spline.derivative(orders).unwrap();
+
+        assert_eq!(ds.order(), &vec![4 - orders[0], 4 - orders[1]]);
+        assert_eq!(ds.smooth(), spline.smooth());
+
+        let expected = s.evaluate_derivative(&xi, orders).unwrap();
+
+        assert_abs_diff_eq!(ds.evaluate(&xi), expected, epsilon = 1e-10);
+        assert_abs_diff_eq!(spline.evaluate_derivative(&xi, orders).unwrap(), expected, epsilon = 1e-10);
+    }
+}
+
+
+#[test]
+fn test_evaluate_points_derivative() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [0.5, 1.2, 3.4, 2.5, 1.5],
+        [1.5, 2.2, 4.4, 3.5, 0.5],
+        [2.5, 3.2, 5.4, 4.5, 2.5],
+        [0.5, 1.7, 2.4, 1.5, 3.0],
+    ];
+
+    let xi0 = array![0.0, 1.0, 1.7, 2.5, 4.0, 5.2];
+    let xi1 = array![0.5, 1.0, 2.2, 3.0, 4.8, 6.0];
+    let points = mesh_points(&xi0, &xi1);
+
+    for &extrapolation in &[Extrapolation::Polynomial, Extrapolation::Linear, Extrapolation::Constant] {
+        let s = GridCubicSmoothingSpline::new(&x, &y)
+            .with_smooth_fill(0.8)
+            .with_extrapolation(extrapolation)
+            .make()
+            .unwrap();
+
+        for orders in &[[1, 0], [0, 1], [1, 1], [2, 0]] {
+            let values = s.evaluate_points_derivative(&points, orders).unwrap();
+            let values_mesh = s.evaluate_derivative(&[xi0.view(), xi1.view()], orders).unwrap();
+
+            assert_abs_diff_eq!(values, values_mesh.iter().cloned().collect::<Array1<f64>>(), epsilon = 1e-10);
+        }
+    }
+}
+
+
+#[test]
+fn test_evaluate_points_gradient() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+    let y = bilinear_data(&x0, &x1);
+
+    let points = array![
+        [1.0, 1.0],
+        [1.5, 2.5],
+        [3.2, 4.1],
+        [4.0, 5.0],
+    ];
+
+    let gradient = GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap()
+        .evaluate_points_gradient(&points)
+        .unwrap();
+
+    let expected = array![
+        [3.0, 4.0],
+        [4.5, 4.5],
+        [6.1, 6.2],
+        [7.0, 7.0],
+    ];
+
+    assert_abs_diff_eq!(gradient, expected, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_evaluate_derivative_extrapolation_linear() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+    let y = bilinear_data(&x0, &x1);
+
+    let xi0 = array![0., 5.];
+    let xi1 = array![3.];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Linear)
+        .make()
+        .unwrap();
+
+    let d0 = s.evaluate_derivative(&xi, &[1, 0]).unwrap();
+    let d00 = s.evaluate_derivative(&xi, &[2, 0]).unwrap();
+
+    assert_abs_diff_eq!(d0, array![[5.], [5.]], epsilon = 1e-10);
+    assert_abs_diff_eq!(d00, array![[0.], [0.]], epsilon = 1e-10);
+}
+
+
+#[test]
+#[should_panic(expected = "The number of derivative `orders` (1) is not equal to the number of dimensions (2)")]
+fn test_evaluate_derivative_orders_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap()
+        .evaluate_derivative(&x, &[1])
+        .unwrap();
+}
+
+
+#[test]
+fn test_spline_derivative_errors() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let spline = s.spline().unwrap();
+    let points = array![[1.5, 2.5]];
+
+    assert!(spline.derivative(&[1]).is_err());
+    assert!(spline.derivative(&[1, 0, 0]).is_err());
+    assert!(spline.evaluate_derivative(&x, &[1]).is_err());
+    assert!(spline.evaluate_derivative(&x[..1], &[1, 0]).is_err());
+    assert!(spline.evaluate_points_derivative(points.view(), &[0, 1, 0]).is_err());
+    assert!(spline.evaluate_points_derivative(array![[1.5, 2.5, 1.0]].view(), &[1, 0]).is_err());
+
+    assert_abs_diff_eq!(spline.evaluate_points_derivative(points.view(), &[1, 0]).unwrap(),
+                        s.evaluate_points_derivative(&points, &[1, 0]).unwrap());
+}
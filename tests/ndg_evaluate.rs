@@ -1,7 +1,11 @@
-use ndarray::array;
+mod common;
+
+use ndarray::{array, Array1, Array2, Array3};
 use approx::assert_abs_diff_eq;
 
-use csaps::GridCubicSmoothingSpline;
+use csaps::{GridCubicSmoothingSpline, NdGridSpline, NdGridWorkspace, Extrapolation};
+
+use common::mesh_points;
 
 
 #[test]
@@ -88,3 +92,356 @@ fn test_make_surface_2() {
 
     assert_abs_diff_eq!(yi, yi_expected);
 }
+
+
+#[test]
+fn test_evaluate_surface_extrapolation_constant() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let xi0 = array![0., 2., 5.];
+    let xi1 = array![0., 2.5, 6.];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let yi_expected = array![
+        [1., 2.5, 4.],
+        [5., 6.5, 8.],
+        [9., 10.5, 12.],
+    ];
+
+    let yi = GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Constant)
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi, yi_expected);
+}
+
+
+#[test]
+fn test_evaluate_surface_extrapolation_nan() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let xi0 = array![2.0f64, 5.0];
+    let xi1 = array![2.5];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let yi = GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Nan)
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi[[0, 0]], 6.5);
+    assert!(yi[[1, 0]].is_nan());
+}
+
+
+#[test]
+#[should_panic(expected = "`xi` value 0.0 is out of the breaks range [1.0, 4.0]")]
+fn test_evaluate_surface_extrapolation_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let xi0 = array![2.];
+    let xi1 = array![0.];
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Error)
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+}
+
+
+#[test]
+fn test_evaluate_owned_surface() {
+    fn make_spline() -> NdGridSpline<f64, ndarray::Ix2> {
+        let x0 = array![1., 2., 3.];
+        let x1 = array![1., 2., 3., 4.];
+        let x = vec![x0.view(), x1.view()];
+
+        let y = array![
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 10., 11., 12.],
+        ];
+
+        GridCubicSmoothingSpline::new(&x, &y)
+            .with_smooth_fill(1.0)
+            .make()
+            .unwrap()
+            .into_spline()
+            .unwrap()
+    }
+
+    let spline = make_spline();
+    let cached = spline.clone();
+
+    let handle = std::thread::spawn(move || {
+        let x0 = array![1., 3.];
+        let x1 = array![1., 4.];
+        spline.evaluate(&[x0.view(), x1.view()])
+    });
+
+    let yi = handle.join().unwrap();
+
+    assert_abs_diff_eq!(yi, array![[1., 4.], [9., 12.]], epsilon = 1e-10);
+    assert_eq!(cached.breaks()[1], array![1., 2., 3., 4.]);
+}
+
+
+#[test]
+fn test_evaluate_points_surface() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [0.5, 1.2, 3.4, 2.5, 1.5],
+        [1.5, 2.2, 4.4, 3.5, 0.5],
+        [2.5, 3.2, 5.4, 4.5, 2.5],
+        [0.5, 1.7, 2.4, 1.5, 3.0],
+    ];
+
+    let xi0 = array![0.0, 1.0, 1.7, 2.5, 4.0, 5.2];
+    let xi1 = array![0.5, 1.0, 2.2, 3.0, 4.8, 6.0];
+    let points = mesh_points(&xi0, &xi1);
+
+    for &extrapolation in &[Extrapolation::Polynomial, Extrapolation::Linear, Extrapolation::Constant] {
+        let s = GridCubicSmoothingSpline::new(&x, &y)
+            .with_smooth_fill(0.8)
+            .with_extrapolation(extrapolation)
+            .make()
+            .unwrap();
+
+        let values = s.evaluate_points(&points).unwrap();
+        let values_mesh = s.evaluate(&[xi0.view(), xi1.view()]).unwrap();
+
+        assert_abs_diff_eq!(values, values_mesh.iter().cloned().collect::<ndarray::Array1<f64>>(), epsilon = 1e-10);
+    }
+}
+
+
+#[test]
+fn test_evaluate_points_volume() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x2 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view(), x2.view()];
+
+    let y = ndarray::Array3::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        ((i + 1) as f64).sin() + ((j * k) as f64).cos()
+    });
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let points = array![[1.5, 2.5, 3.5], [1.0, 4.0, 5.0], [2.2, 1.1, 4.4]];
+    let values = s.evaluate_points(&points).unwrap();
+
+    for (point, &value) in points.outer_iter().zip(values.iter()) {
+        let xi: Vec<_> = point.iter().map(|&v| array![v]).collect();
+        let xi_views: Vec<_> = xi.iter().map(|v| v.view()).collect();
+        let value_mesh = s.spline().unwrap().evaluate(&xi_views);
+
+        assert_abs_diff_eq!(value, value_mesh[[0, 0, 0]], epsilon = 1e-10);
+    }
+}
+
+
+#[test]
+fn test_evaluate_points_nan() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let points = array![[1.5, 2.5], [0.5, 2.5], [f64::NAN, 2.0], [2.0, 4.5]];
+
+    let values = GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Nan)
+        .make()
+        .unwrap()
+        .evaluate_points(&points)
+        .unwrap();
+
+    assert!(values[0].is_finite());
+    assert!(values[1].is_nan());
+    assert!(values[2].is_nan());
+    assert!(values[3].is_nan());
+}
+
+
+#[test]
+#[should_panic(expected = "`xi` value 4.5 is out of the breaks range [1.0, 4.0]")]
+fn test_evaluate_points_extrapolation_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let points = array![[1.5, 2.5], [2.0, 4.5]];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Error)
+        .make()
+        .unwrap()
+        .evaluate_points(&points)
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The number of `points` columns (3) is not equal to the number of dimensions (2)")]
+fn test_evaluate_points_dimensions_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let points = array![[1.5, 2.5, 1.0]];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap()
+        .evaluate_points(&points)
+        .unwrap();
+}
+
+
+#[test]
+fn test_evaluate_into() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 4., 3.],
+        [5., 7., 6., 8.],
+        [9., 10., 12., 11.],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(0.8)
+        .with_periodic(&[false, true])
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+
+    let xi0 = Array1::linspace(0., 4., 9);
+    let xi1 = Array1::linspace(0., 5., 11);
+    let xi = vec![xi0.view(), xi1.view()];
+
+    let mut yi = Array2::<f64>::zeros((9, 11));
+
+    spline.evaluate_into(&xi, &mut yi).unwrap();
+    assert_eq!(yi, spline.evaluate(&xi));
+
+    assert!(spline.evaluate_into(&xi, &mut Array2::<f64>::zeros((11, 9))).is_err());
+    assert!(spline.evaluate_into(&xi[..1], &mut Array2::<f64>::zeros((9, 11))).is_err());
+}
+
+
+#[test]
+fn test_evaluate_into_with_workspace() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x2 = array![1., 2.];
+    let x = vec![x0.view(), x1.view(), x2.view()];
+
+    let y = array![
+        [[1., 2.], [4., 3.], [2., 5.], [3., 1.]],
+        [[5., 7.], [6., 8.], [7., 5.], [8., 6.]],
+        [[9., 10.], [12., 11.], [10., 9.], [11., 12.]],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(0.8)
+        .with_periodic(&[false, true, false])
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+
+    let mut workspace = NdGridWorkspace::new();
+    let mut yi = Array3::<f64>::zeros((9, 11, 5));
+
+    for offset in [0., 0.25] {
+        let xi0 = Array1::linspace(0., 4., 9) + offset;
+        let xi1 = Array1::linspace(0., 5., 11) + offset;
+        let xi2 = Array1::linspace(1., 2., 5) + offset;
+        let xi = vec![xi0.view(), xi1.view(), xi2.view()];
+
+        spline.evaluate_into_with(&xi, &mut workspace, &mut yi).unwrap();
+        assert_eq!(yi, spline.evaluate(&xi));
+    }
+
+    let xi0 = Array1::linspace(0., 4., 9);
+    let xi1 = Array1::linspace(0., 5., 11);
+    let xi2 = Array1::linspace(1., 2., 5);
+    let xi = vec![xi0.view(), xi1.view(), xi2.view()];
+
+    assert!(spline.evaluate_into_with(&xi, &mut workspace, &mut Array3::<f64>::zeros((9, 5, 11))).is_err());
+    assert!(spline.evaluate_into_with(&xi[..2], &mut workspace, &mut Array3::<f64>::zeros((9, 11, 5))).is_err());
+}
+
+
+#[test]
+fn test_spline_evaluate_points_dimensions_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let spline = s.spline().unwrap();
+
+    assert!(spline.evaluate_points(array![[1.5, 2.5, 1.0]].view()).is_err());
+    assert_abs_diff_eq!(spline.evaluate_points(array![[1.5, 2.5]].view()).unwrap(),
+                        s.evaluate_points(&array![[1.5, 2.5]]).unwrap());
+}
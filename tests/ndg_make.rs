@@ -1,7 +1,7 @@
 use ndarray::{array, Array1};
 use approx::assert_abs_diff_eq;
 
-use csaps::GridCubicSmoothingSpline;
+use csaps::{GridCubicSmoothingSpline, CrossValidation};
 
 
 #[test]
@@ -36,3 +36,141 @@ fn test_make_surface() {
     assert_abs_diff_eq!(smooth, array![0.8999999999999999, 0.8999999999999999]);
     assert_abs_diff_eq!(s.spline().unwrap().coeffs(), coeffs_expected)
 }
+
+
+#[test]
+fn test_make_surface_cross_validation() {
+    let x0 = array![1., 2., 3., 4., 5.];
+    let x1 = array![1., 2., 3., 4., 5., 6.];
+
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1.2, 2.1, 2.9, 4.2, 4.8, 6.1],
+        [2.1, 2.9, 4.2, 4.8, 6.1, 7.2],
+        [2.9, 4.2, 4.8, 6.1, 7.2, 7.9],
+        [4.2, 4.8, 6.1, 7.2, 7.9, 9.1],
+        [4.8, 6.1, 7.2, 7.9, 9.1, 9.8],
+    ];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(&[Some(CrossValidation::Generalized), None])
+        .make().unwrap();
+
+    let smooth = s.smooth();
+    let cv_scores = s.cv_scores();
+
+    assert!(smooth[0].unwrap() > 0. && smooth[0].unwrap() < 1.);
+    assert!(cv_scores[0].unwrap() > 0.);
+    assert_eq!(cv_scores[1], None);
+}
+
+
+#[test]
+fn test_make_surface_cross_validation_remake() {
+    let x0 = array![1., 2., 3., 4., 5.];
+    let x1 = array![1., 2., 3., 4., 5., 6.];
+
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1.2, 2.1, 2.9, 4.2, 4.8, 6.1],
+        [2.1, 2.9, 4.2, 4.8, 6.1, 7.2],
+        [2.9, 4.2, 4.8, 6.1, 7.2, 7.9],
+        [4.2, 4.8, 6.1, 7.2, 7.9, 9.1],
+        [4.8, 6.1, 7.2, 7.9, 9.1, 9.8],
+    ];
+
+    let w0 = array![1., 2., 1., 3., 1.];
+    let cv = [Some(CrossValidation::Generalized), None];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(&cv)
+        .make().unwrap();
+
+    let smooth = s.smooth()[0];
+
+    let s = s.with_weights(&[Some(w0.view()), None]);
+
+    assert_eq!(s.smooth(), &vec![None, None]);
+    assert_eq!(s.cv_scores(), &vec![None, None]);
+
+    let s = s.make().unwrap();
+
+    let expected = GridCubicSmoothingSpline::new(&x, &y)
+        .with_weights(&[Some(w0.view()), None])
+        .with_cross_validation(&cv)
+        .make().unwrap();
+
+    assert_ne!(s.smooth()[0], smooth);
+    assert_eq!(s.smooth(), expected.smooth());
+    assert_eq!(s.cv_scores(), expected.cv_scores());
+    assert!(s.cv_scores()[0].unwrap() > 0.);
+}
+
+
+#[test]
+#[should_panic(expected = "The number of `cross_validation` values (1) is not equal to the number of dimensions (2)")]
+fn test_make_surface_cross_validation_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(&[Some(CrossValidation::Generalized)])
+        .make().unwrap();
+}
+
+
+#[test]
+fn test_make_surface_invalid_weights_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+    ];
+
+    let w0 = array![1., 1., 1.];
+
+    let invalid = [array![1., 0., 1., 1.], array![1., 1., -2., 1.], array![1., 1., f64::NAN, 1.]];
+
+    for w1 in &invalid {
+        let result = GridCubicSmoothingSpline::new(&x, &y)
+            .with_weights(&[Some(w0.view()), Some(w1.view())])
+            .make();
+
+        assert!(result.is_err());
+    }
+}
+
+
+#[test]
+fn test_make_volume_interpolation() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x2 = array![1., 2., 3., 4., 5.];
+    let x = vec![x0.view(), x1.view(), x2.view()];
+
+    let y = ndarray::Array3::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        ((i + 1) as f64).sin() + ((j * k) as f64).cos()
+    });
+
+    let yi = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(1.0)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    assert_abs_diff_eq!(yi, y, epsilon = 1e-10);
+}
@@ -0,0 +1,84 @@
+use std::f64::consts::PI;
+
+use ndarray::{array, Array1, Array2};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, GridCubicSmoothingSpline};
+
+
+#[test]
+fn test_surface_periodic_axis() {
+    let x0 = array![0., 1., 2., 3.];
+    let x1 = Array1::linspace(0., 2. * PI, 13);
+    let x = vec![x0.view(), x1.view()];
+
+    let y = Array2::from_shape_fn((4, 13), |(i, j)| (i as f64).powi(2) + x1[j].sin());
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth(&[Some(0.9), Some(0.8)])
+        .with_periodic(&[false, true])
+        .make().unwrap();
+
+    assert_eq!(s.periodic(), &vec![false, true]);
+    assert_eq!(s.spline().unwrap().periodic(), &vec![false, true]);
+
+    // The rows of the grid spline along the periodic axis are periodic univariate splines
+    let xi0 = array![2.];
+    let xi1 = Array1::linspace(0.2, 6.2, 7);
+    let yi = s.evaluate(&[xi0.view(), xi1.view()]).unwrap();
+
+    let xi1_shifted = &xi1 + 2. * PI;
+    let yi_shifted = s.evaluate(&[xi0.view(), xi1_shifted.view()]).unwrap();
+
+    assert_abs_diff_eq!(yi, yi_shifted, epsilon = 1e-10);
+
+    let y_row = s.evaluate(&[xi0.view(), x1.view()]).unwrap();
+    let yi_row = CubicSmoothingSpline::new(&x1, y_row.row(0))
+        .with_smooth(1.0)
+        .with_periodic(true)
+        .make().unwrap()
+        .evaluate(&xi1).unwrap();
+
+    assert_abs_diff_eq!(yi.row(0), yi_row, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_surface_periodic_points() {
+    let x0 = Array1::linspace(0., 1., 5);
+    let x1 = Array1::linspace(0., 1., 6);
+    let x = vec![x0.view(), x1.view()];
+
+    let y = Array2::from_shape_fn((5, 6), |(i, j)| {
+        (2. * PI * x0[i]).cos() * (2. * PI * x1[j]).sin()
+    });
+
+    let points = array![[0.3, 0.4], [1.3, -0.6], [-2.7, 2.4]];
+
+    let s = GridCubicSmoothingSpline::new(&x, &y)
+        .with_periodic(&[true, true])
+        .make().unwrap();
+
+    let values = s.evaluate_points(&points).unwrap();
+    assert_abs_diff_eq!(values, Array1::from_elem(3, values[0]), epsilon = 1e-10);
+
+    let gradient = s.evaluate_points_gradient(&points).unwrap();
+    assert_abs_diff_eq!(gradient.row(1), gradient.row(0), epsilon = 1e-10);
+    assert_abs_diff_eq!(gradient.row(2), gradient.row(0), epsilon = 1e-10);
+}
+
+
+#[test]
+#[should_panic(expected = "The number of `periodic` flags (1) is not equal to the number of dimensions (2)")]
+fn test_periodic_flags_error() {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = Array2::<f64>::zeros((3, 3));
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .with_periodic(&[true])
+        .make()
+        .unwrap();
+}
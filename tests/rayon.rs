@@ -0,0 +1,102 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use ndarray::{s, Array1, Array2, Axis};
+
+use csaps::{CubicSmoothingSpline, CubicSmoother, GridCubicSmoothingSpline};
+
+use common::data_series;
+
+
+#[test]
+fn test_parallel_evaluate_is_deterministic() {
+    let x = Array1::linspace(0., 10., 50);
+    let y = data_series(&x, 3);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    let xi = Array1::linspace(-1., 11., 100_001);
+    let yi = s.evaluate(&xi).unwrap();
+
+    // The small chunks are evaluated serially
+    for (k, xi_chunk) in xi.axis_chunks_iter(Axis(0), 500).enumerate() {
+        let yi_chunk = s.spline().unwrap().evaluate(xi_chunk);
+        assert_eq!(yi.slice(s![.., k * 500..k * 500 + xi_chunk.len()]), yi_chunk);
+    }
+}
+
+
+#[test]
+fn test_parallel_fit_is_deterministic() {
+    let x = Array1::linspace(0., 10., 200);
+    let y = data_series(&x, 64);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    let coeffs = smoother.fit(&y).unwrap().coeffs().to_owned();
+
+    for (i, row) in y.outer_iter().enumerate() {
+        let row_coeffs = smoother.fit(row.insert_axis(Axis(0))).unwrap().coeffs().to_owned();
+        assert_eq!(coeffs.row(i), row_coeffs.row(0));
+    }
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    for (i, row) in y.outer_iter().enumerate() {
+        let s_row = CubicSmoothingSpline::new(&x, row)
+            .with_smooth(0.8)
+            .make().unwrap();
+
+        assert_eq!(s.spline().unwrap().coeffs().row(i), s_row.spline().unwrap().coeffs().row(0));
+    }
+}
+
+
+#[test]
+fn test_parallel_periodic_fit_is_deterministic() {
+    let x = Array1::linspace(0., std::f64::consts::TAU, 101);
+    let mut y = data_series(&x, 16);
+    let first = y.column(0).to_owned();
+    y.column_mut(100).assign(&first);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make().unwrap();
+
+    for (i, row) in y.outer_iter().enumerate() {
+        let s_row = CubicSmoothingSpline::new(&x, row)
+            .with_periodic(true)
+            .make().unwrap();
+
+        assert_eq!(s.spline().unwrap().coeffs().row(i), s_row.spline().unwrap().coeffs().row(0));
+    }
+}
+
+
+#[test]
+fn test_parallel_grid_is_deterministic() {
+    let x0: Array1<f64> = Array1::linspace(0., 1., 40);
+    let x1: Array1<f64> = Array1::linspace(0., 2., 60);
+    let y = Array2::from_shape_fn((40, 60), |(i, j)| (x0[i] * 3.).sin() * (x1[j] * 2.).cos() + 0.01 * ((i * j) % 7) as f64);
+
+    let s = GridCubicSmoothingSpline::new(&[x0.view(), x1.view()], &y)
+        .with_smooth_fill(0.9)
+        .make().unwrap();
+
+    let xi0 = Array1::linspace(0., 1., 400);
+    let xi1 = Array1::linspace(0., 2., 600);
+    let yi = s.evaluate(&[xi0.view(), xi1.view()]).unwrap();
+
+    for k in 0..4 {
+        let xi0_part = xi0.slice(s![k * 100..(k + 1) * 100]);
+        let yi_part = s.evaluate(&[xi0_part, xi1.view()]).unwrap();
+
+        assert_eq!(yi.slice(s![k * 100..(k + 1) * 100, ..]), yi_part);
+    }
+}
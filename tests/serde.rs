@@ -0,0 +1,274 @@
+#![cfg(feature = "serde")]
+
+use ndarray::{array, Ix2, Ix3};
+use approx::assert_abs_diff_eq;
+
+use std::convert::TryFrom;
+
+use csaps::{
+    CsapsError, CubicSmoothingSpline, GridCubicSmoothingSpline,
+    NdSpline, NdGridSpline, NdSplineData, NdGridSplineData,
+};
+
+
+fn make_spline() -> NdSpline<f64> {
+    let x = array![1., 2., 3., 4., 5.];
+    let y = array![[1., 3., 2., 4., 3.], [2., 1., 4., 3., 5.]];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .make()
+        .unwrap()
+        .into_spline()
+        .unwrap()
+}
+
+
+fn make_surface() -> NdGridSpline<f64, Ix2> {
+    let x0 = array![1., 2., 3.];
+    let x1 = array![1., 2., 3., 4.];
+    let x = vec![x0.view(), x1.view()];
+
+    let y = array![
+        [1., 3., 2., 4.],
+        [5., 4., 7., 6.],
+        [9., 11., 10., 12.],
+    ];
+
+    GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth(&[Some(0.7), None])
+        .make()
+        .unwrap()
+        .into_spline()
+        .unwrap()
+}
+
+
+#[test]
+fn test_spline_roundtrip() {
+    let spline = make_spline();
+    let json = serde_json::to_string(&spline).unwrap();
+    let loaded: NdSpline<f64> = serde_json::from_str(&json).unwrap();
+
+    let xi = array![0.5, 1.5, 2.7, 4.9, 6.0];
+
+    assert_eq!(loaded.smooth(), Some(0.8));
+    assert_eq!(loaded.order(), spline.order());
+    assert_eq!(loaded.pieces(), spline.pieces());
+    assert_eq!(loaded.breaks(), spline.breaks());
+    assert_abs_diff_eq!(loaded.evaluate(xi.view()), spline.evaluate(xi.view()), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_surface_roundtrip() {
+    let spline = make_surface();
+    let json = serde_json::to_string(&spline).unwrap();
+    let loaded: NdGridSpline<f64, Ix2> = serde_json::from_str(&json).unwrap();
+
+    let x0 = array![1., 1.5, 3.];
+    let x1 = array![1., 2.5, 4.];
+    let xi = vec![x0.view(), x1.view()];
+
+    assert_eq!(loaded.smooth(), spline.smooth());
+    assert_eq!(loaded.smooth()[0], Some(0.7));
+    assert_abs_diff_eq!(loaded.evaluate(&xi), spline.evaluate(&xi), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_schema_version() {
+    let json = serde_json::to_value(make_spline()).unwrap();
+    assert_eq!(json["version"], 2);
+}
+
+
+#[test]
+fn test_spline_invalid_version() {
+    let mut json = serde_json::to_value(make_spline()).unwrap();
+    json["version"] = serde_json::json!(100);
+
+    let err = serde_json::from_value::<NdSpline<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("Unsupported schema version 100, expected 1 to 2"));
+}
+
+
+#[test]
+fn test_spline_invalid_coeffs() {
+    let mut json = serde_json::to_value(make_spline()).unwrap();
+    json["coeffs"].as_array_mut().unwrap().pop();
+
+    let err = serde_json::from_value::<NdSpline<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("Invalid spline data: The number of coefficients (31) does not match the shape [2, 16]"));
+}
+
+
+#[test]
+fn test_spline_invalid_breaks() {
+    let mut json = serde_json::to_value(make_spline()).unwrap();
+    json["breaks"] = serde_json::json!([1., 3., 2., 4., 5.]);
+
+    let err = serde_json::from_value::<NdSpline<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("Breaks must be finite and strictly increasing"));
+
+    let mut json = serde_json::to_value(make_spline()).unwrap();
+    json["breaks"] = serde_json::json!([1., 2., 3.]);
+
+    let err = serde_json::from_value::<NdSpline<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("The number of breaks (3) does not match the number of pieces (4)"));
+}
+
+
+#[test]
+fn test_spline_invalid_order() {
+    let mut json = serde_json::to_value(make_spline()).unwrap();
+    json["order"] = serde_json::json!(usize::MAX);
+
+    let err = serde_json::from_value::<NdSpline<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("Invalid spline data"));
+}
+
+
+#[test]
+fn test_surface_invalid_dimensionality() {
+    let json = serde_json::to_value(make_surface()).unwrap();
+
+    let err = serde_json::from_value::<NdGridSpline<f64, Ix3>>(json).unwrap_err();
+    assert!(err.to_string().contains("The grid dimensionality (2) is not valid for the spline type"));
+}
+
+
+#[test]
+fn test_surface_invalid_shape() {
+    let mut json = serde_json::to_value(make_surface()).unwrap();
+    json["coeffs_shape"] = serde_json::json!([12, 8]);
+
+    let err = serde_json::from_value::<NdGridSpline<f64, Ix2>>(json).unwrap_err();
+    assert!(err.to_string().contains("The coefficients shape[0] (12) does not match the order (4) and the pieces (2)"));
+}
+
+
+#[test]
+fn test_surface_invalid_smooth() {
+    let mut json = serde_json::to_value(make_surface()).unwrap();
+    json["smooth"] = serde_json::json!([1.5, null]);
+
+    let err = serde_json::from_value::<NdGridSpline<f64, Ix2>>(json).unwrap_err();
+    assert!(err.to_string().contains("`smooth` value must be in range 0..1, given 1.5"));
+}
+
+
+#[test]
+fn test_periodic_roundtrip() {
+    let x = array![0., 1., 2., 3., 4.];
+    let y = array![1., 3., 2., 4., 1.];
+
+    let spline = CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make()
+        .unwrap()
+        .into_spline()
+        .unwrap();
+
+    let json = serde_json::to_string(&spline).unwrap();
+    let loaded: NdSpline<f64> = serde_json::from_str(&json).unwrap();
+
+    let xi = array![-1.5, 0.5, 5.5];
+
+    assert!(loaded.periodic());
+    assert_abs_diff_eq!(loaded.evaluate(xi.view()), spline.evaluate(xi.view()), epsilon = 1e-12);
+
+    // The periodic flag is required since the schema version 2
+    let mut value = serde_json::to_value(&spline).unwrap();
+    value.as_object_mut().unwrap().remove("periodic");
+
+    let err = serde_json::from_value::<NdSpline<f64>>(value).unwrap_err();
+    assert!(err.to_string().contains("The periodic flag is missing"));
+}
+
+
+#[test]
+fn test_load_schema_version_1() {
+    let json = r#"{
+        "version": 1,
+        "ndim": 1,
+        "order": 2,
+        "pieces": 2,
+        "breaks": [0.0, 1.0, 2.0],
+        "coeffs": [1.0, -1.0, 0.0, 1.0],
+        "smooth": null
+    }"#;
+
+    let loaded: NdSpline<f64> = serde_json::from_str(json).unwrap();
+
+    assert!(!loaded.periodic());
+    assert_abs_diff_eq!(loaded.evaluate(array![0.5, 1.5, 3.0].view()), array![[0.5, 0.5, -1.0]], epsilon = 1e-12);
+
+    let json = r#"{
+        "version": 1,
+        "ndim": 2,
+        "order": [2, 2],
+        "pieces": [1, 1],
+        "breaks": [[0.0, 1.0], [0.0, 1.0]],
+        "coeffs_shape": [2, 2],
+        "coeffs": [1.0, 0.0, 0.0, 1.0],
+        "smooth": [null, 0.5]
+    }"#;
+
+    let loaded: NdGridSpline<f64, Ix2> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(loaded.periodic(), &vec![false, false]);
+    assert_eq!(loaded.smooth(), &vec![None, Some(0.5)]);
+}
+
+
+#[test]
+#[should_panic(expected = "The size of the periodic flags (1) is not equal to the grid dimensionality (2)")]
+fn test_surface_invalid_periodic() {
+    let mut value = serde_json::to_value(make_surface()).unwrap();
+    value["periodic"] = serde_json::json!([true]);
+
+    serde_json::from_value::<NdGridSpline<f64, Ix2>>(value).unwrap();
+}
+
+
+#[test]
+fn test_spline_data_typed_error() {
+    let spline = make_spline();
+
+    let data = NdSplineData::from(&spline);
+    let loaded = NdSpline::try_from(data).unwrap();
+    assert_eq!(loaded.breaks(), spline.breaks());
+
+    let mut json = serde_json::to_value(&spline).unwrap();
+    json["breaks"] = serde_json::json!([1., 3., 2., 4., 5.]);
+
+    let data: NdSplineData<f64> = serde_json::from_value(json).unwrap();
+
+    match NdSpline::try_from(data) {
+        Err(CsapsError::InvalidSplineData(message)) => {
+            assert_eq!(message, "Breaks must be finite and strictly increasing")
+        },
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+
+#[test]
+fn test_surface_data_typed_error() {
+    let spline = make_surface();
+
+    let data = NdGridSplineData::from(&spline);
+    let loaded = NdGridSpline::<f64, Ix2>::try_from(data).unwrap();
+    assert_eq!(loaded.smooth(), spline.smooth());
+
+    let json = serde_json::to_value(&spline).unwrap();
+    let data: NdGridSplineData<f64> = serde_json::from_value(json).unwrap();
+
+    match NdGridSpline::<f64, Ix3>::try_from(data) {
+        Err(CsapsError::InvalidSplineData(message)) => {
+            assert_eq!(message, "The grid dimensionality (2) is not valid for the spline type")
+        },
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use ndarray::{array, stack, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, MissingValues};
+
+use common::noisy_sine;
+
+
+#[test]
+fn test_bands_straight_line() {
+    let x: Array1<f64> = array![0., 1., 2., 3., 4., 5.];
+    let y = array![0.1, 0.9, 2.2, 2.8, 4.1, 5.2];
+    let xi = array![-1., 0., 2.5, 5., 6.];
+
+    let bands = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.0)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    // The bands of the least-squares straight line fit
+    let n = x.len() as f64;
+    let x_mean = x.mean().unwrap();
+    let sxx = x.mapv(|v| (v - x_mean).powi(2)).sum();
+    let sxy = (&x - x_mean).dot(&y);
+    let slope = sxy / sxx;
+    let intercept = y.mean().unwrap() - slope * x_mean;
+    let rss = (&y - &x.mapv(|v| intercept + slope * v)).mapv(|r| r * r).sum();
+    let sigma2 = rss / (n - 2.);
+
+    let leverage = xi.mapv(|v| 1. / n + (v - x_mean).powi(2) / sxx);
+    let std_errors = leverage.mapv(|h| (sigma2 * h).sqrt());
+    let prediction_std_errors = leverage.mapv(|h| (sigma2 * (h + 1.)).sqrt());
+
+    assert_abs_diff_eq!(bands.dof()[()], 2., epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.residual_variance()[()], sigma2, epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.values(), xi.mapv(|v| intercept + slope * v), epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.std_errors(), std_errors, epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.prediction_std_errors(), prediction_std_errors, epsilon = 1e-8);
+
+    let z = 1.959963984540054;
+    let (lower, upper) = bands.confidence();
+    assert_abs_diff_eq!(lower, &bands.values() - &(&std_errors * z), epsilon = 1e-7);
+    assert_abs_diff_eq!(upper, &bands.values() + &(&std_errors * z), epsilon = 1e-7);
+
+    let (lower, upper) = bands.prediction();
+    assert_abs_diff_eq!(lower, &bands.values() - &(&prediction_std_errors * z), epsilon = 1e-7);
+    assert_abs_diff_eq!(upper, &bands.values() + &(&prediction_std_errors * z), epsilon = 1e-7);
+}
+
+
+#[test]
+fn test_bands_dof() {
+    let (x, y) = noisy_sine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(8.0)
+        .make().unwrap();
+
+    let bands = s.bands(&x, 0.9).unwrap();
+
+    assert_eq!(bands.level(), 0.9);
+    assert_abs_diff_eq!(bands.dof()[()], 8.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(bands.values(), s.evaluate(&x).unwrap(), epsilon = 1e-12);
+
+    // The noise standard deviation is about 0.05
+    let sigma = bands.residual_variance()[()].sqrt();
+    assert!(sigma > 0.03 && sigma < 0.08);
+
+    assert!(bands.std_errors().iter().all(|&v| v > 0. && v < sigma));
+    assert!(bands.prediction_std_errors().iter().all(|&v| v > sigma));
+}
+
+
+#[test]
+fn test_bands_interpolant() {
+    let (x, y) = noisy_sine();
+
+    let bands = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .make().unwrap()
+        .bands(&x, 0.95).unwrap();
+
+    assert_abs_diff_eq!(bands.dof()[()], x.len() as f64, epsilon = 1e-8);
+    assert!(bands.residual_variance()[()].is_nan());
+}
+
+
+#[test]
+fn test_bands_multivariate() {
+    let (x, y) = noisy_sine();
+    let y2 = stack![Axis(1), y, &y * 2.];
+    let xi = Array1::linspace(0., 6., 13);
+
+    let bands = CubicSmoothingSpline::new(&x, &y2)
+        .with_axis(Axis(0))
+        .with_smooth(0.9)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    assert_eq!(bands.values().shape(), &[13, 2]);
+    assert_eq!(bands.residual_variance().shape(), &[2]);
+
+    let std_errors = bands.std_errors();
+    assert_abs_diff_eq!(std_errors.column(1), &std_errors.column(0) * 2., epsilon = 1e-12);
+    assert_abs_diff_eq!(bands.residual_variance()[1], bands.residual_variance()[0] * 4., epsilon = 1e-12);
+    assert_abs_diff_eq!(bands.dof()[1], bands.dof()[0], epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_bands_missing_values() {
+    let (x, y) = noisy_sine();
+    let xi = Array1::linspace(0., 6., 13);
+    let gaps = [3, 4, 15];
+
+    let mut y_gaps = y.clone();
+    gaps.iter().for_each(|&i| y_gaps[i] = f64::NAN);
+
+    let x_valid: Array1<f64> = x.iter().enumerate().filter(|(i, _)| !gaps.contains(i)).map(|(_, &v)| v).collect();
+    let y_valid: Array1<f64> = y.iter().enumerate().filter(|(i, _)| !gaps.contains(i)).map(|(_, &v)| v).collect();
+
+    let bands = CubicSmoothingSpline::new(&x, &y_gaps)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    let bands_valid = CubicSmoothingSpline::new(&x_valid, &y_valid)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    assert_abs_diff_eq!(bands.dof(), bands_valid.dof(), epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.residual_variance(), bands_valid.residual_variance(), epsilon = 1e-10);
+    assert_abs_diff_eq!(bands.std_errors(), bands_valid.std_errors(), epsilon = 1e-8);
+
+    // The missing values per series
+    let y2 = stack![Axis(0), y_gaps, y];
+
+    let bands2 = CubicSmoothingSpline::new(&x, &y2)
+        .with_smooth(0.9)
+        .with_missing_values(MissingValues::PerSeries)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    let bands_full = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    assert_abs_diff_eq!(bands2.std_errors().row(0), bands.std_errors(), epsilon = 1e-8);
+    assert_abs_diff_eq!(bands2.std_errors().row(1), bands_full.std_errors(), epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_bands_many_data_sites() {
+    let x = Array1::<f64>::linspace(0., 20., 200);
+    let y = x.mapv(|v| v.sin() + 0.05 * (v * 37.).cos());
+    let xi = Array1::linspace(-1., 21., 57);
+    let gaps = [7, 64, 130, 131];
+
+    let mut y_gaps = y.clone();
+    gaps.iter().for_each(|&i| y_gaps[i] = f64::NAN);
+
+    let x_valid: Array1<f64> = x.iter().enumerate().filter(|(i, _)| !gaps.contains(i)).map(|(_, &v)| v).collect();
+    let y_valid: Array1<f64> = y.iter().enumerate().filter(|(i, _)| !gaps.contains(i)).map(|(_, &v)| v).collect();
+
+    // The smoother matrix columns are computed by the constrained system and by the factorized system
+    let bands = CubicSmoothingSpline::new(&x, &y_gaps)
+        .with_smooth(0.99)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    let bands_valid = CubicSmoothingSpline::new(&x_valid, &y_valid)
+        .with_smooth(0.99)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    assert_abs_diff_eq!(bands.dof(), bands_valid.dof(), epsilon = 1e-8);
+    assert_abs_diff_eq!(bands.residual_variance(), bands_valid.residual_variance(), epsilon = 1e-10);
+    assert_abs_diff_eq!(bands.std_errors(), bands_valid.std_errors(), epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_bands_sort_data_sites() {
+    let x = array![3., 1., 5., 2., 4., 6.];
+    let y = array![2.5, 1.2, 1.8, 3.4, 0.5, 1.1];
+    let xi = array![1.5, 3.5];
+
+    let bands = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .with_sort_data_sites(true)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    let bands_sorted = CubicSmoothingSpline::new(&array![1., 2., 3., 4., 5., 6.], &array![1.2, 3.4, 2.5, 0.5, 1.8, 1.1])
+        .with_smooth(0.8)
+        .make().unwrap()
+        .bands(&xi, 0.95).unwrap();
+
+    assert_abs_diff_eq!(bands.std_errors(), bands_sorted.std_errors(), epsilon = 1e-12);
+    assert_abs_diff_eq!(bands.residual_variance(), bands_sorted.residual_variance(), epsilon = 1e-12);
+}
+
+
+#[test]
+#[should_panic(expected = "The confidence level must be in range (0, 1), given 1.0")]
+fn test_bands_level_error() {
+    let (x, y) = noisy_sine();
+
+    CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap()
+        .bands(&x, 1.0)
+        .unwrap();
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use ndarray::{array, Array1, Axis, s, concatenate};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, CrossValidation, Extrapolation};
+
+use common::noisy_sine;
+
+
+#[test]
+fn test_gcv_smooth() {
+    let (x, y) = noisy_sine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make()
+        .unwrap();
+
+    let smooth = s.smooth().unwrap();
+    let score = s.cv_score().unwrap();
+
+    assert!(smooth > 0. && smooth < 1.);
+    assert!(score > 0. && score.is_finite());
+
+    // The GCV spline must be closer to the true function than the data
+    let xi = Array1::linspace(0., 6., 101);
+    let err = (s.evaluate(&xi).unwrap() - xi.mapv(f64::sin)).mapv(f64::abs);
+    let noise_err = (&y - &x.mapv(f64::sin)).mapv(f64::abs);
+
+    assert!(err.mean().unwrap() < noise_err.mean().unwrap());
+}
+
+
+#[test]
+fn test_loo_cv_score_brute_force() {
+    let (x, y) = noisy_sine();
+    let n = x.len();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::LeaveOneOut)
+        .make()
+        .unwrap();
+
+    let smooth = s.smooth().unwrap();
+
+    let score = (0..n).map(|i| {
+        let xs = concatenate![Axis(0), x.slice(s![..i]), x.slice(s![i + 1..])];
+        let ys = concatenate![Axis(0), y.slice(s![..i]), y.slice(s![i + 1..])];
+        let xi = array![x[i]];
+
+        let yi = CubicSmoothingSpline::new(&xs, &ys)
+            .with_smooth(smooth)
+            .with_extrapolation(Extrapolation::Linear)
+            .make()
+            .unwrap()
+            .evaluate(&xi)
+            .unwrap();
+
+        (y[i] - yi[0]).powi(2)
+    }).sum::<f64>() / n as f64;
+
+    assert_abs_diff_eq!(s.cv_score().unwrap(), score, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_cv_explicit_smooth_precedence() {
+    let (x, y) = noisy_sine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::Generalized)
+        .with_smooth(0.5)
+        .make()
+        .unwrap();
+
+    assert_eq!(s.smooth(), Some(0.5));
+    assert_eq!(s.cv_score(), None);
+}
+
+
+#[test]
+fn test_gcv_multivariate() {
+    let (x, y) = noisy_sine();
+    let y2 = concatenate![Axis(0), y.view().insert_axis(Axis(0)), y.view().insert_axis(Axis(0))];
+
+    let s1 = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make()
+        .unwrap();
+
+    let s2 = CubicSmoothingSpline::new(&x, &y2)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make()
+        .unwrap();
+
+    // The same data in both dimensions gives the same smoothing parameter and score
+    assert_abs_diff_eq!(s1.smooth().unwrap(), s2.smooth().unwrap(), epsilon = 1e-10);
+    assert_abs_diff_eq!(s1.cv_score().unwrap(), s2.cv_score().unwrap(), epsilon = 1e-10);
+}
@@ -0,0 +1,146 @@
+use ndarray::{array, Array1, Array2, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingCurve, Parameterization};
+
+
+fn circle_points(n: usize, radius: f64) -> Array2<f64> {
+    let t = Array1::linspace(0., std::f64::consts::PI, n);
+    Array2::from_shape_fn((n, 2), |(i, j)| if j == 0 { radius * t[i].cos() } else { radius * t[i].sin() })
+}
+
+
+#[test]
+fn test_curve_parameterization() {
+    let points = array![[0., 0.], [3., 4.], [3., 0.], [3., 1.]];
+
+    let parameter = |parameterization| {
+        CubicSmoothingCurve::new(&points)
+            .with_parameterization(parameterization)
+            .make().unwrap()
+            .parameter().unwrap()
+            .to_owned()
+    };
+
+    assert_abs_diff_eq!(parameter(Parameterization::Uniform), array![0., 1. / 3., 2. / 3., 1.], epsilon = 1e-15);
+    assert_abs_diff_eq!(parameter(Parameterization::ChordLength), array![0., 0.5, 0.9, 1.], epsilon = 1e-15);
+
+    let c = 5f64.sqrt() + 3.;
+    assert_abs_diff_eq!(parameter(Parameterization::Centripetal),
+                        array![0., 5f64.sqrt() / c, (c - 1.) / c, 1.], epsilon = 1e-15);
+}
+
+
+#[test]
+fn test_curve_interpolates_points() {
+    let points = circle_points(12, 2.);
+
+    let curve = CubicSmoothingCurve::new(&points)
+        .with_smooth(1.0)
+        .make().unwrap();
+
+    let u = curve.parameter().unwrap().to_owned();
+    assert_abs_diff_eq!(curve.evaluate(&u).unwrap(), points, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_curve_remake_recomputes_smooth() {
+    let points = circle_points(12, 2.);
+    let w = Array1::linspace(0.5, 1.5, 12);
+
+    let curve = CubicSmoothingCurve::new(&points).make().unwrap();
+    let smooth = curve.smooth().unwrap();
+
+    let expected = CubicSmoothingCurve::new(&points)
+        .with_weights(&w)
+        .with_parameterization(Parameterization::Uniform)
+        .make().unwrap()
+        .smooth().unwrap();
+
+    // The automatic smoothing parameter is computed again for the new parameter
+    let curve = curve.with_weights(&w).with_parameterization(Parameterization::Uniform).make().unwrap();
+
+    assert!((curve.smooth().unwrap() - smooth).abs() > 1e-6);
+    assert_abs_diff_eq!(curve.smooth().unwrap(), expected, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_curve_straight_line_arc_length() {
+    let points = array![[1., 1., 1.], [2., 3., 3.], [3., 5., 5.], [4., 7., 7.]];
+
+    let curve = CubicSmoothingCurve::new(&points)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(curve.length().unwrap(), 9., epsilon = 1e-12);
+
+    let s = array![0., 1.5, 3., 4.5, 9.];
+    let expected = array![
+        [1., 1., 1.],
+        [1.5, 2., 2.],
+        [2., 3., 3.],
+        [2.5, 4., 4.],
+        [4., 7., 7.],
+    ];
+
+    assert_abs_diff_eq!(curve.evaluate_arc_length(&s).unwrap(), expected, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_curve_arc_length_reparameterization() {
+    let points = circle_points(30, 3.);
+
+    let curve = CubicSmoothingCurve::new(&points)
+        .with_parameterization(Parameterization::Centripetal)
+        .with_smooth(1.0)
+        .make().unwrap();
+
+    let length = curve.length().unwrap();
+    assert_abs_diff_eq!(length, 3. * std::f64::consts::PI, epsilon = 1e-3);
+
+    let u = Array1::linspace(0., 1., 21);
+    let s = curve.arc_length(&u).unwrap();
+
+    assert_abs_diff_eq!(s[0], 0.);
+    assert_abs_diff_eq!(s[20], length, epsilon = 1e-12);
+    assert_abs_diff_eq!(curve.arc_length_parameter(&s).unwrap(), u, epsilon = 1e-10);
+
+    // The points at the uniform arc lengths are equally spaced along the circle
+    let ps = curve.evaluate_arc_length(&Array1::linspace(0., length, 11)).unwrap();
+    let chords: Vec<f64> = ps.axis_windows(Axis(0), 2).into_iter()
+        .map(|w| (&w.row(1) - &w.row(0)).mapv(|d| d * d).sum().sqrt())
+        .collect();
+
+    let chord_expected = 6. * (std::f64::consts::PI / 20.).sin();
+
+    for chord in chords {
+        assert_abs_diff_eq!(chord, chord_expected, epsilon = 1e-4);
+    }
+}
+
+
+#[test]
+fn test_curve_errors() {
+    let points = array![[0., 0.], [1., 1.], [1., 1.], [2., 0.]];
+
+    assert!(CubicSmoothingCurve::new(&points).make().is_err());
+    assert!(CubicSmoothingCurve::new(&points).with_parameterization(Parameterization::Uniform).make().is_ok());
+    assert!(CubicSmoothingCurve::new(&array![[0., 0.]]).make().is_err());
+    assert!(CubicSmoothingCurve::new(&array![[0., 0.], [1., f64::NAN]]).make().is_err());
+    assert!(CubicSmoothingCurve::new(&array![[0., 0.], [1., 1.]]).with_weights(&array![1.]).make().is_err());
+
+    let points = array![[0., 0.], [1., 1.], [2., 0.]];
+    let u = array![0.5];
+
+    assert!(CubicSmoothingCurve::new(&points).evaluate(&u).is_err());
+
+    let curve = CubicSmoothingCurve::new(&points).make().unwrap();
+    let length = curve.length().unwrap();
+
+    assert!(curve.evaluate(&Array1::<f64>::zeros(0)).is_err());
+    assert!(curve.evaluate_arc_length(&array![-0.1]).is_err());
+    assert!(curve.evaluate_arc_length(&array![length * 1.01]).is_err());
+    assert!(curve.evaluate_arc_length(&array![f64::NAN]).is_err());
+}
@@ -0,0 +1,124 @@
+use ndarray::{array, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::CubicSmoothingSpline;
+
+
+const EPS: f64 = 1e-08;
+
+
+#[test]
+fn test_derivative_order() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let spline = s.spline().unwrap();
+
+    for nu in 0..4 {
+        let ds = spline.derivative(nu);
+
+        assert_eq!(ds.order(), 4 - nu);
+        assert_eq!(ds.pieces(), spline.pieces());
+        assert_eq!(ds.ndim(), spline.ndim());
+    }
+
+    let ds = spline.derivative(4);
+
+    assert_eq!(ds.order(), 1);
+    assert_eq!(ds.coeffs(), array![[0., 0., 0., 0.]]);
+}
+
+
+#[test]
+fn test_evaluate_derivative_linear() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 3., 5., 7.];
+    let xi = array![0., 1., 1.5, 2., 2.5, 3., 3.5, 4., 5.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 1).unwrap(), Array1::from_elem(9, 2.));
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 2).unwrap(), Array1::zeros(9));
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 3).unwrap(), Array1::zeros(9));
+}
+
+
+#[test]
+fn test_evaluate_derivative_finite_diff() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4, 2.2, 1.6, 7.8, 9.1];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.7)
+        .make()
+        .unwrap();
+
+    let h = 1e-5;
+    let xi = array![1.2, 2.7, 4.1, 5.5, 8.3];
+    let xi_l = &xi - h;
+    let xi_r = &xi + h;
+
+    for nu in 1..4 {
+        let d = s.evaluate_derivative(&xi, nu).unwrap();
+        let dl = s.evaluate_derivative(&xi_l, nu - 1).unwrap();
+        let dr = s.evaluate_derivative(&xi_r, nu - 1).unwrap();
+
+        assert_abs_diff_eq!(d, (dr - dl) / (2. * h), epsilon = 1e-5);
+    }
+}
+
+
+#[test]
+fn test_evaluate_derivative_interpolant() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .make()
+        .unwrap();
+
+    // The natural cubic spline interpolant has zero 2nd derivative at the ends
+    let ends = array![1.0, 5.0];
+    let d2 = s.evaluate_derivative(&ends, 2).unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), y, epsilon = EPS);
+    assert_abs_diff_eq!(d2, array![0., 0.], epsilon = EPS);
+}
+
+
+#[test]
+fn test_evaluate_derivative_axis() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., 2., 3.],
+                   [2., 4., 6.],
+                   [3., 6., 9.],
+                   [4., 8., 12.]];
+
+    let dy = CubicSmoothingSpline::new(&x, &y)
+        .with_axis(Axis(0))
+        .make()
+        .unwrap()
+        .evaluate_derivative(&x, 1)
+        .unwrap();
+
+    assert_abs_diff_eq!(dy, array![[1., 2., 3.], [1., 2., 3.], [1., 2., 3.], [1., 2., 3.]], epsilon = EPS);
+}
+
+
+#[test]
+#[should_panic(expected = "The spline has not been computed, use `make` method before")]
+fn test_evaluate_derivative_not_valid_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .evaluate_derivative(&x, 1)
+        .unwrap();
+}
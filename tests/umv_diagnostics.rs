@@ -0,0 +1,152 @@
+mod common;
+
+use ndarray::{array, stack, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, CrossValidation};
+
+use common::noisy_sine;
+
+
+#[test]
+fn test_diagnostics_gcv() {
+    let (x, y) = noisy_sine();
+    let w = Array1::linspace(0.5, 1.5, 25);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make().unwrap();
+
+    let diagnostics = s.diagnostics().unwrap();
+    let residuals = &y - &s.evaluate(&x).unwrap();
+
+    assert_abs_diff_eq!(diagnostics.residuals(), residuals, epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.wrss(), (&residuals * &residuals * &w).sum(), epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.dof(), diagnostics.influence_diag().sum(), epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.gcv(), s.cv_score().unwrap(), epsilon = 1e-10);
+    assert!(diagnostics.influence_diag().iter().all(|&h| h > 0. && h < 1.));
+}
+
+
+#[test]
+fn test_diagnostics_straight_line() {
+    let x: Array1<f64> = array![0., 1., 2., 3., 4., 5.];
+    let y = array![0.1, 0.9, 2.2, 2.8, 4.1, 5.2];
+
+    let diagnostics = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.0)
+        .make().unwrap()
+        .diagnostics().unwrap();
+
+    let n = x.len() as f64;
+    let x_mean = x.mean().unwrap();
+    let sxx = x.mapv(|v| (v - x_mean).powi(2)).sum();
+    let leverage = x.mapv(|v| 1. / n + (v - x_mean).powi(2) / sxx);
+
+    assert_abs_diff_eq!(diagnostics.influence_diag(), leverage, epsilon = 1e-8);
+    assert_abs_diff_eq!(diagnostics.dof(), 2.0, epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_diagnostics_interpolant() {
+    let (x, y) = noisy_sine();
+
+    let diagnostics = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .make().unwrap()
+        .diagnostics().unwrap();
+
+    assert_abs_diff_eq!(diagnostics.residuals(), Array1::zeros(25), epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.influence_diag(), Array1::ones(25), epsilon = 1e-8);
+    assert_abs_diff_eq!(diagnostics.dof(), 25.0, epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_diagnostics_multivariate() {
+    let (x, y) = noisy_sine();
+    let y2 = stack![Axis(0), y, &y * 3.];
+
+    let s = CubicSmoothingSpline::new(&x, &y2)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make().unwrap();
+
+    let diagnostics = s.diagnostics().unwrap();
+
+    assert_eq!(diagnostics.residuals().shape(), &[2, 25]);
+    assert_eq!(diagnostics.series_wrss().shape(), &[2]);
+
+    let series_wrss = diagnostics.series_wrss();
+    assert_abs_diff_eq!(series_wrss[1], series_wrss[0] * 9., epsilon = 1e-10);
+    assert_abs_diff_eq!(diagnostics.wrss(), series_wrss.sum(), epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.series_dof()[0], diagnostics.series_dof()[1], epsilon = 1e-12);
+    assert_abs_diff_eq!(diagnostics.series_gcv()[1], diagnostics.series_gcv()[0] * 9., epsilon = 1e-10);
+    assert_abs_diff_eq!(diagnostics.gcv(), s.cv_score().unwrap(), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_diagnostics_periodic() {
+    let (x, y) = noisy_sine();
+
+    let diagnostics = CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .with_dof(8.0)
+        .make().unwrap()
+        .diagnostics().unwrap();
+
+    assert_abs_diff_eq!(diagnostics.dof(), 8.0, epsilon = 1e-6);
+    assert!(diagnostics.influence_diag().iter().all(|&h| h > 0. && h < 1.));
+}
+
+
+#[test]
+fn test_diagnostics_many_data_sites() {
+    let n = 20_000;
+    let x = Array1::<f64>::linspace(0., 100., n);
+    let y = x.mapv(|v| v.sin() + 0.1 * (v * 37.).cos());
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_normalized_smooth(true)
+        .with_smooth(0.5)
+        .make().unwrap();
+
+    let diagnostics = s.diagnostics().unwrap();
+    let residuals = &y - &s.evaluate(&x).unwrap();
+
+    assert_eq!(diagnostics.influence_diag().len(), n);
+    assert_abs_diff_eq!(diagnostics.residuals(), residuals, epsilon = 1e-12);
+    assert!(diagnostics.influence_diag().iter().all(|&h| h > 0. && h < 1.));
+}
+
+
+#[test]
+fn test_diagnostics_missing_values() {
+    let (x, mut y) = noisy_sine();
+    y[4] = f64::NAN;
+    y[11] = f64::NAN;
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make().unwrap();
+
+    let diagnostics = s.diagnostics().unwrap();
+
+    assert!(diagnostics.residuals()[4].is_nan());
+    assert_eq!(diagnostics.influence_diag()[11], 0.0);
+    assert!(diagnostics.wrss().is_finite());
+    assert_abs_diff_eq!(diagnostics.gcv(), s.cv_score().unwrap(), epsilon = 1e-10);
+}
+
+
+#[test]
+#[should_panic(expected = "The spline has not been computed, use `make` method before")]
+fn test_diagnostics_not_computed() {
+    let (x, y) = noisy_sine();
+
+    CubicSmoothingSpline::new(&x, &y)
+        .diagnostics()
+        .unwrap();
+}
@@ -0,0 +1,258 @@
+mod common;
+
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, EndCondition};
+
+use common::noisy_sine;
+
+
+#[test]
+fn test_clamped_interpolation_quadratic() {
+    let x = array![0., 1., 2.5, 3., 4.];
+    let y = x.mapv(|v| v * v);
+    let xi = Array1::linspace(-1., 5., 13);
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::Clamped(8.0))
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi, xi.mapv(|v| v * v), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_second_derivative_interpolation_quadratic() {
+    let x = array![0., 1., 2.5, 3., 4.];
+    let y = x.mapv(|v| v * v);
+    let xi = Array1::linspace(0., 4., 9);
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_end_conditions(EndCondition::SecondDerivative(2.0), EndCondition::SecondDerivative(2.0))
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi, xi.mapv(|v| v * v), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_not_a_knot_interpolation_cubic() {
+    let x = array![0., 0.5, 1.5, 2., 3., 4.5];
+    let y = x.mapv(|v| v * v * v - 2. * v);
+    let xi = Array1::linspace(0., 4.5, 10);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_end_conditions(EndCondition::NotAKnot, EndCondition::NotAKnot)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), xi.mapv(|v| v * v * v - 2. * v), epsilon = 1e-9);
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 3).unwrap(), Array1::from_elem(10, 6.), epsilon = 1e-9);
+}
+
+
+#[test]
+fn test_not_a_knot_three_points_parabola() {
+    let x = array![1., 2., 4.];
+    let y = array![1., 4., 16.];
+    let xi = array![0., 1.5, 3., 5.];
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_end_conditions(EndCondition::NotAKnot, EndCondition::NotAKnot)
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi, array![0., 2.25, 9., 25.], epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_clamped_two_points() {
+    let x = array![0., 1.];
+    let y = array![0., 1.];
+    let xi = array![0., 0.5, 1.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::Clamped(0.0))
+        .make().unwrap();
+
+    // Cubic Hermite polynomial 3x^2 - 2x^3
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), array![0., 0.5, 1.], epsilon = 1e-12);
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 1).unwrap(), array![0., 1.5, 0.], epsilon = 1e-12);
+    assert_eq!(s.smooth(), Some(1.0));
+}
+
+
+#[test]
+fn test_clamped_two_points_smooth() {
+    let x = array![0., 1.];
+    let y = array![1., 2.];
+
+    let make = |smooth| {
+        CubicSmoothingSpline::new(&x, &y)
+            .with_smooth(smooth)
+            .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::Clamped(0.5))
+            .make().unwrap()
+    };
+
+    let s = make(0.3);
+
+    // The smoothed values are between the data values and the least squares limit
+    let yi = s.evaluate(&x).unwrap();
+    assert!(yi[0] > 1. && yi[0] < 1.375);
+    assert!(yi[1] > 1.625 && yi[1] < 2.);
+    assert_abs_diff_eq!(s.evaluate_derivative(&x, 1).unwrap(), array![0., 0.5], epsilon = 1e-12);
+    assert_eq!(s.smooth(), Some(0.3));
+
+    // The least squares limit is the parabola with the given end slopes centered at the mean value
+    let s = make(0.0);
+
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), array![1.375, 1.625], epsilon = 1e-12);
+    assert_eq!(s.smooth(), Some(0.0));
+}
+
+
+#[test]
+fn test_smoothing_end_conditions_hold() {
+    let (x, y) = noisy_sine();
+    let bounds = array![0., 6.];
+
+    for &smooth in &[0.1, 0.5, 0.9, 1.0] {
+        let s = CubicSmoothingSpline::new(&x, &y)
+            .with_smooth(smooth)
+            .with_end_conditions(EndCondition::Clamped(1.0), EndCondition::SecondDerivative(0.5))
+            .make().unwrap();
+
+        let d1 = s.evaluate_derivative(&bounds, 1).unwrap();
+        let d2 = s.evaluate_derivative(&bounds, 2).unwrap();
+
+        assert_abs_diff_eq!(d1[0], 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(d2[1], 0.5, epsilon = 1e-9);
+    }
+}
+
+
+#[test]
+fn test_natural_conditions_consistency() {
+    let (x, y) = noisy_sine();
+    let w = Array1::linspace(0.5, 1.5, 25);
+
+    for &smooth in &[0.3, 0.8, 0.99, 1.0] {
+        let yi_natural = CubicSmoothingSpline::new(&x, &y)
+            .with_weights(&w)
+            .with_smooth(smooth)
+            .make().unwrap()
+            .evaluate(&x).unwrap();
+
+        // The zero second derivative condition is computed by the constrained system
+        let yi = CubicSmoothingSpline::new(&x, &y)
+            .with_weights(&w)
+            .with_smooth(smooth)
+            .with_end_conditions(EndCondition::Natural, EndCondition::SecondDerivative(0.0))
+            .make().unwrap()
+            .evaluate(&x).unwrap();
+
+        assert_abs_diff_eq!(yi, yi_natural, epsilon = 1e-9);
+    }
+}
+
+
+#[test]
+fn test_zero_smooth_limit() {
+    let (x, y) = noisy_sine();
+
+    let yi_natural = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.0)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.0)
+        .with_end_conditions(EndCondition::SecondDerivative(0.0), EndCondition::Natural)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    assert_abs_diff_eq!(yi, yi_natural, epsilon = 1e-9);
+
+    let bounds = array![0., 6.];
+
+    let d1 = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.0)
+        .with_end_conditions(EndCondition::Clamped(1.0), EndCondition::Clamped(-1.0))
+        .make().unwrap()
+        .evaluate_derivative(&bounds, 1).unwrap();
+
+    assert_abs_diff_eq!(d1, array![1.0, -1.0], epsilon = 1e-6);
+}
+
+
+#[test]
+fn test_clamped_multivariate() {
+    let x = array![0., 1., 2., 3.];
+    let y = array![
+        [0., 1., 4., 9.],
+        [0., -1., -4., -9.],
+    ];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.7)
+        .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::NotAKnot)
+        .make().unwrap();
+
+    let x0 = array![0.];
+    let d1 = s.evaluate_derivative(&x0, 1).unwrap();
+    assert_abs_diff_eq!(d1, array![[0.], [0.]], epsilon = 1e-10);
+
+    let yi = s.evaluate(&x).unwrap();
+    assert_abs_diff_eq!(yi.row(0), -&yi.row(1), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_auto_smooth_with_end_conditions() {
+    let (x, y) = noisy_sine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    let s_clamped = CubicSmoothingSpline::new(&x, &y)
+        .with_end_conditions(EndCondition::Clamped(1.0), EndCondition::Clamped(0.96))
+        .make().unwrap();
+
+    assert_eq!(s.smooth(), s_clamped.smooth());
+    assert_eq!(s_clamped.end_conditions(), (EndCondition::Clamped(1.0), EndCondition::Clamped(0.96)));
+}
+
+
+#[test]
+#[should_panic(expected = "The end condition value must be finite, given NaN")]
+fn test_end_condition_value_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_end_conditions(EndCondition::Natural, EndCondition::Clamped(f64::NAN))
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The normalized smoothing parameter 0.3 cannot be mapped for 2 data sites")]
+fn test_two_points_normalized_smooth_error() {
+    let x = array![0., 1.];
+    let y = array![1., 2.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.3)
+        .with_normalized_smooth(true)
+        .with_end_conditions(EndCondition::Clamped(0.0), EndCondition::Natural)
+        .make()
+        .unwrap();
+}
@@ -1,5 +1,7 @@
-use ndarray::array;
-use csaps::CubicSmoothingSpline;
+use ndarray::{array, s, Array1, Array2, Array3, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, Extrapolation, NdSpline};
 
 
 #[test]
@@ -63,3 +65,233 @@ fn test_evaluate_3d() {
 
     assert_eq!(ys, y);
 }
+
+
+#[test]
+fn test_evaluate_owned_spline() {
+    fn make_spline() -> NdSpline<f64> {
+        let x = array![1., 2., 3., 4.];
+        let y = array![[1., 2., 3., 4.], [2., 4., 6., 8.]];
+
+        CubicSmoothingSpline::new(&x, &y)
+            .make()
+            .unwrap()
+            .into_spline()
+            .unwrap()
+    }
+
+    let spline = make_spline();
+
+    let handle = std::thread::spawn(move || {
+        let xi = array![1., 2.5, 4.];
+        spline.evaluate(xi.view())
+    });
+
+    let yi = handle.join().unwrap();
+
+    assert_eq!(yi, array![[1., 2.5, 4.], [2., 5., 8.]]);
+}
+
+
+#[test]
+fn test_evaluate_unsorted() {
+    let x = array![0., 0.4, 1.5, 2., 3.7, 5.];
+    let y = array![[1., 3., 2., 5., 4., 1.], [0., -1., 2., 1., -2., 0.]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap();
+
+    let xi = Array1::linspace(-1., 6., 71);
+    let xi_rev = xi.slice(s![..;-1]).to_owned();
+
+    let yi = s.evaluate(&xi).unwrap();
+    let yi_rev = s.evaluate(&xi_rev).unwrap();
+
+    assert_abs_diff_eq!(yi_rev.slice(s![.., ..;-1]), yi, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_evaluate_nan() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 3., 2., 4.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    let xi = array![2.5, f64::NAN, 1.5];
+    let xi_valid = array![2.5, 1.5];
+
+    let yi = s.evaluate(&xi).unwrap();
+    let yi_valid = s.evaluate(&xi_valid).unwrap();
+
+    assert!(yi[1].is_nan());
+    assert_abs_diff_eq!(yi[0], yi_valid[0]);
+    assert_abs_diff_eq!(yi[2], yi_valid[1]);
+}
+
+
+#[test]
+fn test_evaluate_into() {
+    let x = array![1., 2., 3., 4., 5.];
+    let y = array![[1., 3., 2., 4., 3.], [2., 1., 4., 3., 5.]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+    let xi = Array1::linspace(0., 6., 25);
+
+    let mut yi = Array2::<f64>::zeros((2, 25));
+
+    spline.evaluate_into(xi.view(), &mut yi).unwrap();
+    assert_eq!(yi, spline.evaluate(xi.view()));
+
+    yi.fill(0.);
+
+    s.evaluate_into(&xi, &mut yi).unwrap();
+    assert_eq!(yi, s.evaluate(&xi).unwrap());
+
+    // The output may be a strided view
+    let mut yi_t = Array2::<f64>::zeros((25, 2));
+
+    s.evaluate_into(&xi, yi_t.view_mut().reversed_axes()).unwrap();
+    assert_eq!(yi_t.t(), yi);
+}
+
+
+#[test]
+fn test_evaluate_into_axis_extrapolated() {
+    let x = Array1::linspace(0., 4., 9);
+    let y = Array3::from_shape_fn((2, 9, 3), |(i, j, k)| (x[j] * (1 + i + k) as f64).sin());
+
+    for extrapolation in [Extrapolation::Nan, Extrapolation::Constant, Extrapolation::Linear] {
+        let s = CubicSmoothingSpline::new(&x, &y)
+            .with_axis(Axis(1))
+            .with_extrapolation(extrapolation)
+            .make().unwrap();
+
+        let xi = array![-1., 0.5, 2.2, 3.9, 5.];
+        let mut yi = Array3::<f64>::zeros((2, 5, 3));
+
+        s.evaluate_into(&xi, &mut yi).unwrap();
+
+        let expected = s.evaluate(&xi).unwrap();
+
+        for (v, e) in yi.iter().zip(expected.iter()) {
+            assert!(v == e || (v.is_nan() && e.is_nan()));
+        }
+    }
+}
+
+
+#[test]
+fn test_evaluate_into_periodic() {
+    let x = Array1::linspace(0., std::f64::consts::TAU, 21);
+    let y = x.mapv(f64::sin);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let xi = Array1::linspace(-7., 14., 50);
+    let mut yi = Array1::<f64>::zeros(50);
+
+    s.evaluate_into(&xi, &mut yi).unwrap();
+    assert_eq!(yi, s.evaluate(&xi).unwrap());
+}
+
+
+#[test]
+fn test_evaluate_into_shape_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., 3., 2., 4.], [2., 1., 4., 3.]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    let xi = array![1.5, 2.5, 3.5];
+
+    assert!(s.evaluate_into(&xi, &mut Array2::<f64>::zeros((2, 4))).is_err());
+    assert!(s.evaluate_into(&xi, &mut Array2::<f64>::zeros((3, 2))).is_err());
+    assert!(s.spline().unwrap().evaluate_into(xi.view(), &mut Array2::<f64>::zeros((1, 3))).is_err());
+    assert!(CubicSmoothingSpline::new(&x, &y).evaluate_into(&xi, &mut Array2::<f64>::zeros((2, 3))).is_err());
+}
+
+
+#[test]
+fn test_eval_at() {
+    let x = array![1., 2., 3., 4., 5.];
+    let y = array![1., 3., 2., 4., 3.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+
+    let xi = array![3.3, 0., 1., 2.5, 5., 7., 4.9, f64::NAN];
+    let yi = spline.evaluate(xi.view());
+
+    for (k, &x) in xi.iter().enumerate() {
+        let v = spline.eval_at(x).unwrap();
+        assert!(v == yi[[0, k]] || (v.is_nan() && yi[[0, k]].is_nan()));
+    }
+
+    let yi_iter: Vec<f64> = spline.eval_iter(xi.iter().copied().take(7)).unwrap().collect();
+    assert_eq!(yi_iter, yi.slice(s![0, ..7]).to_vec());
+}
+
+
+#[test]
+fn test_eval_at_multivariate_periodic() {
+    let x = Array1::linspace(0., std::f64::consts::TAU, 15);
+    let y = ndarray::stack![Axis(0), x.mapv(f64::sin), x.mapv(f64::cos)];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+
+    let xi = array![-4., 1., 8., 20.];
+    let yi = spline.evaluate(xi.view());
+
+    let mut out = [0.; 2];
+
+    for (k, &x) in xi.iter().enumerate() {
+        spline.eval_at_into(x, &mut out).unwrap();
+        assert_eq!(out.to_vec(), yi.column(k).to_vec());
+    }
+}
+
+
+#[test]
+#[should_panic(expected = "`eval_at` requires the univariate spline, the spline dimensionality is 2")]
+fn test_eval_at_multivariate_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., 3., 2., 4.], [2., 1., 4., 3.]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    s.spline().unwrap().eval_at(2.5).unwrap();
+}
+
+
+#[test]
+fn test_eval_at_errors() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., 3., 2., 4.], [2., 1., 4., 3.]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+
+    assert!(spline.eval_iter([2.5]).is_err());
+    assert!(spline.eval_at_into(2.5, &mut [0.; 3]).is_err());
+    assert!(spline.eval_at_into(2.5, &mut [0.; 2]).is_ok());
+}
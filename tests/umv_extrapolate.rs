@@ -0,0 +1,132 @@
+use ndarray::array;
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, Extrapolation};
+
+
+const EPS: f64 = 1e-08;
+
+
+#[test]
+fn test_extrapolation_polynomial() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+    let xi = array![-1.0, 0.5, 2.5, 5.5, 7.0];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let yi_default = s.evaluate(&xi).unwrap();
+    let yi = s.spline().unwrap().evaluate_extrapolated(xi.view(), Extrapolation::Polynomial).unwrap();
+
+    assert_eq!(s.extrapolation(), Extrapolation::Polynomial);
+    assert_abs_diff_eq!(yi_default, yi.row(0));
+}
+
+
+#[test]
+fn test_extrapolation_linear() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+    let xi = array![-1.0, 0.5, 2.5, 5.5, 7.0];
+    let bounds = array![1.0, 5.0];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .with_extrapolation(Extrapolation::Linear)
+        .make()
+        .unwrap();
+
+    let v = s.evaluate(&bounds).unwrap();
+    let d = s.evaluate_derivative(&bounds, 1).unwrap();
+
+    let yi = s.evaluate(&xi).unwrap();
+    let yi_expected = array![
+        v[0] - 2.0 * d[0],
+        v[0] - 0.5 * d[0],
+        yi[2],
+        v[1] + 0.5 * d[1],
+        v[1] + 2.0 * d[1],
+    ];
+
+    assert_abs_diff_eq!(yi, yi_expected, epsilon = EPS);
+
+    let dyi = s.evaluate_derivative(&xi, 1).unwrap();
+    assert_abs_diff_eq!(dyi[0], d[0], epsilon = EPS);
+    assert_abs_diff_eq!(dyi[4], d[1], epsilon = EPS);
+
+    let d2yi = s.evaluate_derivative(&xi, 2).unwrap();
+    assert_abs_diff_eq!(d2yi[0], 0.0);
+    assert_abs_diff_eq!(d2yi[4], 0.0);
+}
+
+
+#[test]
+fn test_extrapolation_constant() {
+    let x = array![1.0, 2.0, 3.0, 4.0];
+    let y = array![[1., 3., 2., 4.], [2., 1., 5., 3.]];
+    let xi = array![0.0, 1.0, 4.0, 10.0];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_extrapolation(Extrapolation::Constant)
+        .make()
+        .unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), array![[1., 1., 4., 4.], [2., 2., 3., 3.]], epsilon = EPS);
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 1).unwrap().column(0), array![0., 0.]);
+}
+
+
+#[test]
+fn test_extrapolation_nan() {
+    let x = array![1.0, 2.0, 3.0, 4.0];
+    let y = array![1., 3., 2., 4.];
+    let xi = array![0.0f64, 1.0, 2.5, 4.0, 4.5];
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Nan)
+        .make()
+        .unwrap()
+        .evaluate(&xi)
+        .unwrap();
+
+    assert!(yi[0].is_nan());
+    assert!(yi[1].is_finite());
+    assert!(yi[2].is_finite());
+    assert!(yi[3].is_finite());
+    assert!(yi[4].is_nan());
+}
+
+
+#[test]
+fn test_extrapolation_error_in_range() {
+    let x = array![1.0, 2.0, 3.0, 4.0];
+    let y = array![1., 2., 3., 4.];
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Error)
+        .make()
+        .unwrap()
+        .evaluate(&x)
+        .unwrap();
+
+    assert_abs_diff_eq!(yi, y, epsilon = EPS);
+}
+
+
+#[test]
+#[should_panic(expected = "`xi` value 4.5 is out of the breaks range [1.0, 4.0]")]
+fn test_extrapolation_error() {
+    let x = array![1.0, 2.0, 3.0, 4.0];
+    let y = array![1., 2., 3., 4.];
+    let xi = array![1.0, 2.5, 4.5];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_extrapolation(Extrapolation::Error)
+        .make()
+        .unwrap()
+        .evaluate(&xi)
+        .unwrap();
+}
@@ -0,0 +1,79 @@
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, NdSpline};
+
+
+#[test]
+fn test_parabola_geometry() {
+    // (u, u^2) on [0, 1]
+    let spline = NdSpline::new(&array![0., 1.], array![[0., 1., 0.], [1., 0., 0.]]);
+    let u = array![0., 0.5, 1.];
+
+    let speed = u.mapv(|u: f64| (1. + 4. * u * u).sqrt());
+
+    assert_abs_diff_eq!(spline.length(), (2. * 5f64.sqrt() + 2f64.asinh()) / 4., epsilon = 1e-12);
+    assert_abs_diff_eq!(spline.curvature(u.view()), speed.mapv(|s| 2. / s.powi(3)), epsilon = 1e-12);
+
+    let tangent = spline.tangent(u.view());
+    assert_abs_diff_eq!(tangent.row(0), speed.mapv(|s| 1. / s), epsilon = 1e-12);
+    assert_abs_diff_eq!(tangent.row(1), &u * 2. / &speed, epsilon = 1e-12);
+
+    let normal = spline.normal(u.view());
+    assert_abs_diff_eq!(normal.row(0), -&u * 2. / &speed, epsilon = 1e-12);
+    assert_abs_diff_eq!(normal.row(1), speed.mapv(|s| 1. / s), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_twisted_cubic_torsion() {
+    // (u, u^2, u^3) on [0, 1]
+    let spline = NdSpline::new(&array![0., 1.], array![[0., 0., 1., 0.], [0., 1., 0., 0.], [1., 0., 0., 0.]]);
+    let u: Array1<f64> = Array1::linspace(0., 1., 5);
+
+    let torsion = spline.torsion(u.view()).unwrap();
+    assert_abs_diff_eq!(torsion, u.mapv(|u| 3. / (9. * u.powi(4) + 9. * u * u + 1.)), epsilon = 1e-12);
+
+    let curvature = spline.curvature(u.view());
+    let expected = u.mapv(|u| {
+        let cross2 = 36. * u.powi(4) + 36. * u * u + 4.;
+        cross2.sqrt() / (1. + 4. * u * u + 9. * u.powi(4)).powf(1.5)
+    });
+    assert_abs_diff_eq!(curvature, expected, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_circle_arc_length() {
+    let t = Array1::linspace(0., std::f64::consts::TAU, 41);
+    let y = ndarray::stack![ndarray::Axis(0), t.mapv(|t| 2. * t.cos()), t.mapv(|t| 2. * t.sin())];
+
+    let s = CubicSmoothingSpline::new(&t, &y)
+        .with_smooth(1.0)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let spline = s.spline().unwrap();
+    let length = spline.length();
+
+    assert_abs_diff_eq!(length, 4. * std::f64::consts::PI, epsilon = 1e-4);
+
+    let u = array![0., std::f64::consts::PI, -std::f64::consts::PI, 3. * std::f64::consts::TAU];
+    assert_abs_diff_eq!(spline.arc_length(u.view()), array![0., 0.5, -0.5, 3.] * length, epsilon = 1e-4);
+
+    let ui = Array1::linspace(0., std::f64::consts::TAU, 13);
+    assert_abs_diff_eq!(spline.curvature(ui.view()), Array1::from_elem(13, 0.5), epsilon = 5e-3);
+}
+
+
+#[test]
+fn test_geometry_degenerate() {
+    // The straight line has zero curvature and undefined normal
+    let spline = NdSpline::new(&array![0., 1., 2.], array![[0., 0., 1., 1., 0., 1.], [0., 0., 2., 2., 0., 2.]]);
+    let u = array![0.5, 1.5];
+
+    assert_abs_diff_eq!(spline.length(), 2. * 5f64.sqrt(), epsilon = 1e-12);
+    assert_abs_diff_eq!(spline.curvature(u.view()), array![0., 0.]);
+    assert!(spline.normal(u.view()).iter().all(|v| v.is_nan()));
+    assert!(spline.torsion(u.view()).is_err());
+}
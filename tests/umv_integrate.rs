@@ -0,0 +1,151 @@
+use ndarray::{array, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::CubicSmoothingSpline;
+
+
+const EPS: f64 = 1e-08;
+
+
+#[test]
+fn test_antiderivative_inverse_of_derivative() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    let y = array![[1.5, 3.5, 2.6, 1.2, 4.4, 2.2, 1.6, 7.8, 9.1],
+                   [2.5, 1.5, 2.1, 3.2, 2.4, 1.2, 3.6, 5.8, 2.1]];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let spline = s.spline().unwrap();
+    let is = spline.antiderivative();
+
+    assert_eq!(is.order(), 5);
+    assert_eq!(is.pieces(), spline.pieces());
+    assert_eq!(is.ndim(), 2);
+
+    let xi = Array1::linspace(0., 10., 51);
+
+    assert_abs_diff_eq!(is.derivative(1).coeffs(), spline.coeffs(), epsilon = EPS);
+    assert_abs_diff_eq!(is.evaluate_derivative(xi.view(), 1), spline.evaluate(xi.view()), epsilon = EPS);
+}
+
+
+#[test]
+fn test_antiderivative_continuity() {
+    let x = array![1.0, 2.0, 3.5, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .make()
+        .unwrap();
+
+    let is = s.spline().unwrap().antiderivative();
+
+    let h = 1e-9;
+    let inner = array![2.0, 3.5, 4.0];
+
+    let left = is.evaluate((&inner - h).view());
+    let right = is.evaluate((&inner + h).view());
+
+    assert_abs_diff_eq!(is.evaluate(x.slice(ndarray::s![..1])), array![[0.]]);
+    assert_abs_diff_eq!(left, right, epsilon = 1e-7);
+}
+
+
+#[test]
+fn test_integrate_linear() {
+    let x = array![0., 1., 2., 3.];
+    let y = array![1., 3., 5., 7.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    assert_abs_diff_eq!(s.integrate(0., 3.).unwrap().into_scalar(), 12., epsilon = EPS);
+    assert_abs_diff_eq!(s.integrate(3., 0.).unwrap().into_scalar(), -12., epsilon = EPS);
+    assert_abs_diff_eq!(s.integrate(0.5, 2.5).unwrap().into_scalar(), 8., epsilon = EPS);
+    assert_abs_diff_eq!(s.integrate(-1., 0.).unwrap().into_scalar(), 0., epsilon = EPS);
+}
+
+
+#[test]
+fn test_integrate_simpson() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4, 2.2, 1.6, 7.8, 9.1];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    // Simpson's rule is exact for cubic polynomials on every piece
+    let n = 8 * 2;
+    let xi = Array1::linspace(1., 9., n + 1);
+    let yi = s.evaluate(&xi).unwrap();
+    let h = 8. / n as f64;
+
+    let simpson = (0..n / 2)
+        .map(|i| h / 3. * (yi[2 * i] + 4. * yi[2 * i + 1] + yi[2 * i + 2]))
+        .sum::<f64>();
+
+    assert_abs_diff_eq!(s.integrate(1., 9.).unwrap().into_scalar(), simpson, epsilon = EPS);
+}
+
+
+#[test]
+fn test_integrate_additivity() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![1.5, 3.5, 2.6, 1.2, 4.4];
+
+    let spline = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let s = spline.spline().unwrap();
+
+    assert_abs_diff_eq!(s.integrate(1.2, 4.7), &s.integrate(1.2, 2.5) + &s.integrate(2.5, 4.7), epsilon = EPS);
+}
+
+
+#[test]
+fn test_integrate_axis() {
+    let x = array![0., 1., 2., 3.];
+    let y = array![[[1., 2.], [2., 4.], [3., 6.], [4., 8.]],
+                   [[0., 1.], [0., 1.], [0., 1.], [0., 1.]]];
+
+    let area = CubicSmoothingSpline::new(&x, &y)
+        .with_axis(Axis(1))
+        .make()
+        .unwrap()
+        .integrate(0., 3.)
+        .unwrap();
+
+    assert_abs_diff_eq!(area, array![[7.5, 15.], [0., 3.]], epsilon = EPS);
+}
+
+
+#[test]
+#[should_panic(expected = "The integration bounds must be finite")]
+fn test_integrate_not_finite_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap()
+        .integrate(1., f64::INFINITY)
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The spline has not been computed, use `make` method before")]
+fn test_integrate_not_valid_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .integrate(1., 2.)
+        .unwrap();
+}
@@ -1,8 +1,5 @@
-use std::ops::{Add, Mul};
-
 use ndarray::{array, Array, Array2, Dimension, Array1};
 use csaps::{Real, CubicSmoothingSpline, RealRef};
-use sprs::MulAcc;
 
 
 fn test_driver_make_nd_npt<T, D>(x: Array1<T>, y: Array<T, D>,
@@ -0,0 +1,284 @@
+mod common;
+
+use ndarray::{array, stack, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, CrossValidation, EndCondition, MissingValues, RobustLoss};
+
+use common::noisy_sine;
+
+
+fn with_gaps(y: &Array1<f64>, gaps: &[usize]) -> Array1<f64> {
+    let mut y = y.clone();
+    gaps.iter().for_each(|&i| y[i] = f64::NAN);
+    y
+}
+
+
+fn without_gaps(v: &Array1<f64>, gaps: &[usize]) -> Array1<f64> {
+    v.iter().enumerate().filter(|(i, _)| !gaps.contains(i)).map(|(_, &v)| v).collect()
+}
+
+
+#[test]
+fn test_ignore_interior_gaps() {
+    let (x, y) = noisy_sine();
+    let gaps = [3, 4, 10, 17];
+
+    let y_gaps = with_gaps(&y, &gaps);
+    let x_valid = without_gaps(&x, &gaps);
+    let y_valid = without_gaps(&y, &gaps);
+    let xi = Array1::linspace(0., 6., 61);
+
+    for &smooth in &[0.0, 0.5, 0.9, 1.0] {
+        let yi = CubicSmoothingSpline::new(&x, &y_gaps)
+            .with_smooth(smooth)
+            .make().unwrap()
+            .evaluate(&xi).unwrap();
+
+        let yi_valid = CubicSmoothingSpline::new(&x_valid, &y_valid)
+            .with_smooth(smooth)
+            .make().unwrap()
+            .evaluate(&xi).unwrap();
+
+        assert_abs_diff_eq!(yi, yi_valid, epsilon = 1e-8);
+    }
+}
+
+
+#[test]
+fn test_ignore_end_gaps() {
+    let (x, y) = noisy_sine();
+    let gaps = [0, 1, 23, 24];
+
+    let y_gaps = with_gaps(&y, &gaps);
+    let x_valid = without_gaps(&x, &gaps);
+    let y_valid = without_gaps(&y, &gaps);
+
+    let s = CubicSmoothingSpline::new(&x, &y_gaps)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    let s_valid = CubicSmoothingSpline::new(&x_valid, &y_valid)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    // The spline is defined over the whole data sites range
+    assert_eq!(s.spline().unwrap().breaks(), x.view());
+
+    let xi = Array1::linspace(x_valid[0], x_valid[x_valid.len() - 1], 41);
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), s_valid.evaluate(&xi).unwrap(), epsilon = 1e-8);
+
+    // The spline is linear in the end gaps
+    let xi_ends = array![0., 0.2, 5.8, 6.];
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi_ends, 2).unwrap(), Array1::zeros(4), epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_ignore_auto_smooth() {
+    let (x, y) = noisy_sine();
+    let gaps = [5, 12];
+
+    let y_gaps = with_gaps(&y, &gaps);
+    let x_valid = without_gaps(&x, &gaps);
+    let y_valid = without_gaps(&y, &gaps);
+
+    let s = CubicSmoothingSpline::new(&x, &y_gaps)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make().unwrap();
+
+    let s_valid = CubicSmoothingSpline::new(&x_valid, &y_valid)
+        .with_cross_validation(CrossValidation::Generalized)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(s.smooth().unwrap(), s_valid.smooth().unwrap(), epsilon = 1e-12);
+    assert_abs_diff_eq!(s.cv_score().unwrap(), s_valid.cv_score().unwrap(), epsilon = 1e-12);
+    assert_abs_diff_eq!(s.evaluate(&x_valid).unwrap(), s_valid.evaluate(&x_valid).unwrap(), epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_ignore_two_complete_sites_smooth() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., f64::NAN, f64::NAN, 4.];
+
+    let make = |missing_values, smooth| {
+        CubicSmoothingSpline::new(&x, &y)
+            .with_missing_values(missing_values)
+            .with_smooth(smooth)
+            .with_end_conditions(EndCondition::Clamped(0.), EndCondition::Clamped(0.))
+            .make().unwrap()
+    };
+
+    let s = make(MissingValues::Ignore, 0.3);
+    let s_series = make(MissingValues::PerSeries, 0.3);
+
+    assert_eq!(s.smooth(), Some(0.3));
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), s_series.evaluate(&x).unwrap(), epsilon = 1e-12);
+
+    let x_complete = array![1., 4.];
+    let yi = s.evaluate(&x_complete).unwrap();
+    let yi_interp = make(MissingValues::Ignore, 1.0).evaluate(&x_complete).unwrap();
+
+    assert_abs_diff_eq!(yi_interp, array![1., 4.], epsilon = 1e-12);
+    assert!((yi[0] - 1.).abs() > 1e-3 && (yi[1] - 4.).abs() > 1e-3);
+}
+
+
+#[test]
+fn test_ignore_multivariate() {
+    let (x, y) = noisy_sine();
+
+    let y0 = with_gaps(&y, &[3]);
+    let y1 = with_gaps(&x.mapv(f64::cos), &[7]);
+    let y2 = stack![Axis(0), y0, y1];
+
+    let s = CubicSmoothingSpline::new(&x, &y2)
+        .with_smooth(0.7)
+        .make().unwrap();
+
+    // The data sites 3 and 7 are ignored for both series
+    let yi = s.evaluate(&x).unwrap();
+
+    let yi0 = CubicSmoothingSpline::new(&x, &with_gaps(&y, &[3, 7]))
+        .with_smooth(0.7)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    assert_abs_diff_eq!(yi.row(0), yi0, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_per_series_multivariate() {
+    let (x, y) = noisy_sine();
+
+    let y0 = with_gaps(&y, &[3, 4]);
+    let y1 = with_gaps(&x.mapv(f64::cos), &[0, 7, 20]);
+    let y2 = stack![Axis(0), y0, y1];
+
+    let s = CubicSmoothingSpline::new(&x, &y2)
+        .with_smooth(0.7)
+        .with_missing_values(MissingValues::PerSeries)
+        .make().unwrap();
+
+    assert_eq!(s.missing_values(), MissingValues::PerSeries);
+
+    let yi = s.evaluate(&x).unwrap();
+
+    for (series, yi_series) in [y0, y1].iter().zip(yi.outer_iter()) {
+        let yi_expected = CubicSmoothingSpline::new(&x, series)
+            .with_smooth(0.7)
+            .make().unwrap()
+            .evaluate(&x).unwrap();
+
+        assert_abs_diff_eq!(yi_series, yi_expected, epsilon = 1e-10);
+    }
+
+    // The smoothing parameter is selected for all data sites
+    let s_auto = CubicSmoothingSpline::new(&x, &y2)
+        .with_missing_values(MissingValues::PerSeries)
+        .make().unwrap();
+
+    let s_full = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    assert_eq!(s_auto.smooth(), s_full.smooth());
+}
+
+
+#[test]
+fn test_missing_robust() {
+    let (x, mut y) = noisy_sine();
+    y[9] += 3.0;
+    let y = with_gaps(&y, &[2, 15]);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_robust(RobustLoss::Bisquare(4.685))
+        .make().unwrap();
+
+    let outliers = s.outliers().unwrap();
+    assert!(outliers[9]);
+    assert!(!outliers[2] && !outliers[15]);
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), x.mapv(f64::sin), epsilon = 0.15);
+}
+
+
+#[test]
+#[should_panic(expected = "`y` data contains missing (NaN) values")]
+fn test_missing_values_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., f64::NAN, 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_missing_values(MissingValues::Error)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The number of the data sites without missing values (1) must be greater or equal to 2")]
+fn test_missing_values_count_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., f64::NAN, 3., 4.], [f64::NAN, 2., f64::NAN, 5.]];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The number of not missing values (1) must be greater or equal to 2 for `y` data series 1")]
+fn test_missing_values_per_series_count_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![[1., f64::NAN, 3., 4.], [f64::NAN, 2., f64::NAN, f64::NAN]];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_missing_values(MissingValues::PerSeries)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The smoothing parameter cannot be selected by the residual tolerance or cross-validation")]
+fn test_missing_values_per_series_cv_error() {
+    let x = array![1., 2., 3., 4., 5.];
+    let y = array![1., f64::NAN, 3., 4., 5.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_missing_values(MissingValues::PerSeries)
+        .with_cross_validation(CrossValidation::LeaveOneOut)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The smoothing parameter cannot be selected by the degrees of freedom")]
+fn test_missing_values_two_complete_sites_dof_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., f64::NAN, f64::NAN, 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_dof(2.5)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The missing (NaN) values in `y` data are not supported for the periodic spline")]
+fn test_missing_values_periodic_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., f64::NAN, 3., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make()
+        .unwrap();
+}
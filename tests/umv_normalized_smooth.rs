@@ -0,0 +1,152 @@
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, GridCubicSmoothingSpline};
+
+
+#[test]
+fn test_normalized_smooth_scale_invariance() {
+    let x = array![1.0, 2.0, 3.5, 4.0, 5.5, 7.0];
+    let y = array![0.5, 1.2, 3.4, 2.5, 1.8, 2.2];
+
+    let evaluate = |x: &Array1<f64>| {
+        CubicSmoothingSpline::new(x, &y)
+            .with_smooth(0.3)
+            .with_normalized_smooth(true)
+            .make()
+            .unwrap()
+            .evaluate(x)
+            .unwrap()
+    };
+
+    let ys = evaluate(&x);
+    let ys_scaled = evaluate(&(&x * 1000.0));
+    let ys_shifted = evaluate(&(&x * 0.01 - 5.0));
+
+    assert_abs_diff_eq!(ys, ys_scaled, epsilon = 1e-8);
+    assert_abs_diff_eq!(ys, ys_shifted, epsilon = 1e-8);
+
+    // Without normalization the spline depends on the data scale
+    let ys_raw = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.3)
+        .make()
+        .unwrap()
+        .evaluate(&x)
+        .unwrap();
+
+    let x_scaled = &x * 1000.0;
+    let ys_raw_scaled = CubicSmoothingSpline::new(&x_scaled, &y)
+        .with_smooth(0.3)
+        .make()
+        .unwrap()
+        .evaluate(&x_scaled)
+        .unwrap();
+
+    assert!((ys_raw - ys_raw_scaled).mapv(f64::abs).sum() > 1e-2);
+}
+
+
+#[test]
+fn test_normalized_smooth_default() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![0.5, 1.2, 3.4, 2.5, 1.8];
+
+    let s_auto = CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_normalized_smooth(true)
+        .make()
+        .unwrap();
+
+    assert!(s.normalized_smooth());
+    assert_abs_diff_eq!(s.smooth().unwrap(), 0.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), s_auto.evaluate(&x).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_normalized_smooth_bounds() {
+    let x = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = array![0.5, 1.2, 3.4, 2.5, 1.8];
+
+    for &smooth in &[0.0, 1.0] {
+        let ys_raw = CubicSmoothingSpline::new(&x, &y)
+            .with_smooth(smooth)
+            .make()
+            .unwrap()
+            .evaluate(&x)
+            .unwrap();
+
+        let s = CubicSmoothingSpline::new(&x, &y)
+            .with_smooth(smooth)
+            .with_normalized_smooth(true)
+            .make()
+            .unwrap();
+
+        assert_eq!(s.smooth(), Some(smooth));
+        assert_abs_diff_eq!(s.evaluate(&x).unwrap(), ys_raw, epsilon = 1e-12);
+    }
+}
+
+
+#[test]
+fn test_normalized_smooth_dof() {
+    let x = Array1::linspace(0., 10., 20);
+    let y = x.mapv(f64::cos);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(5.0)
+        .with_normalized_smooth(true)
+        .make()
+        .unwrap();
+
+    // The selected smoothing parameter is reported in the normalized scale
+    let ys = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(s.smooth().unwrap())
+        .with_normalized_smooth(true)
+        .make()
+        .unwrap()
+        .evaluate(&x)
+        .unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), ys, epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_normalized_smooth_grid() {
+    let x0 = array![1., 2., 3., 4.];
+    let x1 = array![1., 2., 3., 4., 5.];
+    let x0_scaled = &x0 * 100.0;
+    let x1_scaled = &x1 * 0.01;
+
+    let y = array![
+        [0.5, 1.2, 3.4, 2.5, 1.8],
+        [1.5, 2.2, 1.4, 3.5, 2.8],
+        [0.3, 2.2, 2.4, 1.5, 0.8],
+        [1.1, 0.2, 3.1, 2.5, 1.2],
+    ];
+
+    let x = vec![x0.view(), x1.view()];
+    let x_scaled = vec![x0_scaled.view(), x1_scaled.view()];
+
+    let ys = GridCubicSmoothingSpline::new(&x, &y)
+        .with_smooth_fill(0.4)
+        .with_normalized_smooth(true)
+        .make()
+        .unwrap()
+        .evaluate(&x)
+        .unwrap();
+
+    let ys_scaled = GridCubicSmoothingSpline::new(&x_scaled, &y)
+        .with_smooth_fill(0.4)
+        .with_normalized_smooth(true)
+        .make()
+        .unwrap()
+        .evaluate(&x_scaled)
+        .unwrap();
+
+    assert_abs_diff_eq!(ys, ys_scaled, epsilon = 1e-8);
+}
@@ -0,0 +1,181 @@
+use std::f64::consts::PI;
+
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, CrossValidation, EndCondition};
+
+
+fn noisy_cosine() -> (Array1<f64>, Array1<f64>) {
+    let x = Array1::linspace(0., 2. * PI, 21);
+    let noise = array![
+        0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04,
+        0.01, -0.05, 0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, 0.05];
+    let y = x.mapv(f64::cos) + noise;
+
+    (x, y)
+}
+
+
+#[test]
+fn test_periodic_interpolation_continuity() {
+    let x = array![0., 0.7, 1.5, 2.6, 3.2, 4.4, 5.1, 2. * PI];
+    let y = x.mapv(|v| v.sin() + 0.5 * (2. * v).cos());
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_periodic(true)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), y, epsilon = 1e-12);
+
+    // The values and the derivatives at the end of the period and at the beginning are the same
+    let ends = array![2. * PI - 1e-9, 1e-9];
+
+    for nu in 0..3 {
+        let d = s.evaluate_derivative(&ends, nu).unwrap();
+        assert_abs_diff_eq!(d[0], d[1], epsilon = 1e-6);
+    }
+}
+
+
+#[test]
+fn test_periodic_sine() {
+    let x = Array1::linspace(0., 2. * PI, 25);
+    let y = x.mapv(f64::sin);
+    let xi = Array1::linspace(0., 2. * PI, 101);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_periodic(true)
+        .make().unwrap();
+
+    assert!(s.periodic());
+    assert!(s.spline().unwrap().periodic());
+
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), xi.mapv(f64::sin), epsilon = 1e-4);
+    assert_abs_diff_eq!(s.evaluate_derivative(&xi, 1).unwrap(), xi.mapv(f64::cos), epsilon = 1e-3);
+}
+
+
+#[test]
+fn test_periodic_wrapping_evaluation() {
+    let (x, y) = noisy_cosine();
+    let xi = Array1::linspace(0.1, 6.1, 13);
+    let shifts: Vec<_> = [-3., -1., 1., 2.].iter().map(|k| &xi + 2. * PI * k).collect();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let yi = s.evaluate(&xi).unwrap();
+
+    for xi_shifted in &shifts {
+        assert_abs_diff_eq!(s.evaluate(xi_shifted).unwrap(), yi, epsilon = 1e-9);
+        assert_abs_diff_eq!(s.evaluate_derivative(xi_shifted, 1).unwrap(),
+                            s.evaluate_derivative(&xi, 1).unwrap(), epsilon = 1e-9);
+    }
+}
+
+
+#[test]
+fn test_periodic_zero_smooth_mean() {
+    let x = array![0., 1., 2., 3., 4.];
+    let y = array![1., 3., 2., 6., 3.];
+    let w = array![1., 2., 1., 1., 3.];
+    let xi = array![0.5, 2.5, 7.];
+
+    let yi = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_smooth(0.0)
+        .with_periodic(true)
+        .make().unwrap()
+        .evaluate(&xi).unwrap();
+
+    // The first and the last data sites are merged into one with the weight 4
+    let mean = (1. * 1. + 3. * 2. + 2. * 1. + 6. * 1. + 3. * 3.) / 8.;
+
+    assert_abs_diff_eq!(yi, Array1::from_elem(3, mean), epsilon = 1e-6);
+}
+
+
+#[test]
+fn test_periodic_integrate() {
+    let x = Array1::linspace(0., 2., 9);
+    let y = x.mapv(|v| 1. + (PI * v).sin());
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let period = s.integrate(0., 2.).unwrap()[()];
+
+    assert_abs_diff_eq!(period, 2., epsilon = 1e-10);
+    assert_abs_diff_eq!(s.integrate(-2., 4.).unwrap()[()], 3. * period, epsilon = 1e-10);
+    assert_abs_diff_eq!(s.integrate(0.5, 2.5).unwrap()[()], period, epsilon = 1e-10);
+    assert_abs_diff_eq!(s.integrate(2.5, 0.5).unwrap()[()], -period, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_periodic_select_smooth() {
+    let (x, y) = noisy_cosine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(6.0)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let smooth = s.smooth().unwrap();
+    assert!(smooth > 0. && smooth < 1.);
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_cross_validation(CrossValidation::Generalized)
+        .with_periodic(true)
+        .make().unwrap();
+
+    assert!(s.cv_score().unwrap().is_finite());
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), x.mapv(f64::cos), epsilon = 0.1);
+}
+
+
+#[test]
+#[should_panic(expected = "The end conditions cannot be set for the periodic spline")]
+fn test_periodic_end_conditions_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .with_end_conditions(EndCondition::NotAKnot, EndCondition::Natural)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The size of data vectors must be greater or equal to 3 for the periodic spline")]
+fn test_periodic_size_error() {
+    let x = array![1., 2.];
+    let y = array![1., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "`dof` value must be in range 1..3, given 4.0")]
+fn test_periodic_dof_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_periodic(true)
+        .with_dof(4.0)
+        .make()
+        .unwrap();
+}
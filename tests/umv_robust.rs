@@ -0,0 +1,147 @@
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, RobustLoss};
+
+
+fn sine_with_spikes() -> (Array1<f64>, Array1<f64>) {
+    let x = Array1::linspace(0., 6., 25);
+    let noise = array![
+        0.05, -0.08, 0.02, 0.07, -0.04, -0.06, 0.03, 0.08, -0.02, -0.07, 0.04, 0.01, -0.05,
+        0.06, -0.03, 0.02, -0.08, 0.05, 0.07, -0.01, -0.06, 0.03, 0.04, -0.07, 0.02];
+    let mut y = x.mapv(f64::sin) + noise;
+
+    y[6] += 2.5;
+    y[17] -= 3.0;
+
+    (x, y)
+}
+
+
+#[test]
+fn test_bisquare_rejects_spikes() {
+    let (x, y) = sine_with_spikes();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_robust(RobustLoss::Bisquare(4.685))
+        .make().unwrap();
+
+    let outliers: Vec<usize> = s.outliers().unwrap().iter().enumerate()
+        .filter(|(_, &o)| o)
+        .map(|(i, _)| i)
+        .collect();
+
+    assert_eq!(outliers, vec![6, 17]);
+
+    let rw = s.robustness_weights().unwrap();
+    assert_eq!(rw[6], 0.);
+    assert_eq!(rw[17], 0.);
+    assert!(rw.iter().enumerate().all(|(i, &w)| i == 6 || i == 17 || w > 0.5));
+
+    // The spikes do not drag the spline
+    let yi = s.evaluate(&x).unwrap();
+    assert_abs_diff_eq!(yi, x.mapv(f64::sin), epsilon = 0.15);
+}
+
+
+#[test]
+fn test_huber_downweights_spikes() {
+    let (x, y) = sine_with_spikes();
+
+    let yi_plain = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_robust(RobustLoss::Huber(1.345))
+        .make().unwrap();
+
+    let rw = s.robustness_weights().unwrap();
+    assert!(rw[6] > 0. && rw[6] < 0.2);
+    assert!(rw[17] > 0. && rw[17] < 0.2);
+    assert!(s.outliers().unwrap()[6] && s.outliers().unwrap()[17]);
+
+    let yi = s.evaluate(&x).unwrap();
+    let sine = x.mapv(f64::sin);
+
+    let err = |v: &Array1<f64>| (v - &sine).mapv(f64::abs).fold(0., |a: f64, &b| a.max(b));
+    assert!(err(&yi) < err(&yi_plain));
+}
+
+
+#[test]
+fn test_robust_without_outliers() {
+    let (x, mut y) = sine_with_spikes();
+    y[6] -= 2.5;
+    y[17] += 3.0;
+
+    let yi_plain = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_robust(RobustLoss::Bisquare(4.685))
+        .make().unwrap();
+
+    assert!(s.outliers().unwrap().iter().all(|&o| !o));
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), yi_plain, epsilon = 0.05);
+}
+
+
+#[test]
+fn test_robust_zero_iterations() {
+    let (x, y) = sine_with_spikes();
+
+    let yi_plain = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .make().unwrap()
+        .evaluate(&x).unwrap();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.9)
+        .with_robust(RobustLoss::Huber(1.345))
+        .with_robust_iterations(0)
+        .make().unwrap();
+
+    assert_eq!(s.robust_iterations(), 0);
+    assert_eq!(s.robustness_weights().unwrap(), Array1::<f64>::ones(25));
+    assert_abs_diff_eq!(s.evaluate(&x).unwrap(), yi_plain, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_robust_auto_smooth_multivariate() {
+    let (x, y) = sine_with_spikes();
+    let y2 = ndarray::stack![ndarray::Axis(0), y, x.mapv(f64::cos)];
+
+    let s = CubicSmoothingSpline::new(&x, &y2)
+        .with_robust(RobustLoss::Bisquare(4.685))
+        .make().unwrap();
+
+    assert!(s.smooth().is_some());
+    assert!(s.outliers().unwrap()[6] && s.outliers().unwrap()[17]);
+
+    let s = CubicSmoothingSpline::new(&x, &y2)
+        .make().unwrap();
+
+    assert!(s.robustness_weights().is_none());
+    assert!(s.outliers().is_none());
+}
+
+
+#[test]
+#[should_panic(expected = "The robust loss tuning constant must be positive and finite, given -1.0")]
+fn test_robust_tuning_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_robust(RobustLoss::Huber(-1.0))
+        .make()
+        .unwrap();
+}
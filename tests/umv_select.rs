@@ -0,0 +1,207 @@
+mod common;
+
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::CubicSmoothingSpline;
+
+use common::noisy_sine;
+
+
+#[test]
+fn test_dof_bounds() {
+    let (x, y) = noisy_sine();
+
+    let smooth = |dof| {
+        CubicSmoothingSpline::new(&x, &y)
+            .with_dof(dof)
+            .make()
+            .unwrap()
+            .smooth()
+            .unwrap()
+    };
+
+    assert_abs_diff_eq!(smooth(2.0), 0.0);
+    assert_abs_diff_eq!(smooth(25.0), 1.0);
+}
+
+
+#[test]
+fn test_dof_monotonic() {
+    let (x, y) = noisy_sine();
+
+    let smooth = |dof| {
+        CubicSmoothingSpline::new(&x, &y)
+            .with_dof(dof)
+            .make()
+            .unwrap()
+            .smooth()
+            .unwrap()
+    };
+
+    let s1 = smooth(4.0);
+    let s2 = smooth(8.0);
+    let s3 = smooth(16.0);
+
+    assert!(0. < s1 && s1 < s2 && s2 < s3 && s3 < 1.);
+}
+
+
+#[test]
+fn test_dof_weights_invariance() {
+    let (x, y) = noisy_sine();
+    let w = Array1::from_elem(x.len(), 3.0);
+
+    // Scaling all weights does not change the influence matrix for the same dof
+    let s1 = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(6.0)
+        .make()
+        .unwrap();
+
+    let s2 = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_dof(6.0)
+        .make()
+        .unwrap();
+
+    assert_abs_diff_eq!(s1.evaluate(&x).unwrap(), s2.evaluate(&x).unwrap(), epsilon = 1e-8);
+}
+
+
+#[test]
+fn test_remake_reselects_smooth() {
+    let (x, y) = noisy_sine();
+    let w = Array1::linspace(0.5, 1.5, x.len());
+
+    let expected = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_dof(6.0)
+        .make()
+        .unwrap()
+        .smooth()
+        .unwrap();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(6.0)
+        .make()
+        .unwrap();
+
+    let smooth = s.smooth().unwrap();
+
+    // The smoothing parameter is selected again for the new weights
+    let s = s.with_weights(&w).make().unwrap();
+
+    assert!((s.smooth().unwrap() - smooth).abs() > 1e-6);
+    assert_abs_diff_eq!(s.smooth().unwrap(), expected, epsilon = 1e-12);
+
+    // The explicit smoothing parameter resets the selection criteria
+    let s = s.with_smooth(0.5).make().unwrap();
+    assert_eq!(s.smooth(), Some(0.5));
+
+    let s = s.with_dof(6.0).with_weights(&w).make().unwrap();
+    assert_abs_diff_eq!(s.smooth().unwrap(), expected, epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_tolerance() {
+    let (x, y) = noisy_sine();
+    let w = Array1::linspace(0.5, 1.5, x.len());
+    let tolerance = 0.05;
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_tolerance(tolerance)
+        .make()
+        .unwrap();
+
+    let smooth = s.smooth().unwrap();
+    assert!(smooth > 0. && smooth < 1.);
+
+    let residuals = &y - &s.evaluate(&x).unwrap();
+    let rss = (&w * &residuals * &residuals).sum();
+
+    assert_abs_diff_eq!(rss, tolerance, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_tolerance_bounds() {
+    let (x, y) = noisy_sine();
+
+    let smooth = |tolerance| {
+        CubicSmoothingSpline::new(&x, &y)
+            .with_tolerance(tolerance)
+            .make()
+            .unwrap()
+            .smooth()
+            .unwrap()
+    };
+
+    assert_abs_diff_eq!(smooth(0.0), 1.0);
+    assert_abs_diff_eq!(smooth(1e6), 0.0);
+}
+
+
+#[test]
+fn test_tolerance_multivariate() {
+    let x = array![1., 2., 3., 4., 5., 6.];
+    let y = array![[1., 3., 2., 4., 3., 5.], [2., 1., 4., 3., 5., 4.]];
+    let tolerance = 0.5;
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_tolerance(tolerance)
+        .make()
+        .unwrap();
+
+    let residuals = &y - &s.evaluate(&x).unwrap();
+    assert_abs_diff_eq!((&residuals * &residuals).sum(), tolerance, epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_criteria_reset() {
+    let (x, y) = noisy_sine();
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(6.0)
+        .with_smooth(0.5)
+        .make()
+        .unwrap();
+
+    assert_eq!(s.smooth(), Some(0.5));
+
+    let s1 = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.5)
+        .with_tolerance(0.0)
+        .make()
+        .unwrap();
+
+    assert_abs_diff_eq!(s1.smooth().unwrap(), 1.0);
+}
+
+
+#[test]
+#[should_panic(expected = "`dof` value must be in range 2..4, given 5.0")]
+fn test_dof_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 3., 2., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_dof(5.0)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "`tolerance` value must be non-negative and finite, given -1.0")]
+fn test_tolerance_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 3., 2., 4.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_tolerance(-1.0)
+        .make()
+        .unwrap();
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use ndarray::{array, Array1};
+use approx::assert_abs_diff_eq;
+
+use csaps::{CubicSmoothingSpline, CubicSmoother};
+
+use common::data_series;
+
+
+#[test]
+fn test_smoother_equals_spline() {
+    let x = Array1::linspace(0., 6., 25);
+    let w = Array1::from_shape_fn(25, |i| 0.5 + (i % 3) as f64 * 0.25);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_weights(&w)
+        .with_smooth(0.9)
+        .make().unwrap();
+
+    let xi = Array1::linspace(0., 6., 61);
+
+    for m in [1, 3, 10] {
+        let y = data_series(&x, m);
+
+        let spline = smoother.fit(&y).unwrap();
+        let s = CubicSmoothingSpline::new(&x, &y)
+            .with_weights(&w)
+            .with_smooth(0.9)
+            .make().unwrap();
+
+        assert_eq!(spline.smooth(), Some(0.9));
+        assert_abs_diff_eq!(spline.evaluate(xi.view()), s.evaluate(&xi).unwrap(), epsilon = 1e-12);
+    }
+}
+
+
+#[test]
+fn test_smoother_auto_smooth() {
+    let x = Array1::linspace(0., 60., 40);
+    let y = data_series(&x, 4);
+
+    let smoother = CubicSmoother::new(&x).make().unwrap();
+    let s = CubicSmoothingSpline::new(&x, &y).make().unwrap();
+
+    assert_abs_diff_eq!(smoother.smooth().unwrap(), s.smooth().unwrap(), epsilon = 1e-15);
+
+    let spline = smoother.fit(&y).unwrap();
+    assert_abs_diff_eq!(spline.evaluate(x.view()), s.evaluate(&x).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_smoother_normalized_and_dof() {
+    let x = Array1::linspace(0., 6., 30);
+    let y = data_series(&x, 2);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_smooth(0.3)
+        .with_normalized_smooth(true)
+        .make().unwrap();
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.3)
+        .with_normalized_smooth(true)
+        .make().unwrap();
+
+    assert_eq!(smoother.smooth(), Some(0.3));
+    assert_abs_diff_eq!(smoother.fit(&y).unwrap().evaluate(x.view()), s.evaluate(&x).unwrap(), epsilon = 1e-12);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_dof(8.0)
+        .make().unwrap();
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_dof(8.0)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(smoother.smooth().unwrap(), s.smooth().unwrap(), epsilon = 1e-12);
+    assert_abs_diff_eq!(smoother.fit(&y).unwrap().evaluate(x.view()), s.evaluate(&x).unwrap(), epsilon = 1e-10);
+}
+
+
+#[test]
+fn test_smoother_remake_reselects_smooth() {
+    let x = Array1::linspace(0., 6., 30);
+    let y = data_series(&x, 2);
+    let w = Array1::linspace(0.5, 1.5, 30);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_dof(8.0)
+        .make().unwrap();
+
+    let smooth = smoother.smooth().unwrap();
+
+    // The smoothing parameter is selected again for the new weights
+    let smoother = smoother.with_weights(&w).make().unwrap();
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_dof(8.0)
+        .make().unwrap();
+
+    assert!((smoother.smooth().unwrap() - smooth).abs() > 1e-6);
+    assert_abs_diff_eq!(smoother.smooth().unwrap(), s.smooth().unwrap(), epsilon = 1e-12);
+    assert_abs_diff_eq!(smoother.fit(&y).unwrap().evaluate(x.view()), s.evaluate(&x).unwrap(), epsilon = 1e-10);
+
+    // The explicit smoothing parameter resets the target degrees of freedom
+    let smoother = smoother.with_smooth(0.5).make().unwrap();
+    assert_eq!(smoother.smooth(), Some(0.5));
+
+    let smoother = smoother.with_dof(8.0).make().unwrap();
+    assert_abs_diff_eq!(smoother.smooth().unwrap(), s.smooth().unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_smoother_periodic() {
+    let x = Array1::linspace(0., std::f64::consts::TAU, 21);
+    let mut y = data_series(&x, 3);
+    let first = y.column(0).to_owned();
+    y.column_mut(20).assign(&first);
+
+    let smoother = CubicSmoother::new(&x)
+        .with_smooth(0.95)
+        .with_periodic(true)
+        .make().unwrap();
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.95)
+        .with_periodic(true)
+        .make().unwrap();
+
+    let spline = smoother.fit(&y).unwrap();
+    assert!(spline.periodic());
+
+    let xi = Array1::linspace(-1., 8., 50);
+    assert_abs_diff_eq!(spline.evaluate(xi.view()), s.evaluate(&xi).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_smoother_two_sites() {
+    let x = array![1., 3.];
+    let y = array![[1., 5.], [2., -2.]];
+
+    let smoother = CubicSmoother::new(&x).with_smooth(0.5).make().unwrap();
+    let spline = smoother.fit(&y).unwrap();
+
+    assert_eq!(smoother.smooth(), Some(1.0));
+    assert_abs_diff_eq!(spline.evaluate(array![2.].view()), array![[3.], [0.]], epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_smoother_errors() {
+    let x = array![1., 2., 3., 4.];
+
+    assert!(CubicSmoother::new(&x).with_smooth(1.5).make().is_err());
+    assert!(CubicSmoother::new(&x).with_weights(&array![1., 1.]).make().is_err());
+    assert!(CubicSmoother::new(&x).with_weights(&array![1., 0., 1., 1.]).make().is_err());
+    assert!(CubicSmoother::new(&x).with_weights(&array![1., -1., 1., 1.]).make().is_err());
+    assert!(CubicSmoother::new(&x).with_weights(&array![1., f64::INFINITY, 1., 1.]).make().is_err());
+    assert!(CubicSmoother::new(&array![1., 3., 2.]).make().is_err());
+    assert!(CubicSmoother::new(&x).with_dof(5.0).make().is_err());
+
+    let smoother = CubicSmoother::new(&x).make().unwrap();
+
+    assert!(smoother.fit(&array![[1., 2., 3.]]).is_err());
+    assert!(smoother.fit(&array![[1., f64::NAN, 3., 4.]]).is_err());
+    assert!(CubicSmoother::new(&x).fit(&array![[1., 2., 3., 4.]]).is_err());
+}
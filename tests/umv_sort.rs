@@ -0,0 +1,191 @@
+use ndarray::{array, Array1, Axis};
+use approx::assert_abs_diff_eq;
+
+use csaps::CubicSmoothingSpline;
+
+
+#[test]
+fn test_sort_unsorted() {
+    let x = array![3., 1., 5., 2., 4.];
+    let y = array![2.5, 1.2, 1.8, 3.4, 0.5];
+    let w = array![0.5, 1.0, 0.7, 1.0, 0.9];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_smooth(0.8)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    assert!(s.sort_data_sites());
+
+    let x_sorted = array![1., 2., 3., 4., 5.];
+    let y_sorted = array![1.2, 3.4, 2.5, 0.5, 1.8];
+    let w_sorted = array![1.0, 1.0, 0.5, 0.9, 0.7];
+
+    let s_sorted = CubicSmoothingSpline::new(&x_sorted, &y_sorted)
+        .with_weights(&w_sorted)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    assert_eq!(s.spline().unwrap().breaks(), x_sorted);
+
+    let xi = Array1::linspace(1., 5., 21);
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), s_sorted.evaluate(&xi).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_sort_sorted_is_unchanged() {
+    let x = array![1., 2., 3., 4., 5.];
+    let y = array![1.2, 3.4, 2.5, 0.5, 1.8];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    let s_default = CubicSmoothingSpline::new(&x, &y)
+        .make().unwrap();
+
+    assert_eq!(s.smooth(), s_default.smooth());
+    assert_eq!(s.spline().unwrap().coeffs(), s_default.spline().unwrap().coeffs());
+}
+
+
+#[test]
+fn test_sort_merge_duplicates() {
+    let x = array![1., 3., 2., 3., 4., 1.];
+    let y = array![1., 2., 4., 6., 5., 3.];
+    let w = array![1., 3., 1., 1., 1., 1.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .with_smooth(0.9)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    // The weighted means of the values and the sums of the weights of the duplicate sites
+    let x_merged = array![1., 2., 3., 4.];
+    let y_merged = array![2., 4., 3., 5.];
+    let w_merged = array![2., 1., 4., 1.];
+
+    let s_merged = CubicSmoothingSpline::new(&x_merged, &y_merged)
+        .with_weights(&w_merged)
+        .with_smooth(0.9)
+        .make().unwrap();
+
+    assert_eq!(s.spline().unwrap().breaks(), x_merged);
+    assert_abs_diff_eq!(s.evaluate(&x_merged).unwrap(), s_merged.evaluate(&x_merged).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_sort_merge_almost_equal() {
+    let x = array![1., 2., 2. + f64::EPSILON, 3.];
+    let y = array![1., 1., 3., 3.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    assert_eq!(s.spline().unwrap().breaks(), array![1., 2., 3.]);
+    assert_abs_diff_eq!(s.evaluate(&array![2.]).unwrap(), array![2.], epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_sort_multivariate_axis() {
+    let x = array![2., 1., 4., 3.];
+    let y = array![
+        [2., 20.],
+        [1., 10.],
+        [4., 40.],
+        [3., 30.],
+    ];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_axis(Axis(0))
+        .with_smooth(1.0)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    let xi = array![1., 1.5, 2.5, 4.];
+    let yi = s.evaluate(&xi).unwrap();
+
+    assert_abs_diff_eq!(yi, array![[1., 10.], [1.5, 15.], [2.5, 25.], [4., 40.]], epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_sort_merge_missing_values() {
+    let x = array![1., 2., 2., 3., 4.];
+    let y = array![1., f64::NAN, 2., 3., 4.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(1.0)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    assert_abs_diff_eq!(s.evaluate(&array![2.]).unwrap(), array![2.], epsilon = 1e-12);
+}
+
+
+#[test]
+fn test_sort_merge_missing_values_weights() {
+    let x = array![1., 2., 2., 3., 4., 5.];
+    let y = array![1., f64::NAN, 2., 3., 1., 4.];
+
+    let s = CubicSmoothingSpline::new(&x, &y)
+        .with_smooth(0.8)
+        .with_sort_data_sites(true)
+        .make().unwrap();
+
+    // The missing value does not increase the weight of the merged value
+    let x_merged = array![1., 2., 3., 4., 5.];
+    let y_merged = array![1., 2., 3., 1., 4.];
+
+    let s_merged = CubicSmoothingSpline::new(&x_merged, &y_merged)
+        .with_smooth(0.8)
+        .make().unwrap();
+
+    let xi = Array1::linspace(1., 5., 17);
+    assert_abs_diff_eq!(s.evaluate(&xi).unwrap(), s_merged.evaluate(&xi).unwrap(), epsilon = 1e-12);
+}
+
+
+#[test]
+#[should_panic(expected = "Data site values must satisfy the condition: x1 < x2 < ... < xN")]
+fn test_sort_disabled_error() {
+    let x = array![1., 3., 2.];
+    let y = array![1., 2., 3.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "The size of data vectors must be greater or equal to 2")]
+fn test_sort_unique_sites_error() {
+    let x = array![1., 1., 1.];
+    let y = array![1., 2., 3.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_sort_data_sites(true)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "Data site values must be finite")]
+fn test_sort_not_finite_error() {
+    let x = array![1., f64::NAN, 2.];
+    let y = array![1., 2., 3.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_sort_data_sites(true)
+        .make()
+        .unwrap();
+}
@@ -77,6 +77,34 @@ fn test_weights_size_mismatch_error() {
 }
 
 
+#[test]
+#[should_panic(expected = "`weights` values must be positive and finite, given 0.0")]
+fn test_weights_zero_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+    let w = array![1., 0., 1., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .make()
+        .unwrap();
+}
+
+
+#[test]
+#[should_panic(expected = "`weights` values must be positive and finite, given -1.0")]
+fn test_weights_negative_error() {
+    let x = array![1., 2., 3., 4.];
+    let y = array![1., 2., 3., 4.];
+    let w = array![1., 1., -1., 1.];
+
+    CubicSmoothingSpline::new(&x, &y)
+        .with_weights(&w)
+        .make()
+        .unwrap();
+}
+
+
 #[test]
 #[should_panic(expected = "`smooth` value must be in range 0..1, given -0.5")]
 fn test_smooth_less_than_error() {